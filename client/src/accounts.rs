@@ -0,0 +1,24 @@
+use v1::{
+    errors::SolanaCoreError,
+    states::{Config, Pool, Referrer},
+    utils::load_acc_unchecked,
+};
+
+/// Decodes raw `Pool` account data, checking its length and discriminator.
+pub fn decode_pool(data: &[u8]) -> Result<Pool, SolanaCoreError> {
+    // `Pool` is made of byte arrays only (alignment 1), so reading it straight out of
+    // an arbitrary `&[u8]` is sound once the length has been checked.
+    unsafe { load_acc_unchecked::<Pool>(data) }.copied()
+}
+
+/// Decodes raw `Referrer` account data, checking its length and discriminator.
+pub fn decode_referrer(data: &[u8]) -> Result<Referrer, SolanaCoreError> {
+    // all byte arrays too, see `decode_pool`
+    unsafe { load_acc_unchecked::<Referrer>(data) }.copied()
+}
+
+/// Decodes raw `Config` account data, checking its length and discriminator.
+pub fn decode_config(data: &[u8]) -> Result<Config, SolanaCoreError> {
+    // all byte arrays too, see `decode_pool`
+    unsafe { load_acc_unchecked::<Config>(data) }.copied()
+}
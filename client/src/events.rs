@@ -0,0 +1,173 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use v1::{
+    errors::SolanaCoreError,
+    events::{
+        decode_event, DepositEvent, Event, FlashLoanEvent, MultiDepositEvent, MultiSwapEvent, MultiWithdrawEvent,
+        PoolCreatedEvent, PoolFeeUpdatedEvent, ReferralFeeEvent, SwapEvent, WithdrawEvent,
+    },
+};
+
+use crate::PROGRAM_ID;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AmmEvent {
+    PoolCreated(PoolCreatedEvent),
+    Swap(SwapEvent),
+    Deposit(DepositEvent),
+    Withdraw(WithdrawEvent),
+    FlashLoan(FlashLoanEvent),
+    PoolFeeUpdated(PoolFeeUpdatedEvent),
+    ReferralFee(ReferralFeeEvent),
+    MultiSwap(MultiSwapEvent),
+    MultiDeposit(MultiDepositEvent),
+    MultiWithdraw(MultiWithdrawEvent),
+}
+
+/// Decodes the raw bytes of one `Program data:` entry. Returns `None` when the
+/// discriminator is not one of the AMM's events.
+pub fn decode_amm_event(data: &[u8]) -> Option<Result<AmmEvent, SolanaCoreError>> {
+    let discriminator: [u8; 8] = data.get(..8)?.try_into().ok()?;
+    let event = match discriminator {
+        PoolCreatedEvent::DISCRIMINATOR => decode_event(data).map(AmmEvent::PoolCreated),
+        SwapEvent::DISCRIMINATOR => decode_event(data).map(AmmEvent::Swap),
+        DepositEvent::DISCRIMINATOR => decode_event(data).map(AmmEvent::Deposit),
+        WithdrawEvent::DISCRIMINATOR => decode_event(data).map(AmmEvent::Withdraw),
+        FlashLoanEvent::DISCRIMINATOR => decode_event(data).map(AmmEvent::FlashLoan),
+        PoolFeeUpdatedEvent::DISCRIMINATOR => decode_event(data).map(AmmEvent::PoolFeeUpdated),
+        ReferralFeeEvent::DISCRIMINATOR => decode_event(data).map(AmmEvent::ReferralFee),
+        MultiSwapEvent::DISCRIMINATOR => decode_event(data).map(AmmEvent::MultiSwap),
+        MultiDepositEvent::DISCRIMINATOR => decode_event(data).map(AmmEvent::MultiDeposit),
+        MultiWithdrawEvent::DISCRIMINATOR => decode_event(data).map(AmmEvent::MultiWithdraw),
+        _ => return None,
+    };
+    Some(event)
+}
+
+/// Extracts the AMM events from a transaction's log messages, in emission order.
+///
+/// Only `Program data:` lines logged while the AMM program is the innermost running
+/// program are considered, so other programs' events with colliding bytes are ignored.
+pub fn parse_logs<S: AsRef<str>>(logs: &[S]) -> Vec<AmmEvent> {
+    let program_id = PROGRAM_ID.to_string();
+    let mut stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for line in logs {
+        let line = line.as_ref();
+        if let Some(rest) = line.strip_prefix("Program data: ") {
+            if stack.last() != Some(&program_id.as_str()) {
+                continue;
+            }
+            for chunk in rest.split(' ') {
+                let Ok(bytes) = STANDARD.decode(chunk) else { continue };
+                if let Some(Ok(event)) = decode_amm_event(&bytes) {
+                    events.push(event);
+                }
+            }
+        } else if let Some(rest) = line.strip_prefix("Program ") {
+            let mut words = rest.split(' ');
+            let (Some(id), Some(action)) = (words.next(), words.next()) else { continue };
+            match action {
+                "invoke" => stack.push(id),
+                "success" | "failed:" => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use v1::events::{EVENT_HEADER_LEN, EVENT_VERSION};
+    use v1::utils::ByteWriter;
+
+    fn encode<T: Event>(event: &T) -> String {
+        let mut data = vec![0u8; EVENT_HEADER_LEN + T::LEN];
+        let mut writer = ByteWriter::new(&mut data);
+        writer.write_bytes(&T::DISCRIMINATOR).unwrap();
+        writer.write_u8(EVENT_VERSION).unwrap();
+        event.write(&mut writer).unwrap();
+        STANDARD.encode(data)
+    }
+
+    #[test]
+    fn test_parse_logs_only_decodes_amm_frames() {
+        let swap = SwapEvent {
+            pool: [1; 32],
+            user: [2; 32],
+            direction: 0,
+            amount_in: 1_000,
+            amount_out: 1_990,
+            fee: 3,
+            reserve_0_after: 1_001_000,
+            reserve_1_after: 1_998_010,
+            fees_bps: Some(30),
+        };
+        let data = encode(&swap);
+        let other = "11111111111111111111111111111111";
+        let logs = vec![
+            format!("Program {PROGRAM_ID} invoke [1]"),
+            "Program log: Instruction: Swap".to_string(),
+            format!("Program {other} invoke [2]"),
+            format!("Program data: {data}"),
+            format!("Program {other} success"),
+            format!("Program data: {data}"),
+            format!("Program {PROGRAM_ID} consumed 12345 of 200000 compute units"),
+            format!("Program {PROGRAM_ID} success"),
+        ];
+
+        assert_eq!(parse_logs(&logs), vec![AmmEvent::Swap(swap)]);
+    }
+    #[test]
+    fn test_decode_multi_pool_events() {
+        let swap = MultiSwapEvent {
+            pool: [1; 32],
+            user: [2; 32],
+            token_in: 0,
+            token_out: 2,
+            amount_in: 1_000,
+            amount_out: 997,
+            fee: 3,
+            reserve_in_after: 1_001_000,
+            reserve_out_after: 999_003,
+        };
+        let mut amounts = [0; 8];
+        amounts[..3].copy_from_slice(&[100, 200, 300]);
+        let mut reserves_after = [0; 8];
+        reserves_after[..3].copy_from_slice(&[1_100, 2_200, 3_300]);
+        let deposit = MultiDepositEvent {
+            pool: [1; 32],
+            user: [2; 32],
+            token_count: 3,
+            amounts,
+            lp_amount: 50,
+            reserves_after,
+            lp_supply_after: 550,
+        };
+        let withdraw = MultiWithdrawEvent {
+            pool: [1; 32],
+            user: [2; 32],
+            token_count: 3,
+            amounts,
+            lp_amount: 50,
+            reserves_after,
+            lp_supply_after: 500,
+        };
+        let logs = vec![
+            format!("Program {PROGRAM_ID} invoke [1]"),
+            format!("Program data: {}", encode(&swap)),
+            format!("Program data: {}", encode(&deposit)),
+            format!("Program data: {}", encode(&withdraw)),
+            format!("Program {PROGRAM_ID} success"),
+        ];
+
+        assert_eq!(
+            parse_logs(&logs),
+            vec![AmmEvent::MultiSwap(swap), AmmEvent::MultiDeposit(deposit), AmmEvent::MultiWithdraw(withdraw)]
+        );
+    }
+}
@@ -0,0 +1,798 @@
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+use v1::{
+    instructions::{
+        DELIQUIDATE_POOL_DISCRIMINATOR, GET_PRICE_DISCRIMINATOR, INCREASE_OBSERVATION_CARDINALITY_DISCRIMINATOR,
+        INIT_CONFIG_DISCRIMINATOR, INIT_POOL_DISCRIMINATOR, LIQUIDATE_POOL_DISCRIMINATOR, OBSERVE_TWAP_DISCRIMINATOR,
+        RAMP_AMP_DISCRIMINATOR, SWAP_DISCRIMINATOR, INIT_CL_POOL_DISCRIMINATOR, INIT_TICK_ARRAY_DISCRIMINATOR,
+        OPEN_POSITION_DISCRIMINATOR, INCREASE_LIQUIDITY_DISCRIMINATOR, DECREASE_LIQUIDITY_DISCRIMINATOR,
+        CL_SWAP_DISCRIMINATOR, INIT_MULTI_POOL_DISCRIMINATOR, MULTI_SWAP_DISCRIMINATOR, MULTI_DEPOSIT_DISCRIMINATOR,
+        MULTI_WITHDRAW_DISCRIMINATOR, MULTI_DEPOSIT_SINGLE_DISCRIMINATOR, MULTI_WITHDRAW_SINGLE_DISCRIMINATOR,
+        ROUTE_SWAP_DISCRIMINATOR, FLASH_LOAN_DISCRIMINATOR, FLASH_REPAY_DISCRIMINATOR, SET_DYNAMIC_FEE_DISCRIMINATOR,
+        UPDATE_POOL_FEE_DISCRIMINATOR, INIT_REFERRER_DISCRIMINATOR, SET_POOL_CREATION_FEE_DISCRIMINATOR,
+    },
+    states::{
+        DeliquidatePool, GetPrice, IncreaseObservationCardinality, InitConfig, InitPool, LiquidatePool, ObserveTwap,
+        RampAmp, Swap, InitClPool, InitTickArray, OpenPosition, ModifyLiquidity, InitMultiPool, MultiSwap,
+        MultiLiquidity, MultiSingleLiquidity, RouteSwap, FlashLoan, SetDynamicFee, UpdatePoolFee, SwapReferral, InitReferrer,
+        SetPoolCreationFee, MAX_MULTI_POOL_TOKENS,
+    },
+    utils::InstructionData,
+};
+
+use crate::{
+    find_config_address, find_position_address, find_program_data_address, find_referrer_address,
+    find_tick_array_address,
+    get_associated_token_address, ClPoolKeys, ConstantProduct, CreationFee, CurveType, MultiPoolKeys, PoolCurve, PoolKeys,
+    StableSwap, SwapDirection,
+    UserKeys, Weighted, PROGRAM_ID, TOKEN_PROGRAM_ID,
+};
+
+/// Discriminator byte followed by the encoded payload.
+pub fn instruction_data<T: InstructionData>(discriminator: u8, payload: &T) -> Vec<u8> {
+    let mut data = Vec::with_capacity(1 + T::LEN);
+    data.push(discriminator);
+    data.extend_from_slice(&payload.pack());
+    data
+}
+
+// InitPool, LiquidatePool and DeliquidatePool share the same account list.
+fn liquidity_accounts(pool: &PoolKeys, user: &UserKeys) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new(user.owner, true),
+        AccountMeta::new(pool.pool, false),
+        AccountMeta::new(user.token_0, false),
+        AccountMeta::new(user.token_1, false),
+        AccountMeta::new_readonly(pool.token_0_mint, false),
+        AccountMeta::new_readonly(pool.token_1_mint, false),
+        AccountMeta::new(pool.vault_0, false),
+        AccountMeta::new(pool.vault_1, false),
+        AccountMeta::new(pool.lp_mint, false),
+        AccountMeta::new(user.lp, false),
+        AccountMeta::new_readonly(sysvar::rent::ID, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ]
+}
+
+/// Creates the constant-product pool described by `pool` and seeds it with the initial reserves.
+pub fn init_pool(
+    pool: &PoolKeys,
+    user: &UserKeys,
+    token_0_amount: u64,
+    token_1_amount: u64,
+    creation_fee: &CreationFee,
+) -> Instruction {
+    let curve = PoolCurve::ConstantProduct(ConstantProduct);
+    init_pool_with_curve(pool, user, token_0_amount, token_1_amount, curve, creation_fee)
+}
+
+/// Creates a stable-swap pool with amplification coefficient `amp`.
+pub fn init_stable_pool(
+    pool: &PoolKeys,
+    user: &UserKeys,
+    token_0_amount: u64,
+    token_1_amount: u64,
+    amp: u64,
+    creation_fee: &CreationFee,
+) -> Instruction {
+    let curve = PoolCurve::StableSwap(StableSwap { amp });
+    init_pool_with_curve(pool, user, token_0_amount, token_1_amount, curve, creation_fee)
+}
+
+/// Creates a weighted pool where token_0 carries `weight_0_bps` of the value and token_1 the rest.
+pub fn init_weighted_pool(
+    pool: &PoolKeys,
+    user: &UserKeys,
+    token_0_amount: u64,
+    token_1_amount: u64,
+    weight_0_bps: u64,
+    creation_fee: &CreationFee,
+) -> Instruction {
+    let curve = PoolCurve::Weighted(Weighted { weight_0_bps });
+    init_pool_with_curve(pool, user, token_0_amount, token_1_amount, curve, creation_fee)
+}
+
+/// Creates a pool on any curve, paying the config's pool creation fee to `creation_fee.treasury`.
+pub fn init_pool_with_curve(
+    pool: &PoolKeys,
+    user: &UserKeys,
+    token_0_amount: u64,
+    token_1_amount: u64,
+    curve: PoolCurve,
+    creation_fee: &CreationFee,
+) -> Instruction {
+    let (curve_type, amp, weight_0_bps) = curve.params();
+    let payload = InitPool {
+        token_0_mint: pool.token_0_mint.to_bytes(),
+        token_1_mint: pool.token_1_mint.to_bytes(),
+        token_0_amount,
+        token_1_amount,
+        vault_0: pool.vault_0.to_bytes(),
+        vault_1: pool.vault_1.to_bytes(),
+        pool_bump: pool.pool_bump,
+        fees_bps: pool.fees_bps,
+        lp_mint: pool.lp_mint.to_bytes(),
+        lp_bump: pool.lp_bump,
+        curve_type: curve_type as u8,
+        amp,
+        weight_0_bps,
+    };
+    let mut accounts = liquidity_accounts(pool, user);
+    accounts.push(AccountMeta::new_readonly(find_config_address().0, false));
+    accounts.push(AccountMeta::new(creation_fee.treasury, false));
+    if let Some((fee_token_account, fee_mint)) = creation_fee.fee_token {
+        accounts.push(AccountMeta::new(fee_token_account, false));
+        accounts.push(AccountMeta::new_readonly(fee_mint, false));
+    }
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data(INIT_POOL_DISCRIMINATOR, &payload),
+    }
+}
+
+pub fn liquidate_pool(
+    pool: &PoolKeys,
+    user: &UserKeys,
+    deposit_token_0_amount: u64,
+    deposit_token_1_amount: u64,
+) -> Instruction {
+    let payload = LiquidatePool {
+        pool_key: pool.pool.to_bytes(),
+        deposit_token_0_amount,
+        deposit_token_1_amount,
+    };
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: liquidity_accounts(pool, user),
+        data: instruction_data(LIQUIDATE_POOL_DISCRIMINATOR, &payload),
+    }
+}
+
+pub fn deliquidate_pool(
+    pool: &PoolKeys,
+    user: &UserKeys,
+    withdraw_token_0_amount: u64,
+    withdraw_token_1_amount: u64,
+) -> Instruction {
+    let payload = DeliquidatePool {
+        pool_key: pool.pool.to_bytes(),
+        withdraw_token_0_amount,
+        withdraw_token_1_amount,
+    };
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: liquidity_accounts(pool, user),
+        data: instruction_data(DELIQUIDATE_POOL_DISCRIMINATOR, &payload),
+    }
+}
+
+pub fn swap(pool: &PoolKeys, user: &UserKeys, payload: Swap) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(user.owner, true),
+            AccountMeta::new(pool.pool, false),
+            AccountMeta::new(user.token_0, false),
+            AccountMeta::new(user.token_1, false),
+            AccountMeta::new_readonly(pool.token_0_mint, false),
+            AccountMeta::new_readonly(pool.token_1_mint, false),
+            AccountMeta::new(pool.vault_0, false),
+            AccountMeta::new(pool.vault_1, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+            // ignored by the program until the observations account is created
+            AccountMeta::new(pool.observations, false),
+        ],
+        data: instruction_data(SWAP_DISCRIMINATOR, &payload),
+    }
+}
+
+/// Flash swap: the output is sent first, then `callback_program` is invoked with
+/// `callback_accounts` and `amount_in | amount_out | callback_data`, and must pay the
+/// input into the pool's input vault before returning.
+pub fn flash_swap(
+    pool: &PoolKeys,
+    user: &UserKeys,
+    payload: Swap,
+    callback_program: &Pubkey,
+    callback_accounts: &[AccountMeta],
+    callback_data: &[u8],
+) -> Instruction {
+    let mut ix = swap(pool, user, payload);
+    ix.accounts.push(AccountMeta::new_readonly(*callback_program, false));
+    ix.accounts.extend_from_slice(callback_accounts);
+    ix.data.extend_from_slice(callback_data);
+    ix
+}
+
+/// Referred swap: `fee_share_bps` of the swap fee, capped by the config, is paid to
+/// `referrer_token_account` in the input mint. Passing the referrer's stats account
+/// (see [`init_referrer`]) also counts the swap there.
+pub fn referred_swap(
+    pool: &PoolKeys,
+    user: &UserKeys,
+    payload: Swap,
+    referrer_token_account: &Pubkey,
+    referrer_stats: Option<&Pubkey>,
+    fee_share_bps: u64,
+) -> Instruction {
+    let mut ix = swap(pool, user, payload);
+    ix.accounts.push(AccountMeta::new_readonly(find_config_address().0, false));
+    ix.accounts.push(AccountMeta::new(*referrer_token_account, false));
+    if let Some(referrer_stats) = referrer_stats {
+        ix.accounts.push(AccountMeta::new(*referrer_stats, false));
+    }
+    ix.data.extend_from_slice(&SwapReferral { fee_share_bps }.pack());
+    ix
+}
+
+/// Creates the referral stats account of `owner` in `mint`.
+pub fn init_referrer(payer: &Pubkey, owner: &Pubkey, mint: &Pubkey) -> Instruction {
+    let (referrer, referrer_bump) = find_referrer_address(owner, mint);
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(referrer, false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data(INIT_REFERRER_DISCRIMINATOR, &InitReferrer { referrer_bump }),
+    }
+}
+
+/// Creates the pool's observations account, or grows it to `cardinality_next` slots.
+pub fn increase_observation_cardinality(pool: &PoolKeys, payer: &Pubkey, cardinality_next: u16) -> Instruction {
+    let payload = IncreaseObservationCardinality {
+        cardinality_next,
+        observations_bump: pool.observations_bump,
+    };
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(pool.pool, false),
+            AccountMeta::new(pool.observations, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data(INCREASE_OBSERVATION_CARDINALITY_DISCRIMINATOR, &payload),
+    }
+}
+
+/// Read-only TWAP query; the result comes back as return data
+/// (`price_0 | price_1 | liquidity`, little-endian u128 each).
+pub fn observe_twap(pool: &PoolKeys, window_seconds: u64) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(pool.pool, false),
+            AccountMeta::new_readonly(pool.observations, false),
+        ],
+        data: instruction_data(OBSERVE_TWAP_DISCRIMINATOR, &ObserveTwap { window_seconds }),
+    }
+}
+
+/// Read-only price query; decode the return data with `PriceQuote::unpack`.
+/// `amount_in` of zero returns spot price and reserves only.
+pub fn get_price(pool: &PoolKeys, amount_in: u64, direction: SwapDirection) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(pool.pool, false),
+            AccountMeta::new_readonly(pool.lp_mint, false),
+        ],
+        data: instruction_data(GET_PRICE_DISCRIMINATOR, &GetPrice { amount_in, direction }),
+    }
+}
+
+/// Creates the config PDA, bounding later `update_pool_fee` rates to `[min_fee_bps, max_fee_bps]`
+/// and referral fee shares to `max_referral_fee_share_bps`, with pool creation fees going to
+/// `treasury`; must be signed by the program's upgrade authority.
+pub fn init_config(
+    upgrade_authority: &Pubkey,
+    admin: &Pubkey,
+    min_fee_bps: u64,
+    max_fee_bps: u64,
+    max_referral_fee_share_bps: u64,
+    treasury: &Pubkey,
+) -> Instruction {
+    let (config, config_bump) = find_config_address();
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*upgrade_authority, true),
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(find_program_data_address(), false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data(
+            INIT_CONFIG_DISCRIMINATOR,
+            &InitConfig {
+                admin: admin.to_bytes(),
+                config_bump,
+                min_fee_bps,
+                max_fee_bps,
+                max_referral_fee_share_bps,
+                treasury: treasury.to_bytes(),
+            },
+        ),
+    }
+}
+
+/// Ramps a stable pool's amplification coefficient to `target_amp` over `ramp_duration` seconds.
+pub fn ramp_amp(pool: &PoolKeys, admin: &Pubkey, target_amp: u64, ramp_duration: u64) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(find_config_address().0, false),
+            AccountMeta::new(pool.pool, false),
+        ],
+        data: instruction_data(RAMP_AMP_DISCRIMINATOR, &RampAmp { target_amp, ramp_duration }),
+    }
+}
+
+/// Creates the concentrated-liquidity pool described by `pool`, starting at `sqrt_price` (Q64.64).
+pub fn init_cl_pool(pool: &ClPoolKeys, payer: &Pubkey, sqrt_price: u128) -> Instruction {
+    let payload = InitClPool {
+        token_0_mint: pool.token_0_mint.to_bytes(),
+        token_1_mint: pool.token_1_mint.to_bytes(),
+        fees_bps: pool.fees_bps,
+        tick_spacing: pool.tick_spacing,
+        sqrt_price,
+        pool_bump: pool.pool_bump,
+    };
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(pool.pool, false),
+            AccountMeta::new_readonly(pool.token_0_mint, false),
+            AccountMeta::new_readonly(pool.token_1_mint, false),
+            AccountMeta::new(pool.vault_0, false),
+            AccountMeta::new(pool.vault_1, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data: instruction_data(INIT_CL_POOL_DISCRIMINATOR, &payload),
+    }
+}
+
+/// Creates the tick array starting at `start_tick_index`, which must be a multiple of
+/// `TICK_ARRAY_SIZE * tick_spacing`.
+pub fn init_tick_array(pool: &ClPoolKeys, payer: &Pubkey, start_tick_index: i32) -> Instruction {
+    let (tick_array, tick_array_bump) = find_tick_array_address(&pool.pool, start_tick_index);
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(pool.pool, false),
+            AccountMeta::new(tick_array, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data(INIT_TICK_ARRAY_DISCRIMINATOR, &InitTickArray { start_tick_index, tick_array_bump }),
+    }
+}
+
+/// Opens an empty position of `owner` over `[tick_lower, tick_upper)`.
+pub fn open_position(pool: &ClPoolKeys, owner: &Pubkey, tick_lower: i32, tick_upper: i32) -> Instruction {
+    let (position, position_bump) = find_position_address(&pool.pool, owner, tick_lower, tick_upper);
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new_readonly(pool.pool, false),
+            AccountMeta::new(position, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: instruction_data(OPEN_POSITION_DISCRIMINATOR, &OpenPosition { tick_lower, tick_upper, position_bump }),
+    }
+}
+
+// IncreaseLiquidity and DecreaseLiquidity share the same account list.
+fn position_accounts(pool: &ClPoolKeys, user: &UserKeys, tick_lower: i32, tick_upper: i32) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(user.owner, true),
+        AccountMeta::new(pool.pool, false),
+        AccountMeta::new(find_position_address(&pool.pool, &user.owner, tick_lower, tick_upper).0, false),
+        AccountMeta::new(pool.tick_array_for(tick_lower).0, false),
+        AccountMeta::new(pool.tick_array_for(tick_upper).0, false),
+        AccountMeta::new(user.token_0, false),
+        AccountMeta::new(user.token_1, false),
+        AccountMeta::new_readonly(pool.token_0_mint, false),
+        AccountMeta::new_readonly(pool.token_1_mint, false),
+        AccountMeta::new(pool.vault_0, false),
+        AccountMeta::new(pool.vault_1, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ]
+}
+
+/// Adds `payload.liquidity` to the owner's position, depositing at most the payload's amount limits.
+pub fn increase_liquidity(
+    pool: &ClPoolKeys,
+    user: &UserKeys,
+    tick_lower: i32,
+    tick_upper: i32,
+    payload: ModifyLiquidity,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: position_accounts(pool, user, tick_lower, tick_upper),
+        data: instruction_data(INCREASE_LIQUIDITY_DISCRIMINATOR, &payload),
+    }
+}
+
+/// Removes `payload.liquidity` from the owner's position and collects its fees; zero liquidity
+/// only collects.
+pub fn decrease_liquidity(
+    pool: &ClPoolKeys,
+    user: &UserKeys,
+    tick_lower: i32,
+    tick_upper: i32,
+    payload: ModifyLiquidity,
+) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: position_accounts(pool, user, tick_lower, tick_upper),
+        data: instruction_data(DECREASE_LIQUIDITY_DISCRIMINATOR, &payload),
+    }
+}
+
+/// Swaps against a concentrated-liquidity pool. `tick_arrays` (one to three) must start with
+/// the array holding the current tick and follow each other in the swap direction.
+pub fn cl_swap(pool: &ClPoolKeys, user: &UserKeys, tick_arrays: &[Pubkey], payload: Swap) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(user.owner, true),
+        AccountMeta::new(pool.pool, false),
+        AccountMeta::new(user.token_0, false),
+        AccountMeta::new(user.token_1, false),
+        AccountMeta::new_readonly(pool.token_0_mint, false),
+        AccountMeta::new_readonly(pool.token_1_mint, false),
+        AccountMeta::new(pool.vault_0, false),
+        AccountMeta::new(pool.vault_1, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ];
+    accounts.extend(tick_arrays.iter().map(|tick_array| AccountMeta::new(*tick_array, false)));
+    Instruction { program_id: PROGRAM_ID, accounts, data: instruction_data(CL_SWAP_DISCRIMINATOR, &payload) }
+}
+
+/// Creates the multi-token pool described by `pool`, seeded with `amounts[i]` of token `i`.
+/// `amp` is only used by stable-swap pools and `weights_bps` only by weighted ones.
+pub fn init_multi_pool(
+    pool: &MultiPoolKeys,
+    owner: &Pubkey,
+    curve_type: CurveType,
+    amp: u64,
+    weights_bps: &[u64],
+    amounts: &[u64],
+) -> Instruction {
+    let mut payload = InitMultiPool {
+        fees_bps: pool.fees_bps,
+        curve_type: curve_type as u8,
+        amp,
+        token_count: pool.mints.len() as u8,
+        pool_bump: pool.pool_bump,
+        lp_bump: pool.lp_bump,
+        weights_bps: [0; MAX_MULTI_POOL_TOKENS],
+        amounts: [0; MAX_MULTI_POOL_TOKENS],
+    };
+    payload.weights_bps[..weights_bps.len()].copy_from_slice(weights_bps);
+    payload.amounts[..amounts.len()].copy_from_slice(amounts);
+    let mut accounts = vec![
+        AccountMeta::new(*owner, true),
+        AccountMeta::new(pool.pool, false),
+        AccountMeta::new(pool.lp_mint, false),
+        AccountMeta::new(get_associated_token_address(owner, &pool.lp_mint), false),
+        AccountMeta::new_readonly(sysvar::rent::ID, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ];
+    accounts.extend(multi_token_accounts(pool, owner));
+    Instruction { program_id: PROGRAM_ID, accounts, data: instruction_data(INIT_MULTI_POOL_DISCRIMINATOR, &payload) }
+}
+
+// (mint, owner ATA, vault) of every pool token, the tail of the multi-pool account lists.
+fn multi_token_accounts<'a>(pool: &'a MultiPoolKeys, owner: &'a Pubkey) -> impl Iterator<Item = AccountMeta> + 'a {
+    pool.mints.iter().zip(&pool.vaults).flat_map(move |(mint, vault)| {
+        [
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(get_associated_token_address(owner, mint), false),
+            AccountMeta::new(*vault, false),
+        ]
+    })
+}
+
+/// Swaps token `payload.token_in` for `payload.token_out` through the owner's ATAs.
+pub fn multi_swap(pool: &MultiPoolKeys, owner: &Pubkey, payload: MultiSwap) -> Instruction {
+    let (mint_in, mint_out) = (&pool.mints[payload.token_in as usize], &pool.mints[payload.token_out as usize]);
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*owner, true),
+            AccountMeta::new(pool.pool, false),
+            AccountMeta::new(get_associated_token_address(owner, mint_in), false),
+            AccountMeta::new(get_associated_token_address(owner, mint_out), false),
+            AccountMeta::new_readonly(*mint_in, false),
+            AccountMeta::new_readonly(*mint_out, false),
+            AccountMeta::new(pool.vaults[payload.token_in as usize], false),
+            AccountMeta::new(pool.vaults[payload.token_out as usize], false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+        ],
+        data: instruction_data(MULTI_SWAP_DISCRIMINATOR, &payload),
+    }
+}
+
+// MultiDeposit and MultiWithdraw share the same account list.
+fn multi_liquidity_accounts(pool: &MultiPoolKeys, owner: &Pubkey) -> Vec<AccountMeta> {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(pool.pool, false),
+        AccountMeta::new(pool.lp_mint, false),
+        AccountMeta::new(get_associated_token_address(owner, &pool.lp_mint), false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ];
+    accounts.extend(multi_token_accounts(pool, owner));
+    accounts
+}
+
+/// Mints `payload.lp_amount` LP tokens against a proportional deposit of every pool token.
+pub fn multi_deposit(pool: &MultiPoolKeys, owner: &Pubkey, payload: MultiLiquidity) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: multi_liquidity_accounts(pool, owner),
+        data: instruction_data(MULTI_DEPOSIT_DISCRIMINATOR, &payload),
+    }
+}
+
+/// Burns `payload.lp_amount` LP tokens for a proportional share of every pool token.
+pub fn multi_withdraw(pool: &MultiPoolKeys, owner: &Pubkey, payload: MultiLiquidity) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: multi_liquidity_accounts(pool, owner),
+        data: instruction_data(MULTI_WITHDRAW_DISCRIMINATOR, &payload),
+    }
+}
+
+// MultiDepositSingle and MultiWithdrawSingle share the same account list.
+fn multi_single_accounts(pool: &MultiPoolKeys, owner: &Pubkey, token: u8) -> Vec<AccountMeta> {
+    let mint = &pool.mints[token as usize];
+    vec![
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(pool.pool, false),
+        AccountMeta::new(pool.lp_mint, false),
+        AccountMeta::new(get_associated_token_address(owner, &pool.lp_mint), false),
+        AccountMeta::new(get_associated_token_address(owner, mint), false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new(pool.vaults[token as usize], false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ]
+}
+
+/// Deposits `payload.amount` of token `payload.token` alone for at least `payload.limit` LP tokens.
+pub fn multi_deposit_single(pool: &MultiPoolKeys, owner: &Pubkey, payload: MultiSingleLiquidity) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: multi_single_accounts(pool, owner, payload.token),
+        data: instruction_data(MULTI_DEPOSIT_SINGLE_DISCRIMINATOR, &payload),
+    }
+}
+
+/// Burns `payload.amount` LP tokens for at least `payload.limit` of token `payload.token`.
+pub fn multi_withdraw_single(pool: &MultiPoolKeys, owner: &Pubkey, payload: MultiSingleLiquidity) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: multi_single_accounts(pool, owner, payload.token),
+        data: instruction_data(MULTI_WITHDRAW_SINGLE_DISCRIMINATOR, &payload),
+    }
+}
+
+/// Swaps `payload.amount_in` of `source_mint` through `pools` in order, each pool's output
+/// mint being the input of the next, into the owner's ATA of the last output mint.
+/// Panics if `pools` do not chain from `source_mint`.
+pub fn route_swap(owner: &Pubkey, source_mint: &Pubkey, pools: &[PoolKeys], payload: RouteSwap) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(get_associated_token_address(owner, source_mint), false),
+        // destination, filled in once the route is walked
+        AccountMeta::new(Pubkey::default(), false),
+        AccountMeta::new_readonly(*source_mint, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ];
+    let mut mint_in = *source_mint;
+    for pool in pools {
+        let (mint_out, vault_in, vault_out) = if mint_in == pool.token_0_mint {
+            (pool.token_1_mint, pool.vault_0, pool.vault_1)
+        } else {
+            assert_eq!(mint_in, pool.token_1_mint, "route does not chain through pool {}", pool.pool);
+            (pool.token_0_mint, pool.vault_1, pool.vault_0)
+        };
+        accounts.extend([
+            AccountMeta::new(pool.pool, false),
+            AccountMeta::new_readonly(mint_out, false),
+            AccountMeta::new(vault_in, false),
+            AccountMeta::new(vault_out, false),
+        ]);
+        mint_in = mint_out;
+    }
+    accounts[2].pubkey = get_associated_token_address(owner, &mint_in);
+    Instruction { program_id: PROGRAM_ID, accounts, data: instruction_data(ROUTE_SWAP_DISCRIMINATOR, &payload) }
+}
+
+// FlashLoan and FlashRepay share the same account list, FlashLoan adding the Instructions sysvar.
+fn flash_loan_accounts(pool: &PoolKeys, user: &UserKeys) -> Vec<AccountMeta> {
+    vec![
+        AccountMeta::new_readonly(user.owner, true),
+        AccountMeta::new(pool.pool, false),
+        AccountMeta::new(user.token_0, false),
+        AccountMeta::new(user.token_1, false),
+        AccountMeta::new_readonly(pool.token_0_mint, false),
+        AccountMeta::new_readonly(pool.token_1_mint, false),
+        AccountMeta::new(pool.vault_0, false),
+        AccountMeta::new(pool.vault_1, false),
+        AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false),
+    ]
+}
+
+/// Borrows `amount_0` / `amount_1` from the pool vaults. The transaction must contain a
+/// later [`flash_repay`] for the same pool.
+pub fn flash_loan(pool: &PoolKeys, user: &UserKeys, amount_0: u64, amount_1: u64) -> Instruction {
+    let mut accounts = flash_loan_accounts(pool, user);
+    accounts.push(AccountMeta::new_readonly(sysvar::instructions::ID, false));
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts,
+        data: instruction_data(FLASH_LOAN_DISCRIMINATOR, &FlashLoan { amount_0, amount_1 }),
+    }
+}
+
+/// Repays the pool's outstanding flash loan plus fee and unlocks the pool.
+pub fn flash_repay(pool: &PoolKeys, user: &UserKeys) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: flash_loan_accounts(pool, user),
+        data: vec![FLASH_REPAY_DISCRIMINATOR],
+    }
+}
+
+/// Bounds the pool's dynamic fee to `[min_fee_bps, max_fee_bps]`; both zero turn dynamic fees off.
+pub fn set_dynamic_fee(pool: &PoolKeys, admin: &Pubkey, min_fee_bps: u64, max_fee_bps: u64) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(find_config_address().0, false),
+            AccountMeta::new(pool.pool, false),
+        ],
+        data: instruction_data(SET_DYNAMIC_FEE_DISCRIMINATOR, &SetDynamicFee { min_fee_bps, max_fee_bps }),
+    }
+}
+
+/// Sets the pool's fee rate to `fee_rate_bps`, `delay` seconds from now. The fee tier, and so
+/// the pool address, does not change.
+pub fn update_pool_fee(pool: &PoolKeys, admin: &Pubkey, fee_rate_bps: u64, delay: u64) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new_readonly(find_config_address().0, false),
+            AccountMeta::new(pool.pool, false),
+        ],
+        data: instruction_data(UPDATE_POOL_FEE_DISCRIMINATOR, &UpdatePoolFee { fee_rate_bps, delay }),
+    }
+}
+
+/// Charges pool creators `amount` of `fee_mint`, or lamports for the default pubkey, paid
+/// to `treasury`, which must be a token account of `fee_mint` for a token fee.
+pub fn set_pool_creation_fee(admin: &Pubkey, treasury: &Pubkey, fee_mint: &Pubkey, amount: u64) -> Instruction {
+    Instruction {
+        program_id: PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(*admin, true),
+            AccountMeta::new(find_config_address().0, false),
+            AccountMeta::new_readonly(*treasury, false),
+        ],
+        data: instruction_data(
+            SET_POOL_CREATION_FEE_DISCRIMINATOR,
+            &SetPoolCreationFee { mint: fee_mint.to_bytes(), amount },
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::decode_pool;
+    use v1::{
+        states::Pool,
+        utils::{AccountDiscriminator, DataLen},
+    };
+
+    const TOKEN_0_MINT: Pubkey = solana_sdk::pubkey!("So11111111111111111111111111111111111111112");
+    const TOKEN_1_MINT: Pubkey = solana_sdk::pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
+
+    #[test]
+    fn test_init_pool_builder() {
+        let pool = PoolKeys::derive(&TOKEN_0_MINT, &TOKEN_1_MINT, 30);
+        let owner = Pubkey::new_unique();
+        let user = UserKeys::associated(&owner, &pool);
+
+        let treasury = Pubkey::new_unique();
+        let creation_fee = CreationFee { treasury, fee_token: None };
+
+        let ix = init_pool(&pool, &user, 1_000_000, 2_000_000, &creation_fee);
+
+        assert_eq!(ix.program_id, PROGRAM_ID);
+        assert_eq!(ix.accounts.len(), 15);
+        assert_eq!(ix.accounts[13].pubkey, find_config_address().0);
+        assert_eq!(ix.accounts[14].pubkey, treasury);
+        assert!(ix.accounts[0].is_signer);
+        assert_eq!(ix.accounts[1].pubkey, pool.pool);
+        assert_eq!(ix.data[0], INIT_POOL_DISCRIMINATOR);
+        assert_eq!(ix.data.len(), 1 + InitPool::LEN);
+
+        let payload = InitPool::unpack(&ix.data[1..]).unwrap();
+        assert_eq!(payload.fees_bps, 30);
+        assert_eq!(payload.pool_bump, pool.pool_bump);
+        assert_eq!(payload.lp_mint, pool.lp_mint.to_bytes());
+    }
+
+    #[test]
+    fn test_decode_pool_roundtrip() {
+        let keys = PoolKeys::derive(&TOKEN_0_MINT, &TOKEN_1_MINT, 30);
+
+        let mut data = vec![0u8; Pool::LEN];
+        data[..8].copy_from_slice(&Pool::DISCRIMINATOR);
+        data[Pool::TOKEN_0_MINT_OFFSET..][..32].copy_from_slice(keys.token_0_mint.as_ref());
+        data[Pool::TOKEN_1_MINT_OFFSET..][..32].copy_from_slice(keys.token_1_mint.as_ref());
+        data[Pool::VAULT_0_OFFSET..][..32].copy_from_slice(keys.vault_0.as_ref());
+        data[Pool::VAULT_1_OFFSET..][..32].copy_from_slice(keys.vault_1.as_ref());
+        data[Pool::LP_MINT_OFFSET..][..32].copy_from_slice(keys.lp_mint.as_ref());
+        data[Pool::FEES_BPS_OFFSET..][..8].copy_from_slice(&30u64.to_le_bytes());
+        data[Pool::POOL_BUMP_OFFSET] = keys.pool_bump;
+        data[Pool::LP_BUMP_OFFSET] = keys.lp_bump;
+
+        let pool = decode_pool(&data).unwrap();
+        assert_eq!(PoolKeys::from_pool(&keys.pool, &pool), keys);
+
+        data[0] ^= 1;
+        assert!(decode_pool(&data).is_err());
+    }
+
+    #[test]
+    fn test_get_price_builder_and_return_data() {
+        use v1::instructions::PriceQuote;
+
+        let pool = PoolKeys::derive(&TOKEN_0_MINT, &TOKEN_1_MINT, 30);
+        let ix = get_price(&pool, 10_000, SwapDirection::OneForZero);
+        assert_eq!(ix.accounts.len(), 2);
+        assert!(ix.accounts.iter().all(|meta| !meta.is_writable));
+        assert_eq!(ix.data[0], GET_PRICE_DISCRIMINATOR);
+        assert_eq!(
+            GetPrice::unpack(&ix.data[1..]).unwrap(),
+            GetPrice { amount_in: 10_000, direction: SwapDirection::OneForZero }
+        );
+
+        let quote = PriceQuote {
+            price_0: 2 << 64,
+            price_1: 1 << 63,
+            reserve_0: 1_000_000,
+            reserve_1: 2_000_000,
+            lp_supply: 1_414_213,
+            fees_bps: 30,
+            amount_in: 10_000,
+            amount_out: 4_950,
+            fee: 30,
+            price_impact_bps: 100,
+        };
+        let mut data = [0u8; PriceQuote::LEN];
+        quote.pack_into(&mut data).unwrap();
+        assert_eq!(PriceQuote::unpack(&data).unwrap(), quote);
+        assert!(PriceQuote::unpack(&data[1..]).is_err());
+    }
+}
@@ -0,0 +1,35 @@
+//! Off-chain client for the AMM program.
+//!
+//! Everything here is derived from the program crate itself (discriminators,
+//! payload encoders, `Pool` layout), so clients never hand-roll instruction bytes.
+
+pub mod accounts;
+pub mod events;
+pub mod instructions;
+pub mod pda;
+
+pub use accounts::*;
+pub use events::*;
+pub use instructions::*;
+pub use pda::*;
+
+// Quotes are computed by the program crate itself, so they match `swap` exactly.
+pub use v1::utils::{
+    curve::{ConstantProduct, ConstantSum, Curve, CurveType, PoolCurve, StableSwap, Weighted},
+    quote::{
+        quote_curve_exact_in, quote_curve_exact_out, quote_exact_in, quote_exact_out, quote_route, quote_swap,
+        SwapDirection, SwapMode, SwapQuote, MAX_ROUTE_LEGS,
+    },
+    concentrated::{swap_through_ticks, ClSwapResult},
+    tick_math::{sqrt_price_at_tick, tick_at_sqrt_price, MAX_TICK, MIN_TICK},
+    multi::{Basket, MultiSwapQuote},
+};
+
+use solana_sdk::pubkey::Pubkey;
+
+/// The AMM program id.
+pub const PROGRAM_ID: Pubkey = Pubkey::new_from_array(v1::ID);
+
+pub const TOKEN_PROGRAM_ID: Pubkey = solana_sdk::pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    solana_sdk::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
@@ -0,0 +1,258 @@
+use solana_sdk::{bpf_loader_upgradeable, pubkey::Pubkey};
+use v1::states::{ClPool, Config, MultiPool, Observations, Pool, Position, Referrer, TickArray};
+
+use crate::{ASSOCIATED_TOKEN_PROGRAM_ID, PROGRAM_ID, TOKEN_PROGRAM_ID};
+
+/// Pool PDA: `["pool", token_0_mint, token_1_mint, fees_bps (u64 LE)]`.
+pub fn find_pool_address(token_0_mint: &Pubkey, token_1_mint: &Pubkey, fees_bps: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            Pool::POOL_SEED.as_bytes(),
+            token_0_mint.as_ref(),
+            token_1_mint.as_ref(),
+            &fees_bps.to_le_bytes(),
+        ],
+        &PROGRAM_ID,
+    )
+}
+
+/// LP mint PDA: `["lp", pool]`.
+pub fn find_lp_mint_address(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[Pool::LP_SEED.as_bytes(), pool.as_ref()], &PROGRAM_ID)
+}
+
+/// Observations PDA: `["observations", pool]`.
+pub fn find_observations_address(pool: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[Observations::SEED.as_bytes(), pool.as_ref()], &PROGRAM_ID)
+}
+
+/// Program-wide config PDA: `["config"]`.
+pub fn find_config_address() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[Config::SEED.as_bytes()], &PROGRAM_ID)
+}
+
+/// Concentrated-liquidity pool PDA: `["cl_pool", token_0_mint, token_1_mint, fees_bps (u64 LE)]`.
+pub fn find_cl_pool_address(token_0_mint: &Pubkey, token_1_mint: &Pubkey, fees_bps: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            ClPool::SEED.as_bytes(),
+            token_0_mint.as_ref(),
+            token_1_mint.as_ref(),
+            &fees_bps.to_le_bytes(),
+        ],
+        &PROGRAM_ID,
+    )
+}
+
+/// Tick array PDA: `["tick_array", pool, start_tick_index (i32 LE)]`.
+pub fn find_tick_array_address(pool: &Pubkey, start_tick_index: i32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[TickArray::SEED.as_bytes(), pool.as_ref(), &start_tick_index.to_le_bytes()],
+        &PROGRAM_ID,
+    )
+}
+
+/// Position PDA: `["position", pool, owner, tick_lower (i32 LE), tick_upper (i32 LE)]`.
+pub fn find_position_address(pool: &Pubkey, owner: &Pubkey, tick_lower: i32, tick_upper: i32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            Position::SEED.as_bytes(),
+            pool.as_ref(),
+            owner.as_ref(),
+            &tick_lower.to_le_bytes(),
+            &tick_upper.to_le_bytes(),
+        ],
+        &PROGRAM_ID,
+    )
+}
+
+/// Multi-token pool PDA: `["multi_pool", mint_0, .., mint_n, fees_bps (u64 LE)]`, mints in
+/// ascending order.
+pub fn find_multi_pool_address(mints: &[Pubkey], fees_bps: u64) -> (Pubkey, u8) {
+    let fees_bps_bytes = fees_bps.to_le_bytes();
+    let mut seeds: Vec<&[u8]> = vec![MultiPool::SEED.as_bytes()];
+    seeds.extend(mints.iter().map(|mint| mint.as_ref()));
+    seeds.push(&fees_bps_bytes);
+    Pubkey::find_program_address(&seeds, &PROGRAM_ID)
+}
+
+/// Referrer stats PDA: `["referrer", owner, mint]`.
+pub fn find_referrer_address(owner: &Pubkey, mint: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[Referrer::SEED.as_bytes(), owner.as_ref(), mint.as_ref()], &PROGRAM_ID)
+}
+
+/// ProgramData account of the (upgradeable) AMM program.
+pub fn find_program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[PROGRAM_ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+/// Associated token account of `wallet` for `mint`. Pool vaults are the pool's ATAs.
+pub fn get_associated_token_address(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), TOKEN_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Every address a pool instruction needs, derived from the pool's mints and fee tier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PoolKeys {
+    pub pool: Pubkey,
+    pub pool_bump: u8,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub vault_0: Pubkey,
+    pub vault_1: Pubkey,
+    pub lp_mint: Pubkey,
+    pub lp_bump: u8,
+    pub fees_bps: u64,
+    pub observations: Pubkey,
+    pub observations_bump: u8,
+}
+
+impl PoolKeys {
+    pub fn derive(token_0_mint: &Pubkey, token_1_mint: &Pubkey, fees_bps: u64) -> Self {
+        let (pool, pool_bump) = find_pool_address(token_0_mint, token_1_mint, fees_bps);
+        let (lp_mint, lp_bump) = find_lp_mint_address(&pool);
+        let (observations, observations_bump) = find_observations_address(&pool);
+        Self {
+            pool,
+            pool_bump,
+            token_0_mint: *token_0_mint,
+            token_1_mint: *token_1_mint,
+            vault_0: get_associated_token_address(&pool, token_0_mint),
+            vault_1: get_associated_token_address(&pool, token_1_mint),
+            lp_mint,
+            lp_bump,
+            fees_bps,
+            observations,
+            observations_bump,
+        }
+    }
+
+    /// Keys of an existing pool, read from its decoded account.
+    pub fn from_pool(address: &Pubkey, pool: &Pool) -> Self {
+        let (observations, observations_bump) = find_observations_address(address);
+        Self {
+            pool: *address,
+            pool_bump: pool.pool_bump,
+            token_0_mint: Pubkey::new_from_array(pool.token_0_mint),
+            token_1_mint: Pubkey::new_from_array(pool.token_1_mint),
+            vault_0: Pubkey::new_from_array(pool.vault_0),
+            vault_1: Pubkey::new_from_array(pool.vault_1),
+            lp_mint: Pubkey::new_from_array(pool.lp_mint),
+            lp_bump: pool.lp_bump,
+            fees_bps: pool.fees_bps(),
+            observations,
+            observations_bump,
+        }
+    }
+}
+
+/// A user's token accounts for one pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UserKeys {
+    pub owner: Pubkey,
+    pub token_0: Pubkey,
+    pub token_1: Pubkey,
+    pub lp: Pubkey,
+}
+
+impl UserKeys {
+    /// Uses the owner's associated token accounts for both pool mints and the LP mint.
+    pub fn associated(owner: &Pubkey, pool: &PoolKeys) -> Self {
+        Self {
+            owner: *owner,
+            token_0: get_associated_token_address(owner, &pool.token_0_mint),
+            token_1: get_associated_token_address(owner, &pool.token_1_mint),
+            lp: get_associated_token_address(owner, &pool.lp_mint),
+        }
+    }
+}
+
+/// Where a pool creator's creation fee goes, as set in the config.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CreationFee {
+    pub treasury: Pubkey,
+    /// `(creator's account of the fee mint, fee mint)`; `None` for a fee in lamports.
+    pub fee_token: Option<(Pubkey, Pubkey)>,
+}
+
+impl CreationFee {
+    /// Reads the treasury and fee mint from the decoded config, paying a token fee from
+    /// the creator's associated token account.
+    pub fn from_config(config: &Config, creator: &Pubkey) -> Self {
+        let fee_mint = Pubkey::new_from_array(config.pool_creation_fee_mint);
+        let fee_token = (!config.pool_creation_fee_in_lamports())
+            .then(|| (get_associated_token_address(creator, &fee_mint), fee_mint));
+        Self {
+            treasury: Pubkey::new_from_array(config.treasury),
+            fee_token,
+        }
+    }
+}
+
+/// Every address a concentrated-liquidity pool instruction needs, derived from its mints and fee tier.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClPoolKeys {
+    pub pool: Pubkey,
+    pub pool_bump: u8,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub vault_0: Pubkey,
+    pub vault_1: Pubkey,
+    pub fees_bps: u64,
+    pub tick_spacing: u16,
+}
+
+impl ClPoolKeys {
+    pub fn derive(token_0_mint: &Pubkey, token_1_mint: &Pubkey, fees_bps: u64, tick_spacing: u16) -> Self {
+        let (pool, pool_bump) = find_cl_pool_address(token_0_mint, token_1_mint, fees_bps);
+        Self {
+            pool,
+            pool_bump,
+            token_0_mint: *token_0_mint,
+            token_1_mint: *token_1_mint,
+            vault_0: get_associated_token_address(&pool, token_0_mint),
+            vault_1: get_associated_token_address(&pool, token_1_mint),
+            fees_bps,
+            tick_spacing,
+        }
+    }
+
+    /// Tick array holding `tick`.
+    pub fn tick_array_for(&self, tick: i32) -> (Pubkey, u8) {
+        find_tick_array_address(&self.pool, TickArray::start_tick_index_for(tick, self.tick_spacing))
+    }
+}
+
+/// Every address a multi-token pool instruction needs, derived from its mints and fee tier.
+/// Token `i` of the pool is `mints[i]`, held in `vaults[i]`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MultiPoolKeys {
+    pub pool: Pubkey,
+    pub pool_bump: u8,
+    pub lp_mint: Pubkey,
+    pub lp_bump: u8,
+    pub mints: Vec<Pubkey>,
+    pub vaults: Vec<Pubkey>,
+    pub fees_bps: u64,
+}
+
+impl MultiPoolKeys {
+    /// Sorts `mints` into pool order first.
+    pub fn derive(mints: &[Pubkey], fees_bps: u64) -> Self {
+        let mut mints = mints.to_vec();
+        mints.sort();
+        let (pool, pool_bump) = find_multi_pool_address(&mints, fees_bps);
+        let (lp_mint, lp_bump) = find_lp_mint_address(&pool);
+        let vaults = mints.iter().map(|mint| get_associated_token_address(&pool, mint)).collect();
+        Self { pool, pool_bump, lp_mint, lp_bump, mints, vaults, fees_bps }
+    }
+
+    /// Pool index of `mint`.
+    pub fn index_of(&self, mint: &Pubkey) -> Option<usize> {
+        self.mints.iter().position(|candidate| candidate == mint)
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{IndexerError, Result, Trade};
+
+/// One OHLCV bucket. Prices are token_1 per token_0, taken from post-trade reserves.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Candle {
+    // bucket start, unix seconds
+    pub start_time: i64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    // traded volume per side, in raw token units (inputs and outputs both counted)
+    pub volume_0: u128,
+    pub volume_1: u128,
+    pub trades: u64,
+}
+
+/// Buckets trades (sorted by time) into candles of `interval` seconds.
+/// Buckets without trades are omitted.
+pub fn build_candles(trades: &[Trade], interval: i64) -> Result<Vec<Candle>> {
+    if interval <= 0 {
+        return Err(IndexerError::InvalidInterval(interval));
+    }
+
+    let mut candles: Vec<Candle> = Vec::new();
+    for trade in trades {
+        let start_time = trade.block_time - trade.block_time.rem_euclid(interval);
+        let price = trade.price();
+        let (volume_0, volume_1) = trade.volumes();
+
+        match candles.last_mut() {
+            Some(candle) if candle.start_time == start_time => {
+                candle.high = candle.high.max(price);
+                candle.low = candle.low.min(price);
+                candle.close = price;
+                candle.volume_0 += volume_0 as u128;
+                candle.volume_1 += volume_1 as u128;
+                candle.trades += 1;
+            }
+            _ => candles.push(Candle {
+                start_time,
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                volume_0: volume_0 as u128,
+                volume_1: volume_1 as u128,
+                trades: 1,
+            }),
+        }
+    }
+    Ok(candles)
+}
@@ -0,0 +1,23 @@
+//! Pool history indexer for the AMM program.
+//!
+//! Transactions are fed in as raw log messages; the AMM events in them are decoded
+//! with `amm-client` and materialized into SQLite tables (pools, trades, liquidity
+//! changes, reserve/LP-supply snapshots) from which OHLCV candles, volume and TVL
+//! series are derived.
+
+pub mod candles;
+pub mod store;
+
+pub use candles::*;
+pub use store::*;
+
+#[derive(Debug, thiserror::Error)]
+pub enum IndexerError {
+    #[error("sqlite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("invalid candle interval: {0}")]
+    InvalidInterval(i64),
+}
+
+pub type Result<T> = core::result::Result<T, IndexerError>;
@@ -0,0 +1,440 @@
+use std::path::Path;
+
+use amm_client::{parse_logs, AmmEvent};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{build_candles, Candle, Result};
+
+// u64 amounts are stored as INTEGER (i64) with a bit-preserving cast and converted
+// back on read; SQLite has no unsigned 64-bit type. `trades.fees_bps` is NULL for swaps
+// logged before swap events carried the fee rate.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS pools (
+    pool          TEXT PRIMARY KEY,
+    token_0_mint  TEXT NOT NULL,
+    token_1_mint  TEXT NOT NULL,
+    lp_mint       TEXT NOT NULL,
+    fees_bps      INTEGER NOT NULL,
+    creator       TEXT NOT NULL,
+    created_slot  INTEGER NOT NULL,
+    created_time  INTEGER NOT NULL
+);
+CREATE TABLE IF NOT EXISTS trades (
+    signature        TEXT NOT NULL,
+    event_index      INTEGER NOT NULL,
+    slot             INTEGER NOT NULL,
+    block_time       INTEGER NOT NULL,
+    pool             TEXT NOT NULL,
+    user             TEXT NOT NULL,
+    direction        INTEGER NOT NULL,
+    amount_in        INTEGER NOT NULL,
+    amount_out       INTEGER NOT NULL,
+    fee              INTEGER NOT NULL,
+    reserve_0_after  INTEGER NOT NULL,
+    reserve_1_after  INTEGER NOT NULL,
+    fees_bps         INTEGER,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS trades_pool_time ON trades (pool, block_time);
+CREATE TABLE IF NOT EXISTS liquidity (
+    signature       TEXT NOT NULL,
+    event_index     INTEGER NOT NULL,
+    slot            INTEGER NOT NULL,
+    block_time      INTEGER NOT NULL,
+    pool            TEXT NOT NULL,
+    user            TEXT NOT NULL,
+    kind            TEXT NOT NULL,
+    token_0_amount  INTEGER NOT NULL,
+    token_1_amount  INTEGER NOT NULL,
+    lp_amount       INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE TABLE IF NOT EXISTS reserves (
+    signature    TEXT NOT NULL,
+    event_index  INTEGER NOT NULL,
+    slot         INTEGER NOT NULL,
+    block_time   INTEGER NOT NULL,
+    pool         TEXT NOT NULL,
+    reserve_0    INTEGER NOT NULL,
+    reserve_1    INTEGER NOT NULL,
+    lp_supply    INTEGER NOT NULL,
+    PRIMARY KEY (signature, event_index)
+);
+CREATE INDEX IF NOT EXISTS reserves_pool_time ON reserves (pool, block_time);
+";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PoolInfo {
+    pub pool: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub fees_bps: u64,
+    pub creator: Pubkey,
+    pub created_slot: u64,
+    pub created_time: i64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trade {
+    pub signature: String,
+    pub slot: u64,
+    pub block_time: i64,
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    // `SwapDirection` as u8: 0 = token_0 in, 1 = token_1 in
+    pub direction: u8,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    pub reserve_0_after: u64,
+    pub reserve_1_after: u64,
+    // fee rate charged, dynamic fee included; `None` for swaps logged before it was recorded
+    pub fees_bps: Option<u64>,
+}
+
+impl Trade {
+    /// Post-trade spot price, token_1 per token_0.
+    pub fn price(&self) -> f64 {
+        self.reserve_1_after as f64 / self.reserve_0_after as f64
+    }
+
+    /// Amounts moved on each side as (token_0, token_1).
+    pub fn volumes(&self) -> (u64, u64) {
+        if self.direction == 0 {
+            (self.amount_in, self.amount_out)
+        } else {
+            (self.amount_out, self.amount_in)
+        }
+    }
+}
+
+/// Pool state after an event: reserves and LP supply.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReserveSnapshot {
+    pub slot: u64,
+    pub block_time: i64,
+    pub reserve_0: u64,
+    pub reserve_1: u64,
+    pub lp_supply: u64,
+}
+
+pub struct Indexer {
+    conn: Connection,
+}
+
+fn key(bytes: &[u8; 32]) -> String {
+    Pubkey::new_from_array(*bytes).to_string()
+}
+
+fn pubkey_column(row: &Row, idx: usize) -> rusqlite::Result<Pubkey> {
+    let value: String = row.get(idx)?;
+    value
+        .parse()
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(idx, rusqlite::types::Type::Text, Box::new(e)))
+}
+
+fn u64_column(row: &Row, idx: usize) -> rusqlite::Result<u64> {
+    Ok(row.get::<_, i64>(idx)? as u64)
+}
+
+// (signature, event_index, slot, block_time) of the event being stored.
+type EventPosition<'a> = (&'a str, i64, u64, i64);
+
+fn insert_liquidity(
+    conn: &Connection,
+    (signature, event_index, slot, block_time): EventPosition,
+    kind: &str,
+    (pool, user): (&[u8; 32], &[u8; 32]),
+    (token_0_amount, token_1_amount, lp_amount): (u64, u64, u64),
+) -> rusqlite::Result<usize> {
+    conn.execute(
+        "INSERT OR IGNORE INTO liquidity VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![
+            signature,
+            event_index,
+            slot as i64,
+            block_time,
+            key(pool),
+            key(user),
+            kind,
+            token_0_amount as i64,
+            token_1_amount as i64,
+            lp_amount as i64
+        ],
+    )
+}
+
+impl Indexer {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self { conn })
+    }
+
+    /// Decodes the AMM events in one transaction's logs and stores them.
+    /// Re-ingesting the same signature is a no-op. Returns the number of events found.
+    pub fn ingest_transaction<S: AsRef<str>>(
+        &mut self,
+        signature: &str,
+        slot: u64,
+        block_time: i64,
+        logs: &[S],
+    ) -> Result<usize> {
+        let events = parse_logs(logs);
+        let tx = self.conn.transaction()?;
+
+        for (event_index, event) in events.iter().enumerate() {
+            let event_index = event_index as i64;
+            let (pool, reserve_0, reserve_1, lp_supply) = match event {
+                AmmEvent::PoolCreated(e) => {
+                    tx.execute(
+                        "INSERT OR IGNORE INTO pools VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                        params![
+                            key(&e.pool),
+                            key(&e.token_0_mint),
+                            key(&e.token_1_mint),
+                            key(&e.lp_mint),
+                            e.fees_bps as i64,
+                            key(&e.creator),
+                            slot as i64,
+                            block_time
+                        ],
+                    )?;
+                    insert_liquidity(
+                        &tx,
+                        (signature, event_index, slot, block_time),
+                        "create",
+                        (&e.pool, &e.creator),
+                        (e.token_0_amount, e.token_1_amount, e.lp_amount),
+                    )?;
+                    (e.pool, e.token_0_amount, e.token_1_amount, Some(e.lp_amount))
+                }
+                AmmEvent::Swap(e) => {
+                    tx.execute(
+                        "INSERT OR IGNORE INTO trades VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                        params![
+                            signature,
+                            event_index,
+                            slot as i64,
+                            block_time,
+                            key(&e.pool),
+                            key(&e.user),
+                            e.direction,
+                            e.amount_in as i64,
+                            e.amount_out as i64,
+                            e.fee as i64,
+                            e.reserve_0_after as i64,
+                            e.reserve_1_after as i64,
+                            e.fees_bps.map(|fees_bps| fees_bps as i64)
+                        ],
+                    )?;
+                    // Swaps do not change the LP supply; carried over below.
+                    (e.pool, e.reserve_0_after, e.reserve_1_after, None)
+                }
+                AmmEvent::Deposit(e) => {
+                    insert_liquidity(
+                        &tx,
+                        (signature, event_index, slot, block_time),
+                        "deposit",
+                        (&e.pool, &e.user),
+                        (e.token_0_amount, e.token_1_amount, e.lp_amount),
+                    )?;
+                    (e.pool, e.reserve_0_after, e.reserve_1_after, Some(e.lp_supply_after))
+                }
+                AmmEvent::Withdraw(e) => {
+                    insert_liquidity(
+                        &tx,
+                        (signature, event_index, slot, block_time),
+                        "withdraw",
+                        (&e.pool, &e.user),
+                        (e.token_0_amount, e.token_1_amount, e.lp_amount),
+                    )?;
+                    (e.pool, e.reserve_0_after, e.reserve_1_after, Some(e.lp_supply_after))
+                }
+                // Only the fee stays in the pool; like swaps, the LP supply is unchanged.
+                AmmEvent::FlashLoan(e) => (e.pool, e.reserve_0_after, e.reserve_1_after, None),
+                // Leaves the reserves alone, and `pools.fees_bps` records the fee tier.
+                AmmEvent::PoolFeeUpdated(_) => continue,
+                // Follows the swap event carrying the reserves.
+                AmmEvent::ReferralFee(_) => continue,
+                // The tables hold two-token pools; multi-token pools are not indexed.
+                AmmEvent::MultiSwap(_) | AmmEvent::MultiDeposit(_) | AmmEvent::MultiWithdraw(_) => continue,
+            };
+
+            let lp_supply = match lp_supply {
+                Some(lp_supply) => lp_supply as i64,
+                None => tx
+                    .query_row(
+                        "SELECT lp_supply FROM reserves WHERE pool = ?1
+                         ORDER BY slot DESC, block_time DESC, signature DESC, event_index DESC LIMIT 1",
+                        params![key(&pool)],
+                        |row| row.get(0),
+                    )
+                    .optional()?
+                    .unwrap_or(0),
+            };
+            tx.execute(
+                "INSERT OR IGNORE INTO reserves VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    signature,
+                    event_index,
+                    slot as i64,
+                    block_time,
+                    key(&pool),
+                    reserve_0 as i64,
+                    reserve_1 as i64,
+                    lp_supply
+                ],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(events.len())
+    }
+
+    pub fn pools(&self) -> Result<Vec<PoolInfo>> {
+        let mut stmt = self.conn.prepare("SELECT * FROM pools ORDER BY created_slot")?;
+        let pools = stmt
+            .query_map([], |row| {
+                Ok(PoolInfo {
+                    pool: pubkey_column(row, 0)?,
+                    token_0_mint: pubkey_column(row, 1)?,
+                    token_1_mint: pubkey_column(row, 2)?,
+                    lp_mint: pubkey_column(row, 3)?,
+                    fees_bps: u64_column(row, 4)?,
+                    creator: pubkey_column(row, 5)?,
+                    created_slot: u64_column(row, 6)?,
+                    created_time: row.get(7)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(pools)
+    }
+
+    /// Trades of `pool` with `from <= block_time < to`, oldest first.
+    pub fn trades(&self, pool: &Pubkey, from: i64, to: i64) -> Result<Vec<Trade>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT signature, slot, block_time, pool, user, direction, amount_in, amount_out, fee,
+                    reserve_0_after, reserve_1_after, fees_bps
+             FROM trades WHERE pool = ?1 AND block_time >= ?2 AND block_time < ?3
+             ORDER BY slot, block_time, signature, event_index",
+        )?;
+        let trades = stmt
+            .query_map(params![pool.to_string(), from, to], |row| {
+                Ok(Trade {
+                    signature: row.get(0)?,
+                    slot: u64_column(row, 1)?,
+                    block_time: row.get(2)?,
+                    pool: pubkey_column(row, 3)?,
+                    user: pubkey_column(row, 4)?,
+                    direction: row.get(5)?,
+                    amount_in: u64_column(row, 6)?,
+                    amount_out: u64_column(row, 7)?,
+                    fee: u64_column(row, 8)?,
+                    reserve_0_after: u64_column(row, 9)?,
+                    reserve_1_after: u64_column(row, 10)?,
+                    fees_bps: row.get::<_, Option<i64>>(11)?.map(|fees_bps| fees_bps as u64),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(trades)
+    }
+
+    /// OHLCV candles of `interval` seconds over `[from, to)`.
+    pub fn candles(&self, pool: &Pubkey, interval: i64, from: i64, to: i64) -> Result<Vec<Candle>> {
+        build_candles(&self.trades(pool, from, to)?, interval)
+    }
+
+    /// Reserves and LP supply after every event of `pool`, oldest first. The reserves
+    /// are the pool's TVL expressed in token units.
+    pub fn reserves(&self, pool: &Pubkey) -> Result<Vec<ReserveSnapshot>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT slot, block_time, reserve_0, reserve_1, lp_supply FROM reserves
+             WHERE pool = ?1 ORDER BY slot, block_time, signature, event_index",
+        )?;
+        let snapshots = stmt
+            .query_map(params![pool.to_string()], |row| {
+                Ok(ReserveSnapshot {
+                    slot: u64_column(row, 0)?,
+                    block_time: row.get(1)?,
+                    reserve_0: u64_column(row, 2)?,
+                    reserve_1: u64_column(row, 3)?,
+                    lp_supply: u64_column(row, 4)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(snapshots)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct FixtureTransaction {
+        signature: String,
+        slot: u64,
+        block_time: i64,
+        logs: Vec<String>,
+    }
+
+    const POOL: Pubkey = Pubkey::new_from_array([7; 32]);
+
+    fn indexer_from_fixture() -> Indexer {
+        let fixture: Vec<FixtureTransaction> =
+            serde_json::from_str(include_str!("../fixtures/pool_history.json")).unwrap();
+        let mut indexer = Indexer::open_in_memory().unwrap();
+        for tx in &fixture {
+            indexer.ingest_transaction(&tx.signature, tx.slot, tx.block_time, &tx.logs).unwrap();
+        }
+        // Replaying the same transactions must not duplicate rows.
+        for tx in &fixture {
+            indexer.ingest_transaction(&tx.signature, tx.slot, tx.block_time, &tx.logs).unwrap();
+        }
+        indexer
+    }
+
+    #[test]
+    fn test_fixture_pool_history() {
+        let indexer = indexer_from_fixture();
+
+        let pools = indexer.pools().unwrap();
+        assert_eq!(pools.len(), 1);
+        assert_eq!(pools[0].pool, POOL);
+        assert_eq!(pools[0].fees_bps, 30);
+
+        let trades = indexer.trades(&POOL, 0, i64::MAX).unwrap();
+        assert_eq!(trades.len(), 3);
+        // sig2 and sig3 were logged before swap events carried the fee rate
+        assert_eq!(trades[0].fees_bps, None);
+        assert_eq!(trades[2].fees_bps, Some(30));
+
+        let candles = indexer.candles(&POOL, 60, 0, i64::MAX).unwrap();
+        assert_eq!(candles.len(), 2);
+        assert_eq!(candles[0].start_time, 1_700_000_040);
+        assert_eq!(candles[0].trades, 2);
+        assert_eq!(candles[0].volume_0, 10_000 + 10_098);
+        assert_eq!(candles[0].open, trades[0].price());
+        assert_eq!(candles[0].close, trades[1].price());
+        assert_eq!(candles[1].trades, 1);
+
+        let reserves = indexer.reserves(&POOL).unwrap();
+        assert_eq!(reserves.len(), 6);
+        assert_eq!(reserves[0].lp_supply, 1_414_213);
+        // swaps carry the LP supply forward
+        assert_eq!(reserves[1].lp_supply, 1_414_213);
+        let last = reserves.last().unwrap();
+        assert_eq!((last.reserve_0, last.reserve_1, last.lp_supply), (1_014_902, 2_010_052, 1_428_286));
+    }
+}
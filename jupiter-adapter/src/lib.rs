@@ -0,0 +1,237 @@
+//! [`Amm`] implementation so SolanaCore AMM pools can be routed by Jupiter-style aggregators.
+//!
+//! Quotes come from `v1::utils::quote`, the same code the on-chain `swap` runs, and the
+//! account list is built by `amm-client`, so the adapter never drifts from the program.
+
+use amm_client::{
+    decode_pool, quote_swap, swap as swap_ix, PoolKeys, SwapDirection, SwapMode, UserKeys, PROGRAM_ID,
+};
+use anyhow::{anyhow, Context, Result};
+use jupiter_amm_interface::{
+    AccountMap, Amm, AmmContext, ClockRef, KeyedAccount, Quote, QuoteParams, SwapAndAccountMetas, SwapParams,
+};
+use rust_decimal::Decimal;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use std::sync::atomic::Ordering;
+use v1::{states::Pool, utils::BPS_DENOMINATOR};
+
+#[derive(Clone)]
+pub struct SolanaCoreAmm {
+    keys: PoolKeys,
+    pool: Pool,
+    // stable pools quote with the amplification coefficient at the current time
+    clock: ClockRef,
+}
+
+impl SolanaCoreAmm {
+    fn direction(&self, input_mint: &Pubkey, output_mint: &Pubkey) -> Result<SwapDirection> {
+        if *input_mint == self.keys.token_0_mint && *output_mint == self.keys.token_1_mint {
+            Ok(SwapDirection::ZeroForOne)
+        } else if *input_mint == self.keys.token_1_mint && *output_mint == self.keys.token_0_mint {
+            Ok(SwapDirection::OneForZero)
+        } else {
+            Err(anyhow!("mints {input_mint} -> {output_mint} do not belong to pool {}", self.keys.pool))
+        }
+    }
+
+    /// The `swap` instruction for `swap_params`, with the accounts an aggregator would pass.
+    pub fn swap_instruction(&self, swap_params: &SwapParams) -> Result<Instruction> {
+        let direction = self.direction(&swap_params.source_mint, &swap_params.destination_mint)?;
+        let (token_0, token_1) = match direction {
+            SwapDirection::ZeroForOne => (swap_params.source_token_account, swap_params.destination_token_account),
+            SwapDirection::OneForZero => (swap_params.destination_token_account, swap_params.source_token_account),
+        };
+        let user = UserKeys {
+            owner: swap_params.token_transfer_authority,
+            token_0,
+            token_1,
+            // `swap` does not touch the LP mint.
+            lp: Pubkey::default(),
+        };
+        let mode = to_swap_mode(swap_params.swap_mode);
+        let (amount, expected_amount) = match mode {
+            SwapMode::ExactIn => (swap_params.in_amount, swap_params.out_amount),
+            SwapMode::ExactOut => (swap_params.out_amount, swap_params.in_amount),
+        };
+        Ok(swap_ix(
+            &self.keys,
+            &user,
+            v1::states::Swap {
+                amount,
+                expected_amount,
+                // The aggregator enforces slippage on the whole route.
+                slippage_bps: 0,
+                direction,
+                mode,
+            },
+        ))
+    }
+}
+
+fn to_swap_mode(mode: jupiter_amm_interface::SwapMode) -> SwapMode {
+    match mode {
+        jupiter_amm_interface::SwapMode::ExactIn => SwapMode::ExactIn,
+        jupiter_amm_interface::SwapMode::ExactOut => SwapMode::ExactOut,
+    }
+}
+
+impl Amm for SolanaCoreAmm {
+    fn from_keyed_account(keyed_account: &KeyedAccount, amm_context: &AmmContext) -> Result<Self> {
+        if keyed_account.account.owner != PROGRAM_ID {
+            return Err(anyhow!(
+                "account {} is owned by {}, not {PROGRAM_ID}",
+                keyed_account.key,
+                keyed_account.account.owner
+            ));
+        }
+        let pool = decode_pool(&keyed_account.account.data)
+            .map_err(|e| anyhow!("invalid pool account {}: {e}", keyed_account.key))?;
+        Ok(Self {
+            keys: PoolKeys::from_pool(&keyed_account.key, &pool),
+            pool,
+            clock: amm_context.clock_ref.clone(),
+        })
+    }
+
+    fn label(&self) -> String {
+        "SolanaCore AMM".to_string()
+    }
+
+    fn program_id(&self) -> Pubkey {
+        PROGRAM_ID
+    }
+
+    fn key(&self) -> Pubkey {
+        self.keys.pool
+    }
+
+    fn get_reserve_mints(&self) -> Vec<Pubkey> {
+        vec![self.keys.token_0_mint, self.keys.token_1_mint]
+    }
+
+    fn get_accounts_to_update(&self) -> Vec<Pubkey> {
+        // Reserves are tracked in the pool account itself.
+        vec![self.keys.pool]
+    }
+
+    fn update(&mut self, account_map: &AccountMap) -> Result<()> {
+        let account = account_map
+            .get(&self.keys.pool)
+            .with_context(|| format!("pool account {} not found", self.keys.pool))?;
+        self.pool = decode_pool(&account.data).map_err(|e| anyhow!("invalid pool account: {e}"))?;
+        Ok(())
+    }
+
+    fn quote(&self, quote_params: &QuoteParams) -> Result<Quote> {
+        let direction = self.direction(&quote_params.input_mint, &quote_params.output_mint)?;
+        let now = self.clock.unix_timestamp.load(Ordering::Relaxed);
+        let quote = quote_swap(&self.pool, quote_params.amount, direction, to_swap_mode(quote_params.swap_mode), now)
+            .map_err(|e| anyhow!("quote failed: {e}"))?;
+
+        Ok(Quote {
+            in_amount: quote.amount_in,
+            out_amount: quote.amount_out,
+            fee_amount: quote.fee,
+            fee_mint: quote_params.input_mint,
+            // the rate actually charged: the live fee rate or the dynamic fee, not the fee tier
+            fee_pct: Decimal::from(quote.fees_bps) / Decimal::from(BPS_DENOMINATOR),
+            ..Quote::default()
+        })
+    }
+
+    fn get_swap_and_account_metas(&self, _swap_params: &SwapParams) -> Result<SwapAndAccountMetas> {
+        // `Swap` has one variant per program the aggregator has integrated and none is
+        // assigned to this AMM until it is onboarded there; any other variant would label
+        // this program's instruction as another program's.
+        Err(anyhow!(
+            "no aggregator swap variant is assigned to {PROGRAM_ID} yet; build the instruction with SolanaCoreAmm::swap_instruction"
+        ))
+    }
+
+    fn clone_amm(&self) -> Box<dyn Amm + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn supports_exact_out(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::account::Account;
+    use v1::utils::{AccountDiscriminator, DataLen};
+
+    fn pool_account(keys: &PoolKeys, reserve_0: u64, reserve_1: u64) -> Account {
+        let mut data = vec![0u8; Pool::LEN];
+        data[..8].copy_from_slice(&Pool::DISCRIMINATOR);
+        data[Pool::TOKEN_0_MINT_OFFSET..][..32].copy_from_slice(keys.token_0_mint.as_ref());
+        data[Pool::TOKEN_1_MINT_OFFSET..][..32].copy_from_slice(keys.token_1_mint.as_ref());
+        data[Pool::VAULT_0_OFFSET..][..32].copy_from_slice(keys.vault_0.as_ref());
+        data[Pool::VAULT_1_OFFSET..][..32].copy_from_slice(keys.vault_1.as_ref());
+        data[Pool::LP_MINT_OFFSET..][..32].copy_from_slice(keys.lp_mint.as_ref());
+        data[Pool::TOKEN_0_AMOUNT_OFFSET..][..8].copy_from_slice(&reserve_0.to_le_bytes());
+        data[Pool::TOKEN_1_AMOUNT_OFFSET..][..8].copy_from_slice(&reserve_1.to_le_bytes());
+        data[Pool::FEES_BPS_OFFSET..][..8].copy_from_slice(&keys.fees_bps.to_le_bytes());
+        data[Pool::FEE_RATE_BPS_OFFSET..][..8].copy_from_slice(&keys.fees_bps.to_le_bytes());
+        Account {
+            lamports: 1,
+            data,
+            owner: PROGRAM_ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn test_quote_and_account_metas() {
+        let keys = PoolKeys::derive(&Pubkey::new_unique(), &Pubkey::new_unique(), 30);
+        let keyed_account = KeyedAccount {
+            key: keys.pool,
+            account: pool_account(&keys, 1_000_000, 2_000_000),
+            params: None,
+        };
+        let amm = SolanaCoreAmm::from_keyed_account(&keyed_account, &AmmContext::default()).unwrap();
+        assert_eq!(amm.get_reserve_mints(), vec![keys.token_0_mint, keys.token_1_mint]);
+
+        let quote = amm
+            .quote(&QuoteParams {
+                amount: 10_000,
+                input_mint: keys.token_0_mint,
+                output_mint: keys.token_1_mint,
+                swap_mode: jupiter_amm_interface::SwapMode::ExactIn,
+            })
+            .unwrap();
+        let expected = amm_client::quote_exact_in(1_000_000, 2_000_000, 30, 10_000, SwapDirection::ZeroForOne).unwrap();
+        assert_eq!(quote.out_amount, expected.amount_out);
+        assert_eq!(quote.fee_pct, Decimal::new(30, 4));
+
+        let owner = Pubkey::new_unique();
+        let (source, destination) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let swap_params = SwapParams {
+            source_mint: keys.token_1_mint,
+            destination_mint: keys.token_0_mint,
+            source_token_account: source,
+            destination_token_account: destination,
+            token_transfer_authority: owner,
+            in_amount: 10_000,
+            out_amount: 4_000,
+            swap_mode: jupiter_amm_interface::SwapMode::ExactIn,
+            ..SwapParams::default()
+        };
+        // no swap variant is assigned to this program yet, so routing through it must fail
+        assert!(amm.get_swap_and_account_metas(&swap_params).is_err());
+
+        let metas = amm.swap_instruction(&swap_params).unwrap().accounts;
+        assert_eq!(metas.len(), 11);
+        assert_eq!(metas[0].pubkey, owner);
+        assert_eq!(metas[2].pubkey, destination);
+        assert_eq!(metas[3].pubkey, source);
+
+        // accounts of other programs are not pools, whatever their data
+        let mut foreign = keyed_account;
+        foreign.account.owner = Pubkey::new_unique();
+        assert!(SolanaCoreAmm::from_keyed_account(&foreign, &AmmContext::default()).is_err());
+    }
+}
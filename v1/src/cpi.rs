@@ -0,0 +1,666 @@
+//! Zero-allocation CPI helpers for calling the AMM from other pinocchio programs,
+//! in the style of `pinocchio_token::instructions`. Enable with the `cpi` feature:
+//!
+//! ```ignore
+//! v1::cpi::Swap { user, pool, /* ... */ observations: None, referral: None, args }.invoke_signed(&signers)?;
+//! ```
+//!
+//! Account order matches the `ShankInstruction` definitions in `instructions::ix`.
+//! Covered: `InitPool`, `LiquidatePool`, `DeliquidatePool`, `Swap` (plain and referred),
+//! `IncreaseObservationCardinality`, `ObserveTwap`, `GetPrice`, `InitConfig`, `RampAmp`
+//! and `ClSwap`. Flash swaps, `RouteSwap`, `FlashLoan`/`FlashRepay`, the multi-token
+//! and CL pool and position instructions, `UpdatePoolFee` and `InitReferrer` have no
+//! helper yet; build those instructions by hand from `instructions::ix`.
+
+use pinocchio::{
+    account_info::AccountInfo,
+    cpi::invoke_signed,
+    instruction::{AccountMeta, Instruction, Signer},
+    program_error::ProgramError,
+    ProgramResult,
+};
+
+use crate::{
+    instructions::{
+        DELIQUIDATE_POOL_DISCRIMINATOR, GET_PRICE_DISCRIMINATOR, INCREASE_OBSERVATION_CARDINALITY_DISCRIMINATOR,
+        INIT_CONFIG_DISCRIMINATOR, INIT_POOL_DISCRIMINATOR, LIQUIDATE_POOL_DISCRIMINATOR, OBSERVE_TWAP_DISCRIMINATOR,
+        RAMP_AMP_DISCRIMINATOR, SWAP_DISCRIMINATOR, CL_SWAP_DISCRIMINATOR,
+    },
+    states,
+    utils::InstructionData,
+};
+
+// Writes `discriminator | payload` into `data` and invokes the AMM. Bytes of `data`
+// past the payload are sent as they are.
+#[inline(always)]
+fn invoke_amm<const ACCOUNTS: usize>(
+    discriminator: u8,
+    payload: &impl InstructionData,
+    data: &mut [u8],
+    account_metas: &[AccountMeta; ACCOUNTS],
+    account_infos: &[&AccountInfo; ACCOUNTS],
+    signers: &[Signer],
+) -> ProgramResult {
+    data[0] = discriminator;
+    payload.pack_into(&mut data[1..]).map_err(ProgramError::from)?;
+
+    let instruction = Instruction {
+        program_id: &crate::ID,
+        accounts: account_metas,
+        data,
+    };
+    invoke_signed(&instruction, account_infos, signers)
+}
+
+/// Accounts shared by `LiquidatePool` and `DeliquidatePool`, and the first 13 of `InitPool`.
+pub struct LiquidityAccounts<'a> {
+    /// Signer and payer.
+    pub user: &'a AccountInfo,
+    pub pool: &'a AccountInfo,
+    pub user_token_0: &'a AccountInfo,
+    pub user_token_1: &'a AccountInfo,
+    pub token_0_mint: &'a AccountInfo,
+    pub token_1_mint: &'a AccountInfo,
+    pub vault_0: &'a AccountInfo,
+    pub vault_1: &'a AccountInfo,
+    pub lp_mint: &'a AccountInfo,
+    pub user_lp: &'a AccountInfo,
+    pub rent_sysvar: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+}
+
+impl<'a> LiquidityAccounts<'a> {
+    #[inline(always)]
+    pub(crate) fn metas(&self) -> [AccountMeta<'a>; 13] {
+        [
+            AccountMeta::writable_signer(self.user.key()),
+            AccountMeta::writable(self.pool.key()),
+            AccountMeta::writable(self.user_token_0.key()),
+            AccountMeta::writable(self.user_token_1.key()),
+            AccountMeta::readonly(self.token_0_mint.key()),
+            AccountMeta::readonly(self.token_1_mint.key()),
+            AccountMeta::writable(self.vault_0.key()),
+            AccountMeta::writable(self.vault_1.key()),
+            AccountMeta::writable(self.lp_mint.key()),
+            AccountMeta::writable(self.user_lp.key()),
+            AccountMeta::readonly(self.rent_sysvar.key()),
+            AccountMeta::readonly(self.system_program.key()),
+            AccountMeta::readonly(self.token_program.key()),
+        ]
+    }
+
+    #[inline(always)]
+    fn infos(&self) -> [&'a AccountInfo; 13] {
+        [
+            self.user,
+            self.pool,
+            self.user_token_0,
+            self.user_token_1,
+            self.token_0_mint,
+            self.token_1_mint,
+            self.vault_0,
+            self.vault_1,
+            self.lp_mint,
+            self.user_lp,
+            self.rent_sysvar,
+            self.system_program,
+            self.token_program,
+        ]
+    }
+}
+
+/// Accounts of `InitPool`: the liquidity accounts, then the config and the treasury it
+/// pays the pool creation fee to.
+pub struct InitPoolAccounts<'a> {
+    pub liquidity: LiquidityAccounts<'a>,
+    pub config: &'a AccountInfo,
+    pub treasury: &'a AccountInfo,
+    /// `(fee_token_account, fee_mint)`: the user's account of the fee mint and the mint,
+    /// when the config charges the creation fee in a token rather than lamports.
+    pub fee_token: Option<(&'a AccountInfo, &'a AccountInfo)>,
+}
+
+impl<'a> InitPoolAccounts<'a> {
+    #[inline(always)]
+    pub(crate) fn metas(&self) -> [AccountMeta<'a>; 15] {
+        let [user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint, vault_0, vault_1, lp_mint, user_lp, rent_sysvar, system_program, token_program] =
+            self.liquidity.metas();
+        [
+            user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint,
+            vault_0, vault_1, lp_mint, user_lp, rent_sysvar, system_program, token_program,
+            AccountMeta::readonly(self.config.key()),
+            AccountMeta::writable(self.treasury.key()),
+        ]
+    }
+
+    #[inline(always)]
+    pub(crate) fn metas_with_fee_token(
+        &self,
+        fee_token_account: &'a AccountInfo,
+        fee_mint: &'a AccountInfo,
+    ) -> [AccountMeta<'a>; 17] {
+        let [user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint, vault_0, vault_1, lp_mint, user_lp, rent_sysvar, system_program, token_program, config, treasury] =
+            self.metas();
+        [
+            user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint,
+            vault_0, vault_1, lp_mint, user_lp, rent_sysvar, system_program, token_program,
+            config, treasury,
+            AccountMeta::writable(fee_token_account.key()),
+            AccountMeta::readonly(fee_mint.key()),
+        ]
+    }
+
+    #[inline(always)]
+    fn infos(&self) -> [&'a AccountInfo; 15] {
+        let l = &self.liquidity;
+        [
+            l.user, l.pool, l.user_token_0, l.user_token_1, l.token_0_mint, l.token_1_mint,
+            l.vault_0, l.vault_1, l.lp_mint, l.user_lp, l.rent_sysvar, l.system_program, l.token_program,
+            self.config, self.treasury,
+        ]
+    }
+}
+
+/// Creates a pool and seeds it with the initial reserves, paying the config's pool creation fee.
+pub struct InitPool<'a> {
+    pub accounts: InitPoolAccounts<'a>,
+    pub args: states::InitPool,
+}
+
+impl InitPool<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let mut data = [0u8; 1 + <states::InitPool as InstructionData>::LEN];
+        match self.accounts.fee_token {
+            None => invoke_amm(
+                INIT_POOL_DISCRIMINATOR,
+                &self.args,
+                &mut data,
+                &self.accounts.metas(),
+                &self.accounts.infos(),
+                signers,
+            ),
+            Some((fee_token_account, fee_mint)) => {
+                let [user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint, vault_0, vault_1, lp_mint, user_lp, rent_sysvar, system_program, token_program, config, treasury] =
+                    self.accounts.infos();
+                invoke_amm(
+                    INIT_POOL_DISCRIMINATOR,
+                    &self.args,
+                    &mut data,
+                    &self.accounts.metas_with_fee_token(fee_token_account, fee_mint),
+                    &[
+                        user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint,
+                        vault_0, vault_1, lp_mint, user_lp, rent_sysvar, system_program, token_program,
+                        config, treasury, fee_token_account, fee_mint,
+                    ],
+                    signers,
+                )
+            }
+        }
+    }
+}
+
+/// Deposits both tokens and mints LP tokens to `user_lp`.
+pub struct LiquidatePool<'a> {
+    pub accounts: LiquidityAccounts<'a>,
+    pub args: states::LiquidatePool,
+}
+
+impl LiquidatePool<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let mut data = [0u8; 1 + <states::LiquidatePool as InstructionData>::LEN];
+        invoke_amm(
+            LIQUIDATE_POOL_DISCRIMINATOR,
+            &self.args,
+            &mut data,
+            &self.accounts.metas(),
+            &self.accounts.infos(),
+            signers,
+        )
+    }
+}
+
+/// Burns LP tokens from `user_lp` and withdraws both tokens.
+pub struct DeliquidatePool<'a> {
+    pub accounts: LiquidityAccounts<'a>,
+    pub args: states::DeliquidatePool,
+}
+
+impl DeliquidatePool<'_> {
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let mut data = [0u8; 1 + <states::DeliquidatePool as InstructionData>::LEN];
+        invoke_amm(
+            DELIQUIDATE_POOL_DISCRIMINATOR,
+            &self.args,
+            &mut data,
+            &self.accounts.metas(),
+            &self.accounts.infos(),
+            signers,
+        )
+    }
+}
+
+/// Referral accounts of a referred [`Swap`], passed after the observations account.
+pub struct SwapReferralAccounts<'a> {
+    pub config: &'a AccountInfo,
+    /// Receives the referral fee, in the input mint.
+    pub referrer_token_account: &'a AccountInfo,
+    /// Referrer stats PDA, also counts the swap when passed.
+    pub referrer_stats: Option<&'a AccountInfo>,
+    pub args: states::SwapReferral,
+}
+
+impl<'a> SwapReferralAccounts<'a> {
+    #[inline(always)]
+    pub(crate) fn metas(&self) -> [AccountMeta<'a>; 2] {
+        [AccountMeta::readonly(self.config.key()), AccountMeta::writable(self.referrer_token_account.key())]
+    }
+}
+
+/// Swaps through the pool, optionally paying a referral fee. Flash swaps are not covered.
+pub struct Swap<'a> {
+    /// Signer, owner of the user token accounts.
+    pub user: &'a AccountInfo,
+    pub pool: &'a AccountInfo,
+    pub user_token_0: &'a AccountInfo,
+    pub user_token_1: &'a AccountInfo,
+    pub token_0_mint: &'a AccountInfo,
+    pub token_1_mint: &'a AccountInfo,
+    pub vault_0: &'a AccountInfo,
+    pub vault_1: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    /// Pool observations PDA, records a TWAP observation once created. Referred swaps
+    /// must pass it, created or not.
+    pub observations: Option<&'a AccountInfo>,
+    pub referral: Option<SwapReferralAccounts<'a>>,
+    pub args: states::Swap,
+}
+
+impl<'a> Swap<'a> {
+    #[inline(always)]
+    pub(crate) fn metas(&self) -> [AccountMeta<'a>; 10] {
+        [
+            AccountMeta::writable_signer(self.user.key()),
+            AccountMeta::writable(self.pool.key()),
+            AccountMeta::writable(self.user_token_0.key()),
+            AccountMeta::writable(self.user_token_1.key()),
+            AccountMeta::readonly(self.token_0_mint.key()),
+            AccountMeta::readonly(self.token_1_mint.key()),
+            AccountMeta::writable(self.vault_0.key()),
+            AccountMeta::writable(self.vault_1.key()),
+            AccountMeta::readonly(self.system_program.key()),
+            AccountMeta::readonly(self.token_program.key()),
+        ]
+    }
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        // a referred swap's `SwapReferral` follows the `Swap` payload
+        const SWAP_DATA_LEN: usize = 1 + <states::Swap as InstructionData>::LEN;
+        let mut data = [0u8; SWAP_DATA_LEN + <states::SwapReferral as InstructionData>::LEN];
+        let [user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint, vault_0, vault_1, system_program, token_program] =
+            self.metas();
+
+        match (self.observations, &self.referral) {
+            (None, None) => invoke_amm(
+                SWAP_DISCRIMINATOR,
+                &self.args,
+                &mut data[..SWAP_DATA_LEN],
+                &[
+                    user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint,
+                    vault_0, vault_1, system_program, token_program,
+                ],
+                &[
+                    self.user, self.pool, self.user_token_0, self.user_token_1, self.token_0_mint, self.token_1_mint,
+                    self.vault_0, self.vault_1, self.system_program, self.token_program,
+                ],
+                signers,
+            ),
+            (Some(observations), None) => invoke_amm(
+                SWAP_DISCRIMINATOR,
+                &self.args,
+                &mut data[..SWAP_DATA_LEN],
+                &[
+                    user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint,
+                    vault_0, vault_1, system_program, token_program,
+                    AccountMeta::writable(observations.key()),
+                ],
+                &[
+                    self.user, self.pool, self.user_token_0, self.user_token_1, self.token_0_mint, self.token_1_mint,
+                    self.vault_0, self.vault_1, self.system_program, self.token_program,
+                    observations,
+                ],
+                signers,
+            ),
+            (Some(observations), Some(referral)) => {
+                referral.args.pack_into(&mut data[SWAP_DATA_LEN..]).map_err(ProgramError::from)?;
+                let [config, referrer_token_account] = referral.metas();
+                match referral.referrer_stats {
+                    None => invoke_amm(
+                        SWAP_DISCRIMINATOR,
+                        &self.args,
+                        &mut data,
+                        &[
+                            user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint,
+                            vault_0, vault_1, system_program, token_program,
+                            AccountMeta::writable(observations.key()),
+                            config, referrer_token_account,
+                        ],
+                        &[
+                            self.user, self.pool, self.user_token_0, self.user_token_1, self.token_0_mint, self.token_1_mint,
+                            self.vault_0, self.vault_1, self.system_program, self.token_program,
+                            observations, referral.config, referral.referrer_token_account,
+                        ],
+                        signers,
+                    ),
+                    Some(referrer_stats) => invoke_amm(
+                        SWAP_DISCRIMINATOR,
+                        &self.args,
+                        &mut data,
+                        &[
+                            user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint,
+                            vault_0, vault_1, system_program, token_program,
+                            AccountMeta::writable(observations.key()),
+                            config, referrer_token_account,
+                            AccountMeta::writable(referrer_stats.key()),
+                        ],
+                        &[
+                            self.user, self.pool, self.user_token_0, self.user_token_1, self.token_0_mint, self.token_1_mint,
+                            self.vault_0, self.vault_1, self.system_program, self.token_program,
+                            observations, referral.config, referral.referrer_token_account, referrer_stats,
+                        ],
+                        signers,
+                    ),
+                }
+            }
+            // the program reads the referral accounts after the observations slot
+            (None, Some(_)) => Err(ProgramError::NotEnoughAccountKeys),
+        }
+    }
+}
+
+/// Creates or grows the pool's observations account.
+pub struct IncreaseObservationCardinality<'a> {
+    /// Signer, funds the rent.
+    pub payer: &'a AccountInfo,
+    pub pool: &'a AccountInfo,
+    pub observations: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub args: states::IncreaseObservationCardinality,
+}
+
+impl<'a> IncreaseObservationCardinality<'a> {
+    #[inline(always)]
+    pub(crate) fn metas(&self) -> [AccountMeta<'a>; 4] {
+        [
+            AccountMeta::writable_signer(self.payer.key()),
+            AccountMeta::readonly(self.pool.key()),
+            AccountMeta::writable(self.observations.key()),
+            AccountMeta::readonly(self.system_program.key()),
+        ]
+    }
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let mut data = [0u8; 1 + <states::IncreaseObservationCardinality as InstructionData>::LEN];
+        invoke_amm(
+            INCREASE_OBSERVATION_CARDINALITY_DISCRIMINATOR,
+            &self.args,
+            &mut data,
+            &self.metas(),
+            &[self.payer, self.pool, self.observations, self.system_program],
+            signers,
+        )
+    }
+}
+
+/// TWAP query; read the result with `pinocchio::program::get_return_data`.
+pub struct ObserveTwap<'a> {
+    pub pool: &'a AccountInfo,
+    pub observations: &'a AccountInfo,
+    pub args: states::ObserveTwap,
+}
+
+impl<'a> ObserveTwap<'a> {
+    #[inline(always)]
+    pub(crate) fn metas(&self) -> [AccountMeta<'a>; 2] {
+        [AccountMeta::readonly(self.pool.key()), AccountMeta::readonly(self.observations.key())]
+    }
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        let mut data = [0u8; 1 + <states::ObserveTwap as InstructionData>::LEN];
+        invoke_amm(
+            OBSERVE_TWAP_DISCRIMINATOR,
+            &self.args,
+            &mut data,
+            &self.metas(),
+            &[self.pool, self.observations],
+            &[],
+        )
+    }
+}
+
+/// Price query; decode the return data with `instructions::PriceQuote::unpack`.
+pub struct GetPrice<'a> {
+    pub pool: &'a AccountInfo,
+    pub lp_mint: &'a AccountInfo,
+    pub args: states::GetPrice,
+}
+
+impl<'a> GetPrice<'a> {
+    #[inline(always)]
+    pub(crate) fn metas(&self) -> [AccountMeta<'a>; 2] {
+        [AccountMeta::readonly(self.pool.key()), AccountMeta::readonly(self.lp_mint.key())]
+    }
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        let mut data = [0u8; 1 + <states::GetPrice as InstructionData>::LEN];
+        invoke_amm(
+            GET_PRICE_DISCRIMINATOR,
+            &self.args,
+            &mut data,
+            &self.metas(),
+            &[self.pool, self.lp_mint],
+            &[],
+        )
+    }
+}
+
+/// Creates the config PDA; `authority` must be the program's upgrade authority.
+pub struct InitConfig<'a> {
+    pub authority: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub program_data: &'a AccountInfo,
+    pub system_program: &'a AccountInfo,
+    pub args: states::InitConfig,
+}
+
+impl<'a> InitConfig<'a> {
+    #[inline(always)]
+    pub(crate) fn metas(&self) -> [AccountMeta<'a>; 4] {
+        [
+            AccountMeta::writable_signer(self.authority.key()),
+            AccountMeta::writable(self.config.key()),
+            AccountMeta::readonly(self.program_data.key()),
+            AccountMeta::readonly(self.system_program.key()),
+        ]
+    }
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let mut data = [0u8; 1 + <states::InitConfig as InstructionData>::LEN];
+        invoke_amm(
+            INIT_CONFIG_DISCRIMINATOR,
+            &self.args,
+            &mut data,
+            &self.metas(),
+            &[self.authority, self.config, self.program_data, self.system_program],
+            signers,
+        )
+    }
+}
+
+/// Ramps a stable pool's amplification coefficient; `admin` must be the config admin.
+pub struct RampAmp<'a> {
+    pub admin: &'a AccountInfo,
+    pub config: &'a AccountInfo,
+    pub pool: &'a AccountInfo,
+    pub args: states::RampAmp,
+}
+
+impl<'a> RampAmp<'a> {
+    #[inline(always)]
+    pub(crate) fn metas(&self) -> [AccountMeta<'a>; 3] {
+        [
+            AccountMeta::readonly_signer(self.admin.key()),
+            AccountMeta::readonly(self.config.key()),
+            AccountMeta::writable(self.pool.key()),
+        ]
+    }
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let mut data = [0u8; 1 + <states::RampAmp as InstructionData>::LEN];
+        invoke_amm(
+            RAMP_AMP_DISCRIMINATOR,
+            &self.args,
+            &mut data,
+            &self.metas(),
+            &[self.admin, self.config, self.pool],
+            signers,
+        )
+    }
+}
+
+/// Swaps through a concentrated-liquidity pool.
+pub struct ClSwap<'a> {
+    /// Signer, owner of the user token accounts.
+    pub user: &'a AccountInfo,
+    pub pool: &'a AccountInfo,
+    pub user_token_0: &'a AccountInfo,
+    pub user_token_1: &'a AccountInfo,
+    pub token_0_mint: &'a AccountInfo,
+    pub token_1_mint: &'a AccountInfo,
+    pub vault_0: &'a AccountInfo,
+    pub vault_1: &'a AccountInfo,
+    pub token_program: &'a AccountInfo,
+    /// Tick array holding the pool's current tick.
+    pub tick_array: &'a AccountInfo,
+    /// Up to two following tick arrays in the swap direction.
+    pub next_tick_arrays: &'a [&'a AccountInfo],
+    pub args: states::Swap,
+}
+
+impl<'a> ClSwap<'a> {
+    #[inline(always)]
+    pub(crate) fn metas(&self) -> [AccountMeta<'a>; 10] {
+        [
+            AccountMeta::readonly_signer(self.user.key()),
+            AccountMeta::writable(self.pool.key()),
+            AccountMeta::writable(self.user_token_0.key()),
+            AccountMeta::writable(self.user_token_1.key()),
+            AccountMeta::readonly(self.token_0_mint.key()),
+            AccountMeta::readonly(self.token_1_mint.key()),
+            AccountMeta::writable(self.vault_0.key()),
+            AccountMeta::writable(self.vault_1.key()),
+            AccountMeta::readonly(self.token_program.key()),
+            AccountMeta::writable(self.tick_array.key()),
+        ]
+    }
+
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_signed(&[])
+    }
+
+    pub fn invoke_signed(&self, signers: &[Signer]) -> ProgramResult {
+        let mut data = [0u8; 1 + <states::Swap as InstructionData>::LEN];
+        let [user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint, vault_0, vault_1, token_program, tick_array] =
+            self.metas();
+
+        match self.next_tick_arrays {
+            [] => invoke_amm(
+                CL_SWAP_DISCRIMINATOR,
+                &self.args,
+                &mut data,
+                &[
+                    user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint,
+                    vault_0, vault_1, token_program, tick_array,
+                ],
+                &[
+                    self.user, self.pool, self.user_token_0, self.user_token_1, self.token_0_mint, self.token_1_mint,
+                    self.vault_0, self.vault_1, self.token_program, self.tick_array,
+                ],
+                signers,
+            ),
+            [tick_array_1] => invoke_amm(
+                CL_SWAP_DISCRIMINATOR,
+                &self.args,
+                &mut data,
+                &[
+                    user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint,
+                    vault_0, vault_1, token_program, tick_array,
+                    AccountMeta::writable(tick_array_1.key()),
+                ],
+                &[
+                    self.user, self.pool, self.user_token_0, self.user_token_1, self.token_0_mint, self.token_1_mint,
+                    self.vault_0, self.vault_1, self.token_program, self.tick_array,
+                    tick_array_1,
+                ],
+                signers,
+            ),
+            [tick_array_1, tick_array_2] => invoke_amm(
+                CL_SWAP_DISCRIMINATOR,
+                &self.args,
+                &mut data,
+                &[
+                    user, pool, user_token_0, user_token_1, token_0_mint, token_1_mint,
+                    vault_0, vault_1, token_program, tick_array,
+                    AccountMeta::writable(tick_array_1.key()),
+                    AccountMeta::writable(tick_array_2.key()),
+                ],
+                &[
+                    self.user, self.pool, self.user_token_0, self.user_token_1, self.token_0_mint, self.token_1_mint,
+                    self.vault_0, self.vault_1, self.token_program, self.tick_array,
+                    tick_array_1, tick_array_2,
+                ],
+                signers,
+            ),
+            _ => Err(ProgramError::NotEnoughAccountKeys),
+        }
+    }
+}
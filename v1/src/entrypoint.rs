@@ -1,7 +1,22 @@
 #![allow(unexpected_cfgs)]
 
 use crate::errors::SolanaCoreError;
-use crate::instructions::{init_pool, liquidate_pool, deliquidate_pool, swap};
+use crate::instructions::{
+    init_pool, liquidate_pool, deliquidate_pool, swap, increase_observation_cardinality, observe_twap,
+    get_price, init_config, ramp_amp, init_cl_pool, init_tick_array, open_position, increase_liquidity,
+    decrease_liquidity, cl_swap, init_multi_pool, multi_swap, multi_deposit, multi_withdraw,
+    multi_deposit_single, multi_withdraw_single, route_swap, flash_loan, flash_repay, set_dynamic_fee,
+    update_pool_fee, init_referrer, set_pool_creation_fee,
+    INIT_POOL_DISCRIMINATOR, LIQUIDATE_POOL_DISCRIMINATOR, DELIQUIDATE_POOL_DISCRIMINATOR,
+    SWAP_DISCRIMINATOR, INCREASE_OBSERVATION_CARDINALITY_DISCRIMINATOR, OBSERVE_TWAP_DISCRIMINATOR,
+    GET_PRICE_DISCRIMINATOR, INIT_CONFIG_DISCRIMINATOR, RAMP_AMP_DISCRIMINATOR, INIT_CL_POOL_DISCRIMINATOR,
+    INIT_TICK_ARRAY_DISCRIMINATOR, OPEN_POSITION_DISCRIMINATOR, INCREASE_LIQUIDITY_DISCRIMINATOR,
+    DECREASE_LIQUIDITY_DISCRIMINATOR, CL_SWAP_DISCRIMINATOR, INIT_MULTI_POOL_DISCRIMINATOR,
+    MULTI_SWAP_DISCRIMINATOR, MULTI_DEPOSIT_DISCRIMINATOR, MULTI_WITHDRAW_DISCRIMINATOR,
+    MULTI_DEPOSIT_SINGLE_DISCRIMINATOR, MULTI_WITHDRAW_SINGLE_DISCRIMINATOR, ROUTE_SWAP_DISCRIMINATOR,
+    FLASH_LOAN_DISCRIMINATOR, FLASH_REPAY_DISCRIMINATOR, SET_DYNAMIC_FEE_DISCRIMINATOR,
+    UPDATE_POOL_FEE_DISCRIMINATOR, INIT_REFERRER_DISCRIMINATOR, SET_POOL_CREATION_FEE_DISCRIMINATOR,
+};
 
 use pinocchio::{
     account_info::AccountInfo, 
@@ -19,12 +34,6 @@ program_entrypoint!(process_instruction);
 
 no_allocator!();
 
-// Instruction discriminators
-const INIT_POOL_DISCRIMINATOR: u8 = 0;
-const LIQUIDATE_POOL_DISCRIMINATOR: u8 = 1;
-const DELIQUIDATE_POOL_DISCRIMINATOR: u8 = 2;
-const SWAP_DISCRIMINATOR: u8 = 3;
-
 #[inline(always)]
 fn process_instruction(
     _program_id: &Pubkey,
@@ -70,6 +79,198 @@ fn process_instruction(
                     ProgramError::from(e)
                 })
         }
+        INCREASE_OBSERVATION_CARDINALITY_DISCRIMINATOR => {
+            msg!("Instruction: IncreaseObservationCardinality");
+            increase_observation_cardinality(accounts, data)
+                .map_err(|e| {
+                    msg!("IncreaseObservationCardinality failed");
+                    ProgramError::from(e)
+                })
+        }
+        OBSERVE_TWAP_DISCRIMINATOR => {
+            msg!("Instruction: ObserveTwap");
+            observe_twap(accounts, data)
+                .map_err(|e| {
+                    msg!("ObserveTwap failed");
+                    ProgramError::from(e)
+                })
+        }
+        GET_PRICE_DISCRIMINATOR => {
+            msg!("Instruction: GetPrice");
+            get_price(accounts, data)
+                .map_err(|e| {
+                    msg!("GetPrice failed");
+                    ProgramError::from(e)
+                })
+        }
+        INIT_CONFIG_DISCRIMINATOR => {
+            msg!("Instruction: InitConfig");
+            init_config(accounts, data)
+                .map_err(|e| {
+                    msg!("InitConfig failed");
+                    ProgramError::from(e)
+                })
+        }
+        RAMP_AMP_DISCRIMINATOR => {
+            msg!("Instruction: RampAmp");
+            ramp_amp(accounts, data)
+                .map_err(|e| {
+                    msg!("RampAmp failed");
+                    ProgramError::from(e)
+                })
+        }
+        INIT_CL_POOL_DISCRIMINATOR => {
+            msg!("Instruction: InitClPool");
+            init_cl_pool(accounts, data)
+                .map_err(|e| {
+                    msg!("InitClPool failed");
+                    ProgramError::from(e)
+                })
+        }
+        INIT_TICK_ARRAY_DISCRIMINATOR => {
+            msg!("Instruction: InitTickArray");
+            init_tick_array(accounts, data)
+                .map_err(|e| {
+                    msg!("InitTickArray failed");
+                    ProgramError::from(e)
+                })
+        }
+        OPEN_POSITION_DISCRIMINATOR => {
+            msg!("Instruction: OpenPosition");
+            open_position(accounts, data)
+                .map_err(|e| {
+                    msg!("OpenPosition failed");
+                    ProgramError::from(e)
+                })
+        }
+        INCREASE_LIQUIDITY_DISCRIMINATOR => {
+            msg!("Instruction: IncreaseLiquidity");
+            increase_liquidity(accounts, data)
+                .map_err(|e| {
+                    msg!("IncreaseLiquidity failed");
+                    ProgramError::from(e)
+                })
+        }
+        DECREASE_LIQUIDITY_DISCRIMINATOR => {
+            msg!("Instruction: DecreaseLiquidity");
+            decrease_liquidity(accounts, data)
+                .map_err(|e| {
+                    msg!("DecreaseLiquidity failed");
+                    ProgramError::from(e)
+                })
+        }
+        CL_SWAP_DISCRIMINATOR => {
+            msg!("Instruction: ClSwap");
+            cl_swap(accounts, data)
+                .map_err(|e| {
+                    msg!("ClSwap failed");
+                    ProgramError::from(e)
+                })
+        }
+        INIT_MULTI_POOL_DISCRIMINATOR => {
+            msg!("Instruction: InitMultiPool");
+            init_multi_pool(accounts, data)
+                .map_err(|e| {
+                    msg!("InitMultiPool failed");
+                    ProgramError::from(e)
+                })
+        }
+        MULTI_SWAP_DISCRIMINATOR => {
+            msg!("Instruction: MultiSwap");
+            multi_swap(accounts, data)
+                .map_err(|e| {
+                    msg!("MultiSwap failed");
+                    ProgramError::from(e)
+                })
+        }
+        MULTI_DEPOSIT_DISCRIMINATOR => {
+            msg!("Instruction: MultiDeposit");
+            multi_deposit(accounts, data)
+                .map_err(|e| {
+                    msg!("MultiDeposit failed");
+                    ProgramError::from(e)
+                })
+        }
+        MULTI_WITHDRAW_DISCRIMINATOR => {
+            msg!("Instruction: MultiWithdraw");
+            multi_withdraw(accounts, data)
+                .map_err(|e| {
+                    msg!("MultiWithdraw failed");
+                    ProgramError::from(e)
+                })
+        }
+        MULTI_DEPOSIT_SINGLE_DISCRIMINATOR => {
+            msg!("Instruction: MultiDepositSingle");
+            multi_deposit_single(accounts, data)
+                .map_err(|e| {
+                    msg!("MultiDepositSingle failed");
+                    ProgramError::from(e)
+                })
+        }
+        MULTI_WITHDRAW_SINGLE_DISCRIMINATOR => {
+            msg!("Instruction: MultiWithdrawSingle");
+            multi_withdraw_single(accounts, data)
+                .map_err(|e| {
+                    msg!("MultiWithdrawSingle failed");
+                    ProgramError::from(e)
+                })
+        }
+        ROUTE_SWAP_DISCRIMINATOR => {
+            msg!("Instruction: RouteSwap");
+            route_swap(accounts, data)
+                .map_err(|e| {
+                    msg!("RouteSwap failed");
+                    ProgramError::from(e)
+                })
+        }
+        FLASH_LOAN_DISCRIMINATOR => {
+            msg!("Instruction: FlashLoan");
+            flash_loan(accounts, data)
+                .map_err(|e| {
+                    msg!("FlashLoan failed");
+                    ProgramError::from(e)
+                })
+        }
+        FLASH_REPAY_DISCRIMINATOR => {
+            msg!("Instruction: FlashRepay");
+            flash_repay(accounts, data)
+                .map_err(|e| {
+                    msg!("FlashRepay failed");
+                    ProgramError::from(e)
+                })
+        }
+        SET_DYNAMIC_FEE_DISCRIMINATOR => {
+            msg!("Instruction: SetDynamicFee");
+            set_dynamic_fee(accounts, data)
+                .map_err(|e| {
+                    msg!("SetDynamicFee failed");
+                    ProgramError::from(e)
+                })
+        }
+        UPDATE_POOL_FEE_DISCRIMINATOR => {
+            msg!("Instruction: UpdatePoolFee");
+            update_pool_fee(accounts, data)
+                .map_err(|e| {
+                    msg!("UpdatePoolFee failed");
+                    ProgramError::from(e)
+                })
+        }
+        INIT_REFERRER_DISCRIMINATOR => {
+            msg!("Instruction: InitReferrer");
+            init_referrer(accounts, data)
+                .map_err(|e| {
+                    msg!("InitReferrer failed");
+                    ProgramError::from(e)
+                })
+        }
+        SET_POOL_CREATION_FEE_DISCRIMINATOR => {
+            msg!("Instruction: SetPoolCreationFee");
+            set_pool_creation_fee(accounts, data)
+                .map_err(|e| {
+                    msg!("SetPoolCreationFee failed");
+                    ProgramError::from(e)
+                })
+        }
         _ => {
             msg!("Unknown instruction discriminator");
             Err(SolanaCoreError::InvalidInstructionData.into())
@@ -41,6 +41,69 @@ pub enum SolanaCoreError {
 
     #[error("Slippage error")]
     Slippage,
+
+    #[error("Invalid account discriminator")]
+    InvalidAccountDiscriminator,
+
+    #[error("Sysvar unavailable")]
+    SysvarUnavailable,
+
+    #[error("Requested observation is older than the oldest recorded one")]
+    ObservationTooOld,
+
+    #[error("Invalid observation cardinality")]
+    InvalidCardinality,
+
+    #[error("Invalid curve type")]
+    InvalidCurveType,
+
+    #[error("Invalid amplification coefficient or ramp")]
+    InvalidAmp,
+
+    #[error("Signer is not the config admin")]
+    Unauthorized,
+
+    #[error("Invalid pool weights")]
+    InvalidWeight,
+
+    #[error("Trade exceeds the maximum share of the reserves")]
+    TradeTooLarge,
+
+    #[error("Invalid tick or tick range")]
+    InvalidTick,
+
+    #[error("Missing or out-of-order tick array")]
+    InvalidTickArray,
+
+    #[error("Not enough liquidity")]
+    InsufficientLiquidity,
+
+    #[error("Invalid swap route")]
+    InvalidRoute,
+
+    #[error("Pool is locked by an outstanding flash loan")]
+    PoolLocked,
+
+    #[error("Flash loan is not repaid later in the transaction")]
+    FlashLoanNotRepaid,
+
+    #[error("Flash swap callback did not pay the swap input")]
+    FlashSwapNotPaid,
+
+    #[error("Invalid fee rate or fee bounds")]
+    InvalidFee,
+
+    #[error("Treasury does not match the config")]
+    InvalidTreasury,
+
+    #[error("Pool creation fee was not paid")]
+    PoolCreationFeeNotPaid,
+
+    #[error("Account not writable")]
+    AccountNotWritable,
+
+    #[error("Token transfer failed")]
+    TokenTransferFailed,
 }
 
 impl PrintProgramError for SolanaCoreError {
@@ -0,0 +1,504 @@
+use pinocchio::{log::sol_log_data, pubkey::Pubkey};
+use crate::{
+    errors::SolanaCoreError,
+    states::MAX_MULTI_POOL_TOKENS,
+    utils::{ByteReader, ByteWriter},
+};
+
+// Binary events emitted with `sol_log_data` (they show up as `Program data: <base64>`
+// log lines). Each event is `discriminator (8) | version (1) | payload`, with the
+// payload encoded little-endian like instruction data. Fields are only ever appended,
+// and every append bumps `EVENT_VERSION` so events logged before it still decode,
+// without the new fields (see `Event::len`).
+//
+// 2: `SwapEvent::fees_bps`
+pub const EVENT_VERSION: u8 = 2;
+pub const EVENT_HEADER_LEN: usize = 8 + 1;
+// Large enough for every event below; checked at compile time.
+pub const MAX_EVENT_LEN: usize = 256;
+
+pub trait Event: Sized {
+    const DISCRIMINATOR: [u8; 8];
+    // payload length, excluding the header
+    const LEN: usize;
+
+    // payload length of an event logged under `version`; overridden by events that gained fields since
+    fn len(_version: u8) -> usize {
+        Self::LEN
+    }
+
+    fn write(&self, writer: &mut ByteWriter) -> Result<(), SolanaCoreError>;
+
+    // `version` is the one the event was logged under, at most `EVENT_VERSION`
+    fn read(reader: &mut ByteReader, version: u8) -> Result<Self, SolanaCoreError>;
+}
+
+#[inline(always)]
+pub fn emit<T: Event>(event: &T) -> Result<(), SolanaCoreError> {
+    let mut buffer = [0u8; MAX_EVENT_LEN];
+    let len = EVENT_HEADER_LEN + T::LEN;
+    let mut writer = ByteWriter::new(&mut buffer[..len]);
+    writer.write_bytes(&T::DISCRIMINATOR)?;
+    writer.write_u8(EVENT_VERSION)?;
+    event.write(&mut writer)?;
+    sol_log_data(&[&buffer[..len]]);
+    Ok(())
+}
+
+/// Decodes an event of type `T` from the raw bytes of a `Program data:` log entry.
+pub fn decode_event<T: Event>(data: &[u8]) -> Result<T, SolanaCoreError> {
+    if data.len() < EVENT_HEADER_LEN || data[..8] != T::DISCRIMINATOR {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+    let version = data[8];
+    if version == 0 || version > EVENT_VERSION {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+    let len = T::len(version);
+    if data.len() < EVENT_HEADER_LEN + len {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+    // Ignore trailing bytes so decoders keep working when fields are appended.
+    let mut reader = ByteReader::new(&data[EVENT_HEADER_LEN..EVENT_HEADER_LEN + len]);
+    T::read(&mut reader, version)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PoolCreatedEvent {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub lp_mint: Pubkey,
+    pub fees_bps: u64,
+    pub token_0_amount: u64,
+    pub token_1_amount: u64,
+    pub lp_amount: u64,
+}
+
+impl Event for PoolCreatedEvent {
+    // sha256("event:PoolCreatedEvent")[..8]
+    const DISCRIMINATOR: [u8; 8] = [25, 94, 75, 47, 112, 99, 53, 63];
+    const LEN: usize = 32 * 5 + 8 * 4;
+
+    fn write(&self, writer: &mut ByteWriter) -> Result<(), SolanaCoreError> {
+        writer.write_pubkey(&self.pool)?;
+        writer.write_pubkey(&self.creator)?;
+        writer.write_pubkey(&self.token_0_mint)?;
+        writer.write_pubkey(&self.token_1_mint)?;
+        writer.write_pubkey(&self.lp_mint)?;
+        writer.write_u64(self.fees_bps)?;
+        writer.write_u64(self.token_0_amount)?;
+        writer.write_u64(self.token_1_amount)?;
+        writer.write_u64(self.lp_amount)
+    }
+
+    fn read(reader: &mut ByteReader, _version: u8) -> Result<Self, SolanaCoreError> {
+        Ok(Self {
+            pool: reader.read_pubkey()?,
+            creator: reader.read_pubkey()?,
+            token_0_mint: reader.read_pubkey()?,
+            token_1_mint: reader.read_pubkey()?,
+            lp_mint: reader.read_pubkey()?,
+            fees_bps: reader.read_u64()?,
+            token_0_amount: reader.read_u64()?,
+            token_1_amount: reader.read_u64()?,
+            lp_amount: reader.read_u64()?,
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SwapEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    // `SwapDirection` as u8
+    pub direction: u8,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    pub reserve_0_after: u64,
+    pub reserve_1_after: u64,
+    // fee rate applied to the swap, dynamic fee included; `None` in events logged
+    // before version 2
+    pub fees_bps: Option<u64>,
+}
+
+impl Event for SwapEvent {
+    // sha256("event:SwapEvent")[..8]
+    const DISCRIMINATOR: [u8; 8] = [64, 198, 205, 232, 38, 8, 113, 226];
+    const LEN: usize = 32 * 2 + 1 + 8 * 6;
+
+    fn len(version: u8) -> usize {
+        if version < 2 { Self::LEN - 8 } else { Self::LEN }
+    }
+
+    fn write(&self, writer: &mut ByteWriter) -> Result<(), SolanaCoreError> {
+        writer.write_pubkey(&self.pool)?;
+        writer.write_pubkey(&self.user)?;
+        writer.write_u8(self.direction)?;
+        writer.write_u64(self.amount_in)?;
+        writer.write_u64(self.amount_out)?;
+        writer.write_u64(self.fee)?;
+        writer.write_u64(self.reserve_0_after)?;
+        writer.write_u64(self.reserve_1_after)?;
+        writer.write_u64(self.fees_bps.unwrap_or_default())
+    }
+
+    fn read(reader: &mut ByteReader, version: u8) -> Result<Self, SolanaCoreError> {
+        Ok(Self {
+            pool: reader.read_pubkey()?,
+            user: reader.read_pubkey()?,
+            direction: reader.read_u8()?,
+            amount_in: reader.read_u64()?,
+            amount_out: reader.read_u64()?,
+            fee: reader.read_u64()?,
+            reserve_0_after: reader.read_u64()?,
+            reserve_1_after: reader.read_u64()?,
+            fees_bps: if version < 2 { None } else { Some(reader.read_u64()?) },
+        })
+    }
+}
+
+// Emitted by `liquidate_pool`, and by `increase_liquidity` where `lp_amount` is the
+// liquidity added and `lp_supply_after` the pool's in-range liquidity, both capped at u64.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DepositEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub token_0_amount: u64,
+    pub token_1_amount: u64,
+    pub lp_amount: u64,
+    pub reserve_0_after: u64,
+    pub reserve_1_after: u64,
+    pub lp_supply_after: u64,
+}
+
+impl Event for DepositEvent {
+    // sha256("event:DepositEvent")[..8]
+    const DISCRIMINATOR: [u8; 8] = [120, 248, 61, 83, 31, 142, 107, 144];
+    const LEN: usize = 32 * 2 + 8 * 6;
+
+    fn write(&self, writer: &mut ByteWriter) -> Result<(), SolanaCoreError> {
+        writer.write_pubkey(&self.pool)?;
+        writer.write_pubkey(&self.user)?;
+        writer.write_u64(self.token_0_amount)?;
+        writer.write_u64(self.token_1_amount)?;
+        writer.write_u64(self.lp_amount)?;
+        writer.write_u64(self.reserve_0_after)?;
+        writer.write_u64(self.reserve_1_after)?;
+        writer.write_u64(self.lp_supply_after)
+    }
+
+    fn read(reader: &mut ByteReader, _version: u8) -> Result<Self, SolanaCoreError> {
+        Ok(Self {
+            pool: reader.read_pubkey()?,
+            user: reader.read_pubkey()?,
+            token_0_amount: reader.read_u64()?,
+            token_1_amount: reader.read_u64()?,
+            lp_amount: reader.read_u64()?,
+            reserve_0_after: reader.read_u64()?,
+            reserve_1_after: reader.read_u64()?,
+            lp_supply_after: reader.read_u64()?,
+        })
+    }
+}
+
+// Emitted by `deliquidate_pool`, and by `decrease_liquidity` where the token amounts include
+// the fees collected and the liquidity fields are as in `DepositEvent`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WithdrawEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub token_0_amount: u64,
+    pub token_1_amount: u64,
+    pub lp_amount: u64,
+    pub reserve_0_after: u64,
+    pub reserve_1_after: u64,
+    pub lp_supply_after: u64,
+}
+
+impl Event for WithdrawEvent {
+    // sha256("event:WithdrawEvent")[..8]
+    const DISCRIMINATOR: [u8; 8] = [22, 9, 133, 26, 160, 44, 71, 192];
+    const LEN: usize = 32 * 2 + 8 * 6;
+
+    fn write(&self, writer: &mut ByteWriter) -> Result<(), SolanaCoreError> {
+        writer.write_pubkey(&self.pool)?;
+        writer.write_pubkey(&self.user)?;
+        writer.write_u64(self.token_0_amount)?;
+        writer.write_u64(self.token_1_amount)?;
+        writer.write_u64(self.lp_amount)?;
+        writer.write_u64(self.reserve_0_after)?;
+        writer.write_u64(self.reserve_1_after)?;
+        writer.write_u64(self.lp_supply_after)
+    }
+
+    fn read(reader: &mut ByteReader, _version: u8) -> Result<Self, SolanaCoreError> {
+        Ok(Self {
+            pool: reader.read_pubkey()?,
+            user: reader.read_pubkey()?,
+            token_0_amount: reader.read_u64()?,
+            token_1_amount: reader.read_u64()?,
+            lp_amount: reader.read_u64()?,
+            reserve_0_after: reader.read_u64()?,
+            reserve_1_after: reader.read_u64()?,
+            lp_supply_after: reader.read_u64()?,
+        })
+    }
+}
+
+// Emitted by `flash_repay`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlashLoanEvent {
+    pub pool: Pubkey,
+    pub borrower: Pubkey,
+    pub amount_0: u64,
+    pub amount_1: u64,
+    pub fee_0: u64,
+    pub fee_1: u64,
+    pub reserve_0_after: u64,
+    pub reserve_1_after: u64,
+}
+
+impl Event for FlashLoanEvent {
+    // sha256("event:FlashLoanEvent")[..8]
+    const DISCRIMINATOR: [u8; 8] = [214, 143, 143, 217, 173, 237, 52, 26];
+    const LEN: usize = 32 * 2 + 8 * 6;
+
+    fn write(&self, writer: &mut ByteWriter) -> Result<(), SolanaCoreError> {
+        writer.write_pubkey(&self.pool)?;
+        writer.write_pubkey(&self.borrower)?;
+        writer.write_u64(self.amount_0)?;
+        writer.write_u64(self.amount_1)?;
+        writer.write_u64(self.fee_0)?;
+        writer.write_u64(self.fee_1)?;
+        writer.write_u64(self.reserve_0_after)?;
+        writer.write_u64(self.reserve_1_after)
+    }
+
+    fn read(reader: &mut ByteReader, _version: u8) -> Result<Self, SolanaCoreError> {
+        Ok(Self {
+            pool: reader.read_pubkey()?,
+            borrower: reader.read_pubkey()?,
+            amount_0: reader.read_u64()?,
+            amount_1: reader.read_u64()?,
+            fee_0: reader.read_u64()?,
+            fee_1: reader.read_u64()?,
+            reserve_0_after: reader.read_u64()?,
+            reserve_1_after: reader.read_u64()?,
+        })
+    }
+}
+
+// Emitted by `update_pool_fee`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PoolFeeUpdatedEvent {
+    pub pool: Pubkey,
+    pub admin: Pubkey,
+    pub old_fee_rate_bps: u64,
+    pub new_fee_rate_bps: u64,
+    // unix timestamp the new rate applies from
+    pub effective_timestamp: u64,
+}
+
+impl Event for PoolFeeUpdatedEvent {
+    // sha256("event:PoolFeeUpdatedEvent")[..8]
+    const DISCRIMINATOR: [u8; 8] = [236, 204, 236, 205, 63, 108, 172, 123];
+    const LEN: usize = 32 * 2 + 8 * 3;
+
+    fn write(&self, writer: &mut ByteWriter) -> Result<(), SolanaCoreError> {
+        writer.write_pubkey(&self.pool)?;
+        writer.write_pubkey(&self.admin)?;
+        writer.write_u64(self.old_fee_rate_bps)?;
+        writer.write_u64(self.new_fee_rate_bps)?;
+        writer.write_u64(self.effective_timestamp)
+    }
+
+    fn read(reader: &mut ByteReader, _version: u8) -> Result<Self, SolanaCoreError> {
+        Ok(Self {
+            pool: reader.read_pubkey()?,
+            admin: reader.read_pubkey()?,
+            old_fee_rate_bps: reader.read_u64()?,
+            new_fee_rate_bps: reader.read_u64()?,
+            effective_timestamp: reader.read_u64()?,
+        })
+    }
+}
+
+// Emitted by `swap` when the swap is referred.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ReferralFeeEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    // owner of the token account the referral fee was paid to
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub amount_in: u64,
+    // part of the swap fee paid to the referrer, in `mint` units
+    pub referral_fee: u64,
+}
+
+impl Event for ReferralFeeEvent {
+    // sha256("event:ReferralFeeEvent")[..8]
+    const DISCRIMINATOR: [u8; 8] = [69, 123, 192, 77, 145, 91, 202, 149];
+    const LEN: usize = 32 * 4 + 8 * 2;
+
+    fn write(&self, writer: &mut ByteWriter) -> Result<(), SolanaCoreError> {
+        writer.write_pubkey(&self.pool)?;
+        writer.write_pubkey(&self.user)?;
+        writer.write_pubkey(&self.referrer)?;
+        writer.write_pubkey(&self.mint)?;
+        writer.write_u64(self.amount_in)?;
+        writer.write_u64(self.referral_fee)
+    }
+
+    fn read(reader: &mut ByteReader, _version: u8) -> Result<Self, SolanaCoreError> {
+        Ok(Self {
+            pool: reader.read_pubkey()?,
+            user: reader.read_pubkey()?,
+            referrer: reader.read_pubkey()?,
+            mint: reader.read_pubkey()?,
+            amount_in: reader.read_u64()?,
+            referral_fee: reader.read_u64()?,
+        })
+    }
+}
+
+// Emitted by `multi_swap`; tokens are indices into the multi-token pool.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MultiSwapEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub token_in: u8,
+    pub token_out: u8,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    pub reserve_in_after: u64,
+    pub reserve_out_after: u64,
+}
+
+impl Event for MultiSwapEvent {
+    // sha256("event:MultiSwapEvent")[..8]
+    const DISCRIMINATOR: [u8; 8] = [245, 52, 162, 135, 252, 146, 93, 44];
+    const LEN: usize = 32 * 2 + 2 + 8 * 5;
+
+    fn write(&self, writer: &mut ByteWriter) -> Result<(), SolanaCoreError> {
+        writer.write_pubkey(&self.pool)?;
+        writer.write_pubkey(&self.user)?;
+        writer.write_u8(self.token_in)?;
+        writer.write_u8(self.token_out)?;
+        writer.write_u64(self.amount_in)?;
+        writer.write_u64(self.amount_out)?;
+        writer.write_u64(self.fee)?;
+        writer.write_u64(self.reserve_in_after)?;
+        writer.write_u64(self.reserve_out_after)
+    }
+
+    fn read(reader: &mut ByteReader, _version: u8) -> Result<Self, SolanaCoreError> {
+        Ok(Self {
+            pool: reader.read_pubkey()?,
+            user: reader.read_pubkey()?,
+            token_in: reader.read_u8()?,
+            token_out: reader.read_u8()?,
+            amount_in: reader.read_u64()?,
+            amount_out: reader.read_u64()?,
+            fee: reader.read_u64()?,
+            reserve_in_after: reader.read_u64()?,
+            reserve_out_after: reader.read_u64()?,
+        })
+    }
+}
+
+// Emitted by `multi_deposit` and `multi_deposit_single`. The arrays are in pool token
+// order, with `token_count` entries used; a single-token deposit leaves the others zero.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MultiDepositEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub token_count: u8,
+    pub amounts: [u64; MAX_MULTI_POOL_TOKENS],
+    pub lp_amount: u64,
+    pub reserves_after: [u64; MAX_MULTI_POOL_TOKENS],
+    pub lp_supply_after: u64,
+}
+
+impl Event for MultiDepositEvent {
+    // sha256("event:MultiDepositEvent")[..8]
+    const DISCRIMINATOR: [u8; 8] = [50, 238, 37, 128, 137, 75, 202, 116];
+    const LEN: usize = 32 * 2 + 1 + 8 * (2 * MAX_MULTI_POOL_TOKENS + 2);
+
+    fn write(&self, writer: &mut ByteWriter) -> Result<(), SolanaCoreError> {
+        writer.write_pubkey(&self.pool)?;
+        writer.write_pubkey(&self.user)?;
+        writer.write_u8(self.token_count)?;
+        writer.write_u64_array(&self.amounts)?;
+        writer.write_u64(self.lp_amount)?;
+        writer.write_u64_array(&self.reserves_after)?;
+        writer.write_u64(self.lp_supply_after)
+    }
+
+    fn read(reader: &mut ByteReader, _version: u8) -> Result<Self, SolanaCoreError> {
+        Ok(Self {
+            pool: reader.read_pubkey()?,
+            user: reader.read_pubkey()?,
+            token_count: reader.read_u8()?,
+            amounts: reader.read_u64_array()?,
+            lp_amount: reader.read_u64()?,
+            reserves_after: reader.read_u64_array()?,
+            lp_supply_after: reader.read_u64()?,
+        })
+    }
+}
+
+// Emitted by `multi_withdraw` and `multi_withdraw_single`, laid out like `MultiDepositEvent`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MultiWithdrawEvent {
+    pub pool: Pubkey,
+    pub user: Pubkey,
+    pub token_count: u8,
+    pub amounts: [u64; MAX_MULTI_POOL_TOKENS],
+    pub lp_amount: u64,
+    pub reserves_after: [u64; MAX_MULTI_POOL_TOKENS],
+    pub lp_supply_after: u64,
+}
+
+impl Event for MultiWithdrawEvent {
+    // sha256("event:MultiWithdrawEvent")[..8]
+    const DISCRIMINATOR: [u8; 8] = [222, 225, 76, 75, 27, 49, 137, 149];
+    const LEN: usize = 32 * 2 + 1 + 8 * (2 * MAX_MULTI_POOL_TOKENS + 2);
+
+    fn write(&self, writer: &mut ByteWriter) -> Result<(), SolanaCoreError> {
+        writer.write_pubkey(&self.pool)?;
+        writer.write_pubkey(&self.user)?;
+        writer.write_u8(self.token_count)?;
+        writer.write_u64_array(&self.amounts)?;
+        writer.write_u64(self.lp_amount)?;
+        writer.write_u64_array(&self.reserves_after)?;
+        writer.write_u64(self.lp_supply_after)
+    }
+
+    fn read(reader: &mut ByteReader, _version: u8) -> Result<Self, SolanaCoreError> {
+        Ok(Self {
+            pool: reader.read_pubkey()?,
+            user: reader.read_pubkey()?,
+            token_count: reader.read_u8()?,
+            amounts: reader.read_u64_array()?,
+            lp_amount: reader.read_u64()?,
+            reserves_after: reader.read_u64_array()?,
+            lp_supply_after: reader.read_u64()?,
+        })
+    }
+}
+
+const _: () = assert!(EVENT_HEADER_LEN + PoolCreatedEvent::LEN <= MAX_EVENT_LEN);
+const _: () = assert!(EVENT_HEADER_LEN + SwapEvent::LEN <= MAX_EVENT_LEN);
+const _: () = assert!(EVENT_HEADER_LEN + DepositEvent::LEN <= MAX_EVENT_LEN);
+const _: () = assert!(EVENT_HEADER_LEN + WithdrawEvent::LEN <= MAX_EVENT_LEN);
+const _: () = assert!(EVENT_HEADER_LEN + FlashLoanEvent::LEN <= MAX_EVENT_LEN);
+const _: () = assert!(EVENT_HEADER_LEN + PoolFeeUpdatedEvent::LEN <= MAX_EVENT_LEN);
+const _: () = assert!(EVENT_HEADER_LEN + ReferralFeeEvent::LEN <= MAX_EVENT_LEN);
+const _: () = assert!(EVENT_HEADER_LEN + MultiSwapEvent::LEN <= MAX_EVENT_LEN);
+const _: () = assert!(EVENT_HEADER_LEN + MultiDepositEvent::LEN <= MAX_EVENT_LEN);
+const _: () = assert!(EVENT_HEADER_LEN + MultiWithdrawEvent::LEN <= MAX_EVENT_LEN);
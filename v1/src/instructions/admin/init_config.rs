@@ -0,0 +1,88 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    pubkey::{find_program_address, Pubkey},
+    sysvars::{rent::Rent, Sysvar},
+};
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::{
+    errors::SolanaCoreError,
+    utils::{
+        checks::{load_ix_data, DataLen},
+        validate_pda, BPS_DENOMINATOR, MAX_FEES_BPS,
+    },
+    states::{
+        Config, InitConfig,
+    },
+};
+
+pub const BPF_LOADER_UPGRADEABLE_ID: Pubkey = pinocchio_pubkey::pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+
+// `UpgradeableLoaderState::ProgramData`: enum tag (u32) | slot (u64) | Option<Pubkey> authority
+const PROGRAM_DATA_TAG: u32 = 3;
+const PROGRAM_DATA_AUTHORITY_OFFSET: usize = 4 + 8;
+
+// Upgrade authority recorded in this program's ProgramData account, if any.
+fn upgrade_authority(program_data: &AccountInfo) -> Result<Option<Pubkey>, SolanaCoreError> {
+    if !program_data.is_owned_by(&BPF_LOADER_UPGRADEABLE_ID) {
+        return Err(SolanaCoreError::InvalidOwner);
+    }
+    let (expected, _) = find_program_address(&[crate::ID.as_ref()], &BPF_LOADER_UPGRADEABLE_ID);
+    if *program_data.key() != expected {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+
+    let data = unsafe { program_data.borrow_data_unchecked() };
+    if data.len() < PROGRAM_DATA_AUTHORITY_OFFSET + 1 + 32
+        || data[..4] != PROGRAM_DATA_TAG.to_le_bytes()
+    {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+    if data[PROGRAM_DATA_AUTHORITY_OFFSET] == 0 {
+        return Ok(None);
+    }
+    let mut authority = [0u8; 32];
+    authority.copy_from_slice(&data[PROGRAM_DATA_AUTHORITY_OFFSET + 1..PROGRAM_DATA_AUTHORITY_OFFSET + 1 + 32]);
+    Ok(Some(authority))
+}
+
+// Creates the config PDA. Only the program's upgrade authority may do this, once.
+pub fn init_config(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [authority, config, program_data, _system_program] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !authority.is_signer() {
+        return Err(SolanaCoreError::MissingRequiredSignature);
+    }
+    if upgrade_authority(program_data)? != Some(*authority.key()) {
+        return Err(SolanaCoreError::Unauthorized);
+    }
+
+    let ix_data: InitConfig = load_ix_data::<InitConfig>(data)?;
+    if ix_data.min_fee_bps > ix_data.max_fee_bps
+        || ix_data.max_fee_bps > MAX_FEES_BPS
+        || ix_data.max_referral_fee_share_bps > BPS_DENOMINATOR
+    {
+        return Err(SolanaCoreError::InvalidFee);
+    }
+
+    let bump = [ix_data.config_bump];
+    validate_pda(&[Config::SEED.as_bytes(), &bump], config.key())?;
+
+    let rent = match Rent::get() {
+        Ok(rent) => rent,
+        Err(_) => return Err(SolanaCoreError::SysvarUnavailable),
+    };
+    let signer_seeds = [Seed::from(Config::SEED.as_bytes()), Seed::from(&bump)];
+    let _ = CreateAccount {
+        from: authority,
+        to: config,
+        lamports: rent.minimum_balance(Config::LEN),
+        space: Config::LEN as u64,
+        owner: &crate::ID,
+    }.invoke_signed(&[Signer::from(&signer_seeds[..])]);
+
+    Config::init(config, &ix_data)
+}
@@ -1,2 +1,13 @@
 pub mod collect_protocol_fee;
+pub mod init_config;
+pub mod ramp_amp;
+pub mod set_dynamic_fee;
+pub mod set_pool_creation_fee;
+pub mod update_pool_fee;
+
 pub use collect_protocol_fee::*;
+pub use init_config::*;
+pub use ramp_amp::*;
+pub use set_dynamic_fee::*;
+pub use set_pool_creation_fee::*;
+pub use update_pool_fee::*;
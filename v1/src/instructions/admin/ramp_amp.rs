@@ -0,0 +1,53 @@
+use pinocchio::account_info::AccountInfo;
+
+use crate::{
+    errors::SolanaCoreError,
+    utils::{
+        checks::load_ix_data,
+        current_timestamp, CurveType, MAX_AMP, MAX_AMP_CHANGE, MIN_AMP, MIN_RAMP_DURATION,
+    },
+    states::{
+        Config, Pool, RampAmp,
+    },
+};
+
+// Ramps a stable pool's amplification coefficient linearly to `target_amp`.
+// Like Curve, ramps are slow and bounded so LPs cannot be drained by a sudden change of A.
+pub fn ramp_amp(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [admin, config, pool] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    Config::load(config)?.check_admin(admin)?;
+
+    let pool_acc: &mut Pool = unsafe { Pool::load_mut(pool) }?;
+    if pool_acc.curve_type()? != CurveType::StableSwap {
+        return Err(SolanaCoreError::InvalidCurveType);
+    }
+
+    let ix_data: RampAmp = load_ix_data::<RampAmp>(data)?;
+    let now = current_timestamp()?;
+
+    // one ramp per day at most, each lasting at least a day
+    if now < pool_acc.amp_start_timestamp().saturating_add(MIN_RAMP_DURATION)
+        || ix_data.ramp_duration < MIN_RAMP_DURATION as u64
+        || ix_data.ramp_duration > i64::MAX as u64
+    {
+        return Err(SolanaCoreError::InvalidAmp);
+    }
+
+    let current = pool_acc.amp(now);
+    let target = ix_data.target_amp;
+    if target < MIN_AMP
+        || target > MAX_AMP
+        || target > current.saturating_mul(MAX_AMP_CHANGE)
+        || target.saturating_mul(MAX_AMP_CHANGE) < current
+    {
+        return Err(SolanaCoreError::InvalidAmp);
+    }
+
+    let end = now.checked_add(ix_data.ramp_duration as i64).ok_or(SolanaCoreError::OverFlowDetected)?;
+    pool_acc.set_amp_ramp(now, target, end);
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use pinocchio::account_info::AccountInfo;
+
+use crate::{
+    errors::SolanaCoreError,
+    utils::{
+        checks::load_ix_data,
+        current_timestamp, MAX_DYNAMIC_FEE_BPS,
+    },
+    states::{
+        Config, Pool, SetDynamicFee,
+    },
+};
+
+// Puts a pool in dynamic fee mode, charging between `min_fee_bps` and `max_fee_bps`
+// depending on recent volatility, or back to its fee tier when `max_fee_bps` is zero.
+// The volatility accumulator restarts from zero either way.
+pub fn set_dynamic_fee(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [admin, config, pool] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    Config::load(config)?.check_admin(admin)?;
+
+    let pool_acc: &mut Pool = unsafe { Pool::load_mut(pool) }?;
+    let ix_data: SetDynamicFee = load_ix_data::<SetDynamicFee>(data)?;
+
+    // disabling takes both bounds at zero
+    if ix_data.min_fee_bps > ix_data.max_fee_bps || ix_data.max_fee_bps > MAX_DYNAMIC_FEE_BPS {
+        return Err(SolanaCoreError::InvalidFee);
+    }
+
+    let now = current_timestamp()?;
+    pool_acc.set_dynamic_fee(ix_data.min_fee_bps, ix_data.max_fee_bps, now);
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey};
+
+use crate::{
+    errors::SolanaCoreError,
+    utils::{
+        checks::load_ix_data,
+        get_token_mint_and_owner,
+    },
+    states::{
+        Config, SetPoolCreationFee,
+    },
+};
+
+// Sets the fee `init_pool` charges pool creators and the treasury it is paid to.
+// A fee in a token needs the treasury to be a token account of that mint.
+pub fn set_pool_creation_fee(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [admin, config, treasury] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    let config_acc: &mut Config = unsafe { Config::load_mut(config) }?;
+    config_acc.check_admin(admin)?;
+
+    let ix_data: SetPoolCreationFee = load_ix_data::<SetPoolCreationFee>(data)?;
+    if ix_data.mint != Pubkey::default() && get_token_mint_and_owner(treasury)?.0 != ix_data.mint {
+        return Err(SolanaCoreError::InvalidTreasury);
+    }
+
+    config_acc.set_pool_creation_fee(treasury.key(), &ix_data.mint, ix_data.amount);
+
+    Ok(())
+}
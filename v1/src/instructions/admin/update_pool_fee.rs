@@ -0,0 +1,47 @@
+use pinocchio::account_info::AccountInfo;
+
+use crate::{
+    errors::SolanaCoreError,
+    events::{emit, PoolFeeUpdatedEvent},
+    utils::{
+        checks::load_ix_data,
+        current_timestamp,
+    },
+    states::{
+        Config, Pool, UpdatePoolFee,
+    },
+};
+
+// Changes the fee rate a pool charges, within the config's fee bounds. The fee tier in
+// the pool seeds stays as created. With a non-zero `delay` the rate is only scheduled,
+// giving LPs and integrators notice; a later update replaces a rate still pending.
+pub fn update_pool_fee(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [admin, config, pool] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    let config_acc = Config::load(config)?;
+    config_acc.check_admin(admin)?;
+
+    let pool_acc: &mut Pool = unsafe { Pool::load_mut(pool) }?;
+    let ix_data: UpdatePoolFee = load_ix_data::<UpdatePoolFee>(data)?;
+    config_acc.check_fee_rate(ix_data.fee_rate_bps)?;
+    if ix_data.delay > i64::MAX as u64 {
+        return Err(SolanaCoreError::InvalidInstructionData);
+    }
+
+    let now = current_timestamp()?;
+    let old_fee_rate_bps = pool_acc.fee_rate_bps(now);
+    let activation = now.checked_add(ix_data.delay as i64).ok_or(SolanaCoreError::OverFlowDetected)?;
+    pool_acc.set_fee_rate(ix_data.fee_rate_bps, now, activation);
+
+    emit(&PoolFeeUpdatedEvent {
+        pool: *pool.key(),
+        admin: *admin.key(),
+        old_fee_rate_bps,
+        new_fee_rate_bps: ix_data.fee_rate_bps,
+        effective_timestamp: activation as u64,
+    })?;
+
+    Ok(())
+}
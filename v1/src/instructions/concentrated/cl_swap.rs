@@ -0,0 +1,126 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+};
+use pinocchio_token::instructions::TransferChecked;
+
+use crate::{
+    errors::SolanaCoreError,
+    events::{emit, SwapEvent},
+    utils::{
+        checks::load_ix_data,
+        check_slippage, get_token_balance, swap_through_ticks, SwapDirection,
+    },
+    states::{
+        ClPool, Swap, TickArray,
+    },
+};
+
+// Swaps against a concentrated-liquidity pool. Takes the same payload as `swap`; the
+// tick arrays to walk (one to three, starting with the one holding the current tick)
+// follow the fixed accounts.
+pub fn cl_swap(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [signer, pool, token_0_ata, token_1_ata, token_0_mint, token_1_mint, vault_0_ata, vault_1_ata, _token_program, tick_arrays @ ..] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer() {
+        return Err(SolanaCoreError::SignerRequired);
+    }
+
+    let ix_data: Swap = load_ix_data::<Swap>(data)?;
+    let pool_acc: &mut ClPool = unsafe { ClPool::load_mut(pool) }?;
+    // the mints and vaults must be this pool's, or vault_in could be any account the user controls
+    if *token_0_mint.key() != pool_acc.token_0_mint
+        || *token_1_mint.key() != pool_acc.token_1_mint
+        || *vault_0_ata.key() != pool_acc.vault_0
+        || *vault_1_ata.key() != pool_acc.vault_1
+    {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+
+    // each tick array is loaded mutably once below, so they must be distinct accounts
+    for (index, tick_array) in tick_arrays.iter().enumerate() {
+        if tick_arrays[..index].iter().any(|other| other.key() == tick_array.key()) {
+            return Err(SolanaCoreError::InvalidTickArray);
+        }
+    }
+
+    let key = pool.key();
+    let result = match tick_arrays {
+        [a] => swap_through_ticks(
+            pool_acc,
+            &mut unsafe { [TickArray::load_mut(a, key)?] },
+            ix_data.amount,
+            ix_data.direction,
+            ix_data.mode,
+        ),
+        [a, b] => swap_through_ticks(
+            pool_acc,
+            &mut unsafe { [TickArray::load_mut(a, key)?, TickArray::load_mut(b, key)?] },
+            ix_data.amount,
+            ix_data.direction,
+            ix_data.mode,
+        ),
+        [a, b, c] => swap_through_ticks(
+            pool_acc,
+            &mut unsafe { [TickArray::load_mut(a, key)?, TickArray::load_mut(b, key)?, TickArray::load_mut(c, key)?] },
+            ix_data.amount,
+            ix_data.direction,
+            ix_data.mode,
+        ),
+        _ => Err(SolanaCoreError::InvalidTickArray),
+    }?;
+    check_slippage(ix_data.mode, result.amount_in, result.amount_out, ix_data.expected_amount, ix_data.slippage_bps)?;
+
+    let (user_in, user_out, mint_in, mint_out, vault_in, vault_out) = match ix_data.direction {
+        SwapDirection::ZeroForOne => (token_0_ata, token_1_ata, token_0_mint, token_1_mint, vault_0_ata, vault_1_ata),
+        SwapDirection::OneForZero => (token_1_ata, token_0_ata, token_1_mint, token_0_mint, vault_1_ata, vault_0_ata),
+    };
+
+    let pda_bump_bytes = [pool_acc.pool_bump];
+    let fees_bps_bytes = pool_acc.fees_bps;
+    let signer_seeds = [
+        Seed::from(ClPool::SEED.as_bytes()),
+        Seed::from(pool_acc.token_0_mint.as_ref()),
+        Seed::from(pool_acc.token_1_mint.as_ref()),
+        Seed::from(&fees_bps_bytes),
+        Seed::from(&pda_bump_bytes[..]),
+    ];
+    let pool_signers = [Signer::from(&signer_seeds[..])];
+
+    //transfer user -> vault_in
+    let _ = TransferChecked {
+        from: user_in,
+        mint: mint_in,
+        to: vault_in,
+        authority: signer,
+        amount: result.amount_in,
+        decimals: 9,
+    }.invoke();
+
+    //vault_out -> user
+    let _ = TransferChecked {
+        from: vault_out,
+        mint: mint_out,
+        to: user_out,
+        authority: pool,
+        amount: result.amount_out,
+        decimals: 9,
+    }.invoke_signed(&pool_signers);
+
+    // the pool tracks no reserves of its own, the vaults hold them
+    emit(&SwapEvent {
+        pool: *pool.key(),
+        user: *signer.key(),
+        direction: ix_data.direction as u8,
+        amount_in: result.amount_in,
+        amount_out: result.amount_out,
+        fee: result.fee,
+        reserve_0_after: get_token_balance(vault_0_ata)?,
+        reserve_1_after: get_token_balance(vault_1_ata)?,
+        fees_bps: Some(pool_acc.fees_bps()),
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+};
+use pinocchio_token::instructions::TransferChecked;
+
+use crate::{
+    errors::SolanaCoreError,
+    events::{emit, WithdrawEvent},
+    instructions::position_ticks,
+    utils::{
+        checks::load_ix_data,
+        get_token_balance, modify_position,
+    },
+    states::{
+        ClPool, ModifyLiquidity, Position,
+    },
+};
+
+// Removes `liquidity` from a position and pays out the released tokens together with all
+// fees the position has earned; `liquidity == 0` only collects fees.
+pub fn decrease_liquidity(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [owner, pool, position, tick_array_lower, tick_array_upper, token_0_ata, token_1_ata, token_0_mint, token_1_mint, vault_0_ata, vault_1_ata, _token_program] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    let ix_data: ModifyLiquidity = load_ix_data::<ModifyLiquidity>(data)?;
+    let liquidity_delta = i128::try_from(ix_data.liquidity)
+        .map(|liquidity| -liquidity)
+        .map_err(|_| SolanaCoreError::OverFlowDetected)?;
+
+    let pool_acc: &mut ClPool = unsafe { ClPool::load_mut(pool) }?;
+    if *vault_0_ata.key() != pool_acc.vault_0 || *vault_1_ata.key() != pool_acc.vault_1 {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+    let position_acc: &mut Position = unsafe { Position::load_mut(position, pool.key()) }?;
+    position_acc.check_owner(owner)?;
+
+    let (lower, upper) = position_ticks(pool, pool_acc, position_acc, tick_array_lower, tick_array_upper)?;
+    let (amount_0, amount_1) = modify_position(pool_acc, position_acc, lower, upper, liquidity_delta)?;
+    if amount_0 < ix_data.amount_0_limit || amount_1 < ix_data.amount_1_limit {
+        return Err(SolanaCoreError::Slippage);
+    }
+
+    let (owed_0, owed_1) = position_acc.tokens_owed();
+    position_acc.set_tokens_owed(0, 0);
+    let payout_0 = amount_0.checked_add(owed_0).ok_or(SolanaCoreError::OverFlowDetected)?;
+    let payout_1 = amount_1.checked_add(owed_1).ok_or(SolanaCoreError::OverFlowDetected)?;
+
+    let pda_bump_bytes = [pool_acc.pool_bump];
+    let fees_bps_bytes = pool_acc.fees_bps;
+    let signer_seeds = [
+        Seed::from(ClPool::SEED.as_bytes()),
+        Seed::from(pool_acc.token_0_mint.as_ref()),
+        Seed::from(pool_acc.token_1_mint.as_ref()),
+        Seed::from(&fees_bps_bytes),
+        Seed::from(&pda_bump_bytes[..]),
+    ];
+    let pool_signers = [Signer::from(&signer_seeds[..])];
+
+    if payout_0 > 0 {
+        let _ = TransferChecked {
+            from: vault_0_ata,
+            mint: token_0_mint,
+            to: token_0_ata,
+            authority: pool,
+            amount: payout_0,
+            decimals: 9,
+        }.invoke_signed(&pool_signers);
+    }
+
+    if payout_1 > 0 {
+        let _ = TransferChecked {
+            from: vault_1_ata,
+            mint: token_1_mint,
+            to: token_1_ata,
+            authority: pool,
+            amount: payout_1,
+            decimals: 9,
+        }.invoke_signed(&pool_signers);
+    }
+
+    emit(&WithdrawEvent {
+        pool: *pool.key(),
+        user: *owner.key(),
+        token_0_amount: payout_0,
+        token_1_amount: payout_1,
+        lp_amount: u64::try_from(ix_data.liquidity).unwrap_or(u64::MAX),
+        reserve_0_after: get_token_balance(vault_0_ata)?,
+        reserve_1_after: get_token_balance(vault_1_ata)?,
+        lp_supply_after: u64::try_from(pool_acc.liquidity()).unwrap_or(u64::MAX),
+    })?;
+
+    Ok(())
+}
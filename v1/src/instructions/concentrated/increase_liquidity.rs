@@ -0,0 +1,76 @@
+use pinocchio::account_info::AccountInfo;
+use pinocchio_token::instructions::TransferChecked;
+
+use crate::{
+    errors::SolanaCoreError,
+    events::{emit, DepositEvent},
+    instructions::position_ticks,
+    utils::{
+        checks::load_ix_data,
+        get_token_balance, modify_position,
+    },
+    states::{
+        ClPool, ModifyLiquidity, Position,
+    },
+};
+
+// Adds `liquidity` to a position, pulling at most `amount_*_limit` from the owner.
+pub fn increase_liquidity(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [owner, pool, position, tick_array_lower, tick_array_upper, token_0_ata, token_1_ata, token_0_mint, token_1_mint, vault_0_ata, vault_1_ata, _token_program] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    let ix_data: ModifyLiquidity = load_ix_data::<ModifyLiquidity>(data)?;
+    let liquidity_delta = i128::try_from(ix_data.liquidity).map_err(|_| SolanaCoreError::OverFlowDetected)?;
+    if liquidity_delta == 0 {
+        return Err(SolanaCoreError::InvalidInstructionData);
+    }
+
+    let pool_acc: &mut ClPool = unsafe { ClPool::load_mut(pool) }?;
+    if *vault_0_ata.key() != pool_acc.vault_0 || *vault_1_ata.key() != pool_acc.vault_1 {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+    let position_acc: &mut Position = unsafe { Position::load_mut(position, pool.key()) }?;
+    position_acc.check_owner(owner)?;
+
+    let (lower, upper) = position_ticks(pool, pool_acc, position_acc, tick_array_lower, tick_array_upper)?;
+    let (amount_0, amount_1) = modify_position(pool_acc, position_acc, lower, upper, liquidity_delta)?;
+    if amount_0 > ix_data.amount_0_limit || amount_1 > ix_data.amount_1_limit {
+        return Err(SolanaCoreError::Slippage);
+    }
+
+    if amount_0 > 0 {
+        let _ = TransferChecked {
+            from: token_0_ata,
+            mint: token_0_mint,
+            to: vault_0_ata,
+            authority: owner,
+            amount: amount_0,
+            decimals: 9,
+        }.invoke();
+    }
+
+    if amount_1 > 0 {
+        let _ = TransferChecked {
+            from: token_1_ata,
+            mint: token_1_mint,
+            to: vault_1_ata,
+            authority: owner,
+            amount: amount_1,
+            decimals: 9,
+        }.invoke();
+    }
+
+    emit(&DepositEvent {
+        pool: *pool.key(),
+        user: *owner.key(),
+        token_0_amount: amount_0,
+        token_1_amount: amount_1,
+        lp_amount: u64::try_from(ix_data.liquidity).unwrap_or(u64::MAX),
+        reserve_0_after: get_token_balance(vault_0_ata)?,
+        reserve_1_after: get_token_balance(vault_1_ata)?,
+        lp_supply_after: u64::try_from(pool_acc.liquidity()).unwrap_or(u64::MAX),
+    })?;
+
+    Ok(())
+}
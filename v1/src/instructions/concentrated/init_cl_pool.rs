@@ -0,0 +1,111 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    sysvars::{rent::Rent, Sysvar},
+    msg,
+};
+use pinocchio_system::instructions::CreateAccount;
+use pinocchio_associated_token_account::instructions::Create;
+
+use crate::{
+    errors::SolanaCoreError,
+    utils::{
+        checks::{load_ix_data, DataLen},
+        validate_pda, tick_at_sqrt_price, MAX_SQRT_PRICE, MIN_SQRT_PRICE,
+    },
+    states::{
+        ClPool, InitClPool,
+    },
+};
+
+// Creates a concentrated-liquidity pool at `sqrt_price` with its two vault ATAs.
+// Liquidity is added afterwards through positions.
+pub fn init_cl_pool(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [signer, pool, token_0_mint, token_1_mint, vault_0_ata, vault_1_ata, system_program, token_program] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer() {
+        return Err(SolanaCoreError::MissingRequiredSignature);
+    }
+
+    let ix_data: InitClPool = load_ix_data::<InitClPool>(data)?;
+    if *token_0_mint.key() != ix_data.token_0_mint || *token_1_mint.key() != ix_data.token_1_mint {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+    if ix_data.fees_bps > 500 || ix_data.tick_spacing == 0 || ix_data.tick_spacing > ClPool::MAX_TICK_SPACING {
+        return Err(SolanaCoreError::InvalidInstructionData);
+    }
+    if !(MIN_SQRT_PRICE..MAX_SQRT_PRICE).contains(&ix_data.sqrt_price) {
+        return Err(SolanaCoreError::InvalidTick);
+    }
+
+    let pda_bump_bytes = [ix_data.pool_bump];
+    let fees_bps_bytes = ix_data.fees_bps.to_le_bytes();
+    validate_pda(
+        &[
+            ClPool::SEED.as_bytes(),
+            ix_data.token_0_mint.as_ref(),
+            ix_data.token_1_mint.as_ref(),
+            &fees_bps_bytes,
+            &pda_bump_bytes,
+        ],
+        pool.key(),
+    )?;
+    let signer_seeds = [
+        Seed::from(ClPool::SEED.as_bytes()),
+        Seed::from(&ix_data.token_0_mint),
+        Seed::from(&ix_data.token_1_mint),
+        Seed::from(&fees_bps_bytes),
+        Seed::from(&pda_bump_bytes),
+    ];
+    let pool_signers = [Signer::from(&signer_seeds[..])];
+
+    let rent = match Rent::get() {
+        Ok(rent) => rent,
+        Err(_) => return Err(SolanaCoreError::SysvarUnavailable),
+    };
+
+    msg!("Creating concentrated-liquidity pool account");
+
+    let _ = CreateAccount {
+        from: signer,
+        to: pool,
+        space: ClPool::LEN as u64,
+        owner: &crate::ID,
+        lamports: rent.minimum_balance(ClPool::LEN),
+    }.invoke_signed(&pool_signers);
+
+    msg!("Creating vault ATAs");
+
+    let _ = Create {
+        funding_account: signer,
+        account: vault_0_ata,
+        wallet: pool,
+        mint: token_0_mint,
+        system_program,
+        token_program,
+    }.invoke();
+
+    let _ = Create {
+        funding_account: signer,
+        account: vault_1_ata,
+        wallet: pool,
+        mint: token_1_mint,
+        system_program,
+        token_program,
+    }.invoke();
+
+    let pool_acc = ClPool::init(pool)?;
+    pool_acc.token_0_mint = ix_data.token_0_mint;
+    pool_acc.token_1_mint = ix_data.token_1_mint;
+    pool_acc.vault_0 = *vault_0_ata.key();
+    pool_acc.vault_1 = *vault_1_ata.key();
+    pool_acc.fees_bps = ix_data.fees_bps.to_le_bytes();
+    pool_acc.tick_spacing = ix_data.tick_spacing.to_le_bytes();
+    pool_acc.pool_bump = ix_data.pool_bump;
+    pool_acc.set_sqrt_price(ix_data.sqrt_price);
+    pool_acc.set_tick_current(tick_at_sqrt_price(ix_data.sqrt_price)?);
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    sysvars::{rent::Rent, Sysvar},
+};
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::{
+    errors::SolanaCoreError,
+    utils::{
+        checks::{load_ix_data, DataLen},
+        validate_pda, MAX_TICK, MIN_TICK,
+    },
+    states::{
+        ClPool, InitTickArray, TickArray,
+    },
+};
+
+// Creates the tick array starting at `start_tick_index`. Anyone may pay for one;
+// positions and swaps need the arrays covering the ticks they touch.
+pub fn init_tick_array(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [payer, pool, tick_array, _system_program] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !payer.is_signer() {
+        return Err(SolanaCoreError::MissingRequiredSignature);
+    }
+
+    let ix_data: InitTickArray = load_ix_data::<InitTickArray>(data)?;
+    let pool_acc: &ClPool = ClPool::load(pool)?;
+
+    let spacing = pool_acc.tick_spacing();
+    let start = ix_data.start_tick_index;
+    if start != TickArray::start_tick_index_for(start, spacing)
+        || start > MAX_TICK
+        || start + TickArray::ticks_per_array(spacing) <= MIN_TICK
+    {
+        return Err(SolanaCoreError::InvalidTickArray);
+    }
+
+    let start_bytes = start.to_le_bytes();
+    let bump = [ix_data.tick_array_bump];
+    validate_pda(&[TickArray::SEED.as_bytes(), pool.key().as_ref(), &start_bytes, &bump], tick_array.key())?;
+
+    let rent = match Rent::get() {
+        Ok(rent) => rent,
+        Err(_) => return Err(SolanaCoreError::SysvarUnavailable),
+    };
+    let signer_seeds = [
+        Seed::from(TickArray::SEED.as_bytes()),
+        Seed::from(pool.key()),
+        Seed::from(&start_bytes),
+        Seed::from(&bump),
+    ];
+    let _ = CreateAccount {
+        from: payer,
+        to: tick_array,
+        lamports: rent.minimum_balance(TickArray::LEN),
+        space: TickArray::LEN as u64,
+        owner: &crate::ID,
+    }.invoke_signed(&[Signer::from(&signer_seeds[..])]);
+
+    TickArray::init(tick_array, pool.key(), start, ix_data.tick_array_bump)
+}
@@ -0,0 +1,39 @@
+pub mod cl_swap;
+pub mod decrease_liquidity;
+pub mod increase_liquidity;
+pub mod init_cl_pool;
+pub mod init_tick_array;
+pub mod open_position;
+
+pub use cl_swap::*;
+pub use decrease_liquidity::*;
+pub use increase_liquidity::*;
+pub use init_cl_pool::*;
+pub use init_tick_array::*;
+pub use open_position::*;
+
+use pinocchio::account_info::AccountInfo;
+use crate::{
+    errors::SolanaCoreError,
+    states::{ClPool, Position, Tick, TickArray},
+};
+
+// Bounds of `position`, loaded from the tick arrays holding them (possibly the same one).
+pub(crate) fn position_ticks<'a>(
+    pool: &AccountInfo,
+    pool_acc: &ClPool,
+    position: &Position,
+    tick_array_lower: &'a AccountInfo,
+    tick_array_upper: &'a AccountInfo,
+) -> Result<(&'a mut Tick, &'a mut Tick), SolanaCoreError> {
+    let spacing = pool_acc.tick_spacing();
+    let (tick_lower, tick_upper) = (position.tick_lower(), position.tick_upper());
+    // a tick array shared by both bounds is loaded once
+    if tick_array_lower.key() == tick_array_upper.key() {
+        let tick_array = unsafe { TickArray::load_mut(tick_array_lower, pool.key()) }?;
+        return tick_array.tick_pair_mut(tick_lower, tick_upper, spacing);
+    }
+    let lower = unsafe { TickArray::load_mut(tick_array_lower, pool.key()) }?.tick_mut(tick_lower, spacing)?;
+    let upper = unsafe { TickArray::load_mut(tick_array_upper, pool.key()) }?.tick_mut(tick_upper, spacing)?;
+    Ok((lower, upper))
+}
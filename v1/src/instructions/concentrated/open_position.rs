@@ -0,0 +1,66 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    sysvars::{rent::Rent, Sysvar},
+};
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::{
+    errors::SolanaCoreError,
+    utils::{
+        checks::{load_ix_data, DataLen},
+        validate_pda,
+    },
+    states::{
+        ClPool, OpenPosition, Position,
+    },
+};
+
+// Creates an empty position of `owner` over `[tick_lower, tick_upper)`.
+pub fn open_position(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [owner, pool, position, _system_program] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !owner.is_signer() {
+        return Err(SolanaCoreError::MissingRequiredSignature);
+    }
+
+    let ix_data: OpenPosition = load_ix_data::<OpenPosition>(data)?;
+    let pool_acc: &ClPool = ClPool::load(pool)?;
+    pool_acc.check_tick(ix_data.tick_lower)?;
+    pool_acc.check_tick(ix_data.tick_upper)?;
+    if ix_data.tick_lower >= ix_data.tick_upper {
+        return Err(SolanaCoreError::InvalidTick);
+    }
+
+    let lower_bytes = ix_data.tick_lower.to_le_bytes();
+    let upper_bytes = ix_data.tick_upper.to_le_bytes();
+    let bump = [ix_data.position_bump];
+    validate_pda(
+        &[Position::SEED.as_bytes(), pool.key().as_ref(), owner.key().as_ref(), &lower_bytes, &upper_bytes, &bump],
+        position.key(),
+    )?;
+
+    let rent = match Rent::get() {
+        Ok(rent) => rent,
+        Err(_) => return Err(SolanaCoreError::SysvarUnavailable),
+    };
+    let signer_seeds = [
+        Seed::from(Position::SEED.as_bytes()),
+        Seed::from(pool.key()),
+        Seed::from(owner.key()),
+        Seed::from(&lower_bytes),
+        Seed::from(&upper_bytes),
+        Seed::from(&bump),
+    ];
+    let _ = CreateAccount {
+        from: owner,
+        to: position,
+        lamports: rent.minimum_balance(Position::LEN),
+        space: Position::LEN as u64,
+        owner: &crate::ID,
+    }.invoke_signed(&[Signer::from(&signer_seeds[..])]);
+
+    Position::init(position, pool.key(), owner.key(), ix_data.tick_lower, ix_data.tick_upper, ix_data.position_bump)
+}
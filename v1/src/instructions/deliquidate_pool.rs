@@ -14,10 +14,11 @@ use pinocchio_associated_token_account::instructions::Create;
 
 use crate::{
     errors::SolanaCoreError,
+    events::{emit, WithdrawEvent},
     utils::{
         checks::{load_ix_data, DataLen},
-        load_acc_mut_unchecked, validate_pda,
-        lp_to_burn, get_mint_supply
+        validate_pda, current_timestamp,
+        get_mint_supply, Curve
     },
     states::{
         Pool, DeliquidatePool
@@ -34,48 +35,45 @@ pub fn deliquidate_pool(accounts: &[AccountInfo], data: &[u8]) -> Result<(), Sol
     if !pool.is_writable() {
         return Err(SolanaCoreError::PoolAccountNotWritable.into());
     }
-    let  pool_acc: &mut Pool = unsafe { load_acc_mut_unchecked::<Pool>(pool.borrow_mut_data_unchecked()).unwrap()};  
-    let  ix_data: &DeliquidatePool = unsafe { load_ix_data::<DeliquidatePool>(data)}.unwrap();
+    let pool_acc: &mut Pool = unsafe { Pool::load_mut(pool) }?;
+    pool_acc.check_unlocked()?;
+    // accrue the TWAP accumulators at the pre-trade price
+    let now = current_timestamp()?;
+    pool_acc.update_price_accumulators(now);
+    let ix_data: DeliquidatePool = load_ix_data::<DeliquidatePool>(data)?;
 
     let pda_bump_bytes = [pool_acc.pool_bump];
-    // signer seeds
-let signer_seeds = [
-    Seed::from(Pool::POOL_SEED.as_bytes()),
-    Seed::from(&pool_acc.token_0_mint),
-    Seed::from(&pool_acc.token_1_mint),
-    Seed::from(&pda_bump_bytes[..]),
-];
+    let fees_bps_bytes = pool_acc.fees_bps;
+    let signer_seeds = [
+        Seed::from(Pool::POOL_SEED.as_bytes()),
+        Seed::from(&pool_acc.token_0_mint),
+        Seed::from(&pool_acc.token_1_mint),
+        Seed::from(&fees_bps_bytes),
+        Seed::from(&pda_bump_bytes[..]),
+    ];
     let pool_signers = [Signer::from(&signer_seeds[..])];
-// convert to Vec<&[u8]>
-let signer_seed_slices: Vec<&[u8]> = signer_seeds.iter()
-    .map(|s| s.as_ref()) // calls AsRef<[u8]>
-    .collect();
-
-// now it's &[&[u8]]
-let signer_seed_slices: &[&[u8]] = &signer_seed_slices;
-
-    let _ = validate_pda(signer_seed_slices as &[&[u8]], pool.key());
+    let signer_seed_slices: [&[u8]; 5] = signer_seeds.each_ref().map(|s| s.as_ref());
+    validate_pda(&signer_seed_slices, pool.key())?;
 
     let lp_bump = [pool_acc.lp_bump];
-    let lp_signer_seeds = [
-        Seed::from(Pool::LP_SEED.as_bytes()),
-        Seed::from(pool.key()),
-        Seed::from(&lp_bump),
-    ];
-
-    
-     // convert to Vec<&[u8]>
-    let lp_signer_seed_slices: Vec<&[u8]> = lp_signer_seeds.iter()
-    .map(|s| s.as_ref()) // calls AsRef<[u8]>
-    .collect();
-
-    // now it's &[&[u8]]
-    let lp_signer_seed_slices: &[&[u8]] = &lp_signer_seed_slices;
-
-    let _ = validate_pda(lp_signer_seed_slices as &[&[u8]], lp_mint.key());
+    validate_pda(&[Pool::LP_SEED.as_bytes(), pool.key().as_ref(), &lp_bump], lp_mint.key())?;
+    pool_acc.check_liquidity_accounts(
+        token_0_mint.key(),
+        token_1_mint.key(),
+        vault_0_ata.key(),
+        vault_1_ata.key(),
+        lp_mint.key(),
+    )?;
 
     // check the ratio in which they are withdrawing token
-    let (lp_to_burn, max_token_0, max_token_1)= lp_to_burn(ix_data.withdraw_token_0_amount, ix_data.withdraw_token_1_amount, pool_acc.token_0_amount, pool_acc.token_1_amount, get_mint_supply(lp_mint).unwrap());
+    let lp_supply = get_mint_supply(lp_mint)?;
+    let (lp_to_burn, max_token_0, max_token_1) = pool_acc.curve(now)?.withdraw(
+        ix_data.withdraw_token_0_amount,
+        ix_data.withdraw_token_1_amount,
+        pool_acc.token_0_amount(),
+        pool_acc.token_1_amount(),
+        lp_supply,
+    )?;
 
     // burn lp_token
     let _ = BurnChecked {
@@ -106,7 +104,18 @@ let signer_seed_slices: &[&[u8]] = &signer_seed_slices;
         decimals: 9,
     }.invoke_signed(&pool_signers);
 
-    pool_acc.token_0_amount -= max_token_0;
-    pool_acc.token_1_amount -= max_token_1;
+    pool_acc.set_token_0_amount(pool_acc.token_0_amount() - max_token_0);
+    pool_acc.set_token_1_amount(pool_acc.token_1_amount() - max_token_1);
+
+    emit(&WithdrawEvent {
+        pool: *pool.key(),
+        user: *signer.key(),
+        token_0_amount: max_token_0,
+        token_1_amount: max_token_1,
+        lp_amount: lp_to_burn,
+        reserve_0_after: pool_acc.token_0_amount(),
+        reserve_1_after: pool_acc.token_1_amount(),
+        lp_supply_after: lp_supply - lp_to_burn,
+    })?;
     Ok(())
 }
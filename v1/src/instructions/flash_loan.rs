@@ -0,0 +1,95 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    pubkey::Pubkey,
+    sysvars::instructions::Instructions,
+};
+use pinocchio_token::instructions::TransferChecked;
+
+use crate::{
+    errors::SolanaCoreError,
+    instructions::FLASH_REPAY_DISCRIMINATOR,
+    utils::{checks::load_ix_data, get_mint_decimals},
+    states::{
+        FlashLoan, Pool,
+    },
+};
+
+// Lends from the vaults until a `flash_repay` for the same pool, which must come later
+// in the same top-level transaction (checked through the Instructions sysvar). The
+// pool stays locked in between, so the borrowed tokens cannot be used to trade against
+// or withdraw from the pool they were borrowed from.
+pub fn flash_loan(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [borrower, pool, borrower_token_0_ata, borrower_token_1_ata, token_0_mint, token_1_mint, vault_0_ata, vault_1_ata, _token_program, instructions_sysvar] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !borrower.is_signer() {
+        return Err(SolanaCoreError::SignerRequired);
+    }
+
+    let ix_data: FlashLoan = load_ix_data::<FlashLoan>(data)?;
+    if ix_data.amount_0 == 0 && ix_data.amount_1 == 0 {
+        return Err(SolanaCoreError::InvalidInstructionData);
+    }
+
+    let pool_acc: &mut Pool = unsafe { Pool::load_mut(pool) }?;
+    if *token_0_mint.key() != pool_acc.token_0_mint
+        || *token_1_mint.key() != pool_acc.token_1_mint
+        || *vault_0_ata.key() != pool_acc.vault_0
+        || *vault_1_ata.key() != pool_acc.vault_1
+    {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+    require_flash_repay(instructions_sysvar, pool.key())?;
+    pool_acc.start_flash_loan(ix_data.amount_0, ix_data.amount_1)?;
+
+    let pda_bump_bytes = [pool_acc.pool_bump];
+    let fees_bps_bytes = pool_acc.fees_bps;
+    let signer_seeds = [
+        Seed::from(Pool::POOL_SEED.as_bytes()),
+        Seed::from(pool_acc.token_0_mint.as_ref()),
+        Seed::from(pool_acc.token_1_mint.as_ref()),
+        Seed::from(&fees_bps_bytes),
+        Seed::from(&pda_bump_bytes[..]),
+    ];
+    let pool_signers = [Signer::from(&signer_seeds[..])];
+
+    for (vault, mint, to, amount) in [
+        (vault_0_ata, token_0_mint, borrower_token_0_ata, ix_data.amount_0),
+        (vault_1_ata, token_1_mint, borrower_token_1_ata, ix_data.amount_1),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        //vault -> borrower
+        TransferChecked {
+            from: vault,
+            mint,
+            to,
+            authority: pool,
+            amount,
+            decimals: get_mint_decimals(mint)?,
+        }.invoke_signed(&pool_signers).map_err(|_| SolanaCoreError::TokenTransferFailed)?;
+    }
+
+    Ok(())
+}
+
+// A later top-level instruction must be this program's `flash_repay` for `pool`.
+fn require_flash_repay(instructions_sysvar: &AccountInfo, pool: &Pubkey) -> Result<(), SolanaCoreError> {
+    let instructions = Instructions::try_from(instructions_sysvar).map_err(|_| SolanaCoreError::SysvarUnavailable)?;
+    let current = instructions.load_current_index() as usize;
+    for index in current + 1..instructions.num_instructions() {
+        let Ok(instruction) = instructions.load_instruction_at(index) else {
+            break;
+        };
+        if instruction.get_program_id() == &crate::ID
+            && instruction.get_instruction_data().first() == Some(&FLASH_REPAY_DISCRIMINATOR)
+            && instruction.get_account_meta_at(1).is_ok_and(|meta| meta.key == *pool)
+        {
+            return Ok(());
+        }
+    }
+    Err(SolanaCoreError::FlashLoanNotRepaid)
+}
@@ -0,0 +1,75 @@
+use pinocchio::account_info::AccountInfo;
+use pinocchio_token::instructions::TransferChecked;
+
+use crate::{
+    errors::SolanaCoreError,
+    events::{emit, FlashLoanEvent},
+    utils::{current_timestamp, get_mint_decimals, swap_fee},
+    states::Pool,
+};
+
+// Repays the pool's outstanding flash loan plus the pool fee on each borrowed amount,
+// and unlocks it. The fee is added to the reserves, i.e. credited to LPs.
+pub fn flash_repay(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [borrower, pool, borrower_token_0_ata, borrower_token_1_ata, token_0_mint, token_1_mint, vault_0_ata, vault_1_ata, _token_program] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !borrower.is_signer() {
+        return Err(SolanaCoreError::SignerRequired);
+    }
+    if !data.is_empty() {
+        return Err(SolanaCoreError::InvalidInstructionData);
+    }
+
+    let pool_acc: &mut Pool = unsafe { Pool::load_mut(pool) }?;
+    if *token_0_mint.key() != pool_acc.token_0_mint
+        || *token_1_mint.key() != pool_acc.token_1_mint
+        || *vault_0_ata.key() != pool_acc.vault_0
+        || *vault_1_ata.key() != pool_acc.vault_1
+    {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+
+    let (amount_0, amount_1) = pool_acc.outstanding_flash_loan()?;
+    let fees_bps = pool_acc.fee_rate_bps(current_timestamp()?);
+    let (fee_0, fee_1) = (swap_fee(amount_0, fees_bps)?, swap_fee(amount_1, fees_bps)?);
+
+    for (from, mint, vault, amount, fee) in [
+        (borrower_token_0_ata, token_0_mint, vault_0_ata, amount_0, fee_0),
+        (borrower_token_1_ata, token_1_mint, vault_1_ata, amount_1, fee_1),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        //borrower -> vault
+        TransferChecked {
+            from,
+            mint,
+            to: vault,
+            authority: borrower,
+            amount: amount.checked_add(fee).ok_or(SolanaCoreError::OverFlowDetected)?,
+            decimals: get_mint_decimals(mint)?,
+        }.invoke().map_err(|_| SolanaCoreError::FlashLoanNotRepaid)?;
+    }
+
+    // unlocked and credited only once both repayments went through
+    pool_acc.finish_flash_loan()?;
+
+    let reserve_0_after = pool_acc.token_0_amount().checked_add(fee_0).ok_or(SolanaCoreError::OverFlowDetected)?;
+    let reserve_1_after = pool_acc.token_1_amount().checked_add(fee_1).ok_or(SolanaCoreError::OverFlowDetected)?;
+    pool_acc.set_token_0_amount(reserve_0_after);
+    pool_acc.set_token_1_amount(reserve_1_after);
+
+    emit(&FlashLoanEvent {
+        pool: *pool.key(),
+        borrower: *borrower.key(),
+        amount_0,
+        amount_1,
+        fee_0,
+        fee_1,
+        reserve_0_after,
+        reserve_1_after,
+    })?;
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use pinocchio::{account_info::AccountInfo, program::set_return_data};
+
+use crate::{
+    errors::SolanaCoreError,
+    utils::{
+        checks::load_ix_data,
+        current_timestamp, get_mint_supply, quote_swap, ByteReader, ByteWriter, SwapMode,
+    },
+    states::{
+        GetPrice, Pool,
+    },
+};
+
+/// Return data of `get_price`, encoded little-endian in field order.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PriceQuote {
+    // spot prices, Q64.64: price_0 = token_1 per token_0, price_1 = token_0 per token_1
+    pub price_0: u128,
+    pub price_1: u128,
+    pub reserve_0: u64,
+    pub reserve_1: u64,
+    pub lp_supply: u64,
+    pub fees_bps: u64,
+    // quote for `GetPrice::amount_in`, all zero when no amount was given
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    pub price_impact_bps: u64,
+}
+
+impl PriceQuote {
+    pub const LEN: usize = 16 * 2 + 8 * 8;
+
+    pub fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u128(self.price_0)?;
+        writer.write_u128(self.price_1)?;
+        writer.write_u64(self.reserve_0)?;
+        writer.write_u64(self.reserve_1)?;
+        writer.write_u64(self.lp_supply)?;
+        writer.write_u64(self.fees_bps)?;
+        writer.write_u64(self.amount_in)?;
+        writer.write_u64(self.amount_out)?;
+        writer.write_u64(self.fee)?;
+        writer.write_u64(self.price_impact_bps)
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let quote = Self {
+            price_0: reader.read_u128()?,
+            price_1: reader.read_u128()?,
+            reserve_0: reader.read_u64()?,
+            reserve_1: reader.read_u64()?,
+            lp_supply: reader.read_u64()?,
+            fees_bps: reader.read_u64()?,
+            amount_in: reader.read_u64()?,
+            amount_out: reader.read_u64()?,
+            fee: reader.read_u64()?,
+            price_impact_bps: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(quote)
+    }
+}
+
+// Read-only: validates the pool and returns a `PriceQuote` via `set_return_data`,
+// so other programs can price against the pool over CPI without decoding `Pool`.
+pub fn get_price(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [pool, lp_mint] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    let pool_acc: &Pool = Pool::load(pool)?;
+    if *lp_mint.key() != pool_acc.lp_mint {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+
+    let ix_data: GetPrice = load_ix_data::<GetPrice>(data)?;
+
+    let now = current_timestamp()?;
+    let (reserve_0, reserve_1) = (pool_acc.token_0_amount(), pool_acc.token_1_amount());
+    let (price_0, price_1) = pool_acc.spot_prices(now);
+    let mut price_quote = PriceQuote {
+        price_0,
+        price_1,
+        reserve_0,
+        reserve_1,
+        lp_supply: get_mint_supply(lp_mint)?,
+        fees_bps: pool_acc.effective_fees_bps(now),
+        ..PriceQuote::default()
+    };
+
+    if ix_data.amount_in > 0 {
+        let quote = quote_swap(pool_acc, ix_data.amount_in, ix_data.direction, SwapMode::ExactIn, now)?;
+        price_quote.amount_in = quote.amount_in;
+        price_quote.amount_out = quote.amount_out;
+        price_quote.fee = quote.fee;
+        price_quote.price_impact_bps = quote.price_impact_bps;
+    }
+
+    let mut return_data = [0u8; PriceQuote::LEN];
+    price_quote.pack_into(&mut return_data)?;
+    set_return_data(&return_data);
+
+    Ok(())
+}
@@ -0,0 +1,96 @@
+use core::mem::offset_of;
+use pinocchio::{
+    account_info::{AccountInfo, MAX_PERMITTED_DATA_INCREASE},
+    instruction::{Seed, Signer},
+    sysvars::{rent::Rent, Sysvar},
+};
+use pinocchio_system::instructions::{CreateAccount, Transfer};
+
+use crate::{
+    errors::SolanaCoreError,
+    utils::{
+        checks::load_ix_data,
+        current_timestamp, validate_pda,
+    },
+    states::{
+        IncreaseObservationCardinality, Observations, ObservationsHeader, Pool,
+    },
+};
+
+// Creates the pool's observations account on first use, otherwise grows it to
+// `cardinality_next` slots. The payer funds the extra rent.
+pub fn increase_observation_cardinality(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [payer, pool, observations, _system_program] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !payer.is_signer() {
+        return Err(SolanaCoreError::MissingRequiredSignature);
+    }
+
+    let ix_data: IncreaseObservationCardinality = load_ix_data::<IncreaseObservationCardinality>(data)?;
+    if ix_data.cardinality_next == 0 || ix_data.cardinality_next > Observations::MAX_CARDINALITY {
+        return Err(SolanaCoreError::InvalidCardinality);
+    }
+
+    let pool_acc: &Pool = Pool::load(pool)?;
+
+    let bump = [ix_data.observations_bump];
+    validate_pda(&[Observations::SEED.as_bytes(), pool.key().as_ref(), &bump], observations.key())?;
+
+    let rent = match Rent::get() {
+        Ok(rent) => rent,
+        Err(_) => return Err(SolanaCoreError::SysvarUnavailable),
+    };
+    let new_len = Observations::space(ix_data.cardinality_next);
+
+    if observations.data_is_empty() {
+        let signer_seeds = [
+            Seed::from(Observations::SEED.as_bytes()),
+            Seed::from(pool.key()),
+            Seed::from(&bump),
+        ];
+        let _ = CreateAccount {
+            from: payer,
+            to: observations,
+            lamports: rent.minimum_balance(new_len),
+            space: new_len as u64,
+            owner: &crate::ID,
+        }.invoke_signed(&[Signer::from(&signer_seeds[..])]);
+
+        // seed the ring with the pool's current accumulators
+        let now = current_timestamp()?;
+        Observations::init(observations, pool.key(), ix_data.observations_bump, &pool_acc.observe(now))?;
+        return Ok(());
+    }
+
+    let current = Observations::load(observations, pool.key())?;
+    if ix_data.cardinality_next <= current.cardinality_next() {
+        // already large enough
+        return Ok(());
+    }
+
+    if new_len - observations.data_len() > MAX_PERMITTED_DATA_INCREASE {
+        return Err(SolanaCoreError::InvalidCardinality);
+    }
+
+    let required_lamports = rent.minimum_balance(new_len);
+    if observations.lamports() < required_lamports {
+        let _ = Transfer {
+            from: payer,
+            to: observations,
+            lamports: required_lamports - observations.lamports(),
+        }.invoke();
+    }
+
+    if observations.resize(new_len).is_err() {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+
+    // the new slots are zeroed; they join the ring once the write index wraps to them
+    let offset = offset_of!(ObservationsHeader, cardinality_next);
+    let bytes = unsafe { observations.borrow_mut_data_unchecked() };
+    bytes[offset..offset + 2].copy_from_slice(&ix_data.cardinality_next.to_le_bytes());
+
+    Ok(())
+}
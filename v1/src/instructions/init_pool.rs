@@ -6,23 +6,24 @@ use pinocchio::{
     ProgramResult,
     msg,
 };
-use pinocchio_system::instructions::CreateAccount;
+use pinocchio_system::instructions::{CreateAccount, Transfer};
 use pinocchio_token::instructions::{InitializeMint, TransferChecked, MintToChecked};
 use pinocchio_associated_token_account::instructions::Create;
 
 use crate::{
     errors::SolanaCoreError,
+    events::{emit, PoolCreatedEvent},
     utils::{
         checks::{load_ix_data, DataLen},
-        load_acc_mut_unchecked, validate_pda
+        get_mint_decimals, get_token_mint_and_owner, load_acc_mut_unchecked, validate_pda, current_timestamp, Curve, CurveType, PoolCurve
     },
     states::{
-        Pool, InitPool
+        Config, Pool, InitPool
     }
 };
 
 pub fn init_pool(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
-    let [signer, pool, token_0_ata, token_1_ata, token_0_mint, token_1_mint, vault_0_ata, vault_1_ata, lp_mint, lp_user_ata, sysvar_rent_acc, system_program, token_program] = accounts else {
+    let [signer, pool, token_0_ata, token_1_ata, token_0_mint, token_1_mint, vault_0_ata, vault_1_ata, lp_mint, lp_user_ata, sysvar_rent_acc, system_program, token_program, config, treasury, remaining @ ..] = accounts else {
         return Err(SolanaCoreError::NotEnoughAccountKeys);
     };   
 
@@ -30,46 +31,43 @@ pub fn init_pool(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCore
         return Err(SolanaCoreError::MissingRequiredSignature);
     }
 
+    let config_acc: &Config = Config::load(config)?;
+    config_acc.check_treasury(treasury)?;
+
     let rent = match Rent::from_account_info(sysvar_rent_acc) {
         Ok(rent) => rent,
         Err(_) => return Err(SolanaCoreError::InvalidAccountData),
     };
 
-    let ix_data = unsafe { load_ix_data::<InitPool>(data) }?;
+    let ix_data: InitPool = load_ix_data::<InitPool>(data)?;
+    ix_data.check_accounts(
+        token_0_mint.key(),
+        token_1_mint.key(),
+        vault_0_ata.key(),
+        vault_1_ata.key(),
+        lp_mint.key(),
+    )?;
 
     let pda_bump_bytes = [ix_data.pool_bump];
+    let fees_bps_bytes = ix_data.fees_bps.to_le_bytes();
 
     let signer_seeds = [
         Seed::from(Pool::POOL_SEED.as_bytes()),
         Seed::from(&ix_data.token_0_mint),
         Seed::from(&ix_data.token_1_mint),
+        Seed::from(&fees_bps_bytes),
         Seed::from(&pda_bump_bytes),
     ];
 
     let pool_signers = [Signer::from(&signer_seeds[..])];
 
-    let signer_seed_slices: Vec<&[u8]> = signer_seeds.iter()
-        .map(|s| s.as_ref())
-        .collect();
-
-    let signer_seed_slices_: &[&[u8]] = &signer_seed_slices;
-    
-    validate_pda(signer_seed_slices_, pool.key())?;
+    let signer_seed_slices: [&[u8]; 5] = signer_seeds.each_ref().map(|s| s.as_ref());
+    validate_pda(&signer_seed_slices, pool.key())?;
 
     let lp_bump = [ix_data.lp_bump];
-    let lp_signer_seeds = [
-        Seed::from(Pool::LP_SEED.as_bytes()),
-        Seed::from(pool.key()),
-        Seed::from(&lp_bump),
-    ];
-    
-    let lp_signer_seed_slices: Vec<&[u8]> = lp_signer_seeds.iter()
-        .map(|s| s.as_ref())
-        .collect();
+    validate_pda(&[Pool::LP_SEED.as_bytes(), pool.key().as_ref(), &lp_bump], lp_mint.key())?;
 
-    let lp_signer_seed_slices: &[&[u8]] = &lp_signer_seed_slices;
-    
-    let _ = validate_pda(lp_signer_seed_slices, lp_mint.key())?;
+    pay_pool_creation_fee(config_acc, signer, treasury, remaining)?;
 
     msg!("Creating pool account");
     
@@ -101,6 +99,13 @@ pub fn init_pool(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCore
         token_program,
     }.invoke();
 
+    // the vaults must be the pool's token accounts, whether or not they were created above
+    if get_token_mint_and_owner(vault_0_ata)? != (*token_0_mint.key(), *pool.key())
+        || get_token_mint_and_owner(vault_1_ata)? != (*token_1_mint.key(), *pool.key())
+    {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+
     msg!("Transferring initial tokens");
 
     let _ = TransferChecked {
@@ -131,8 +136,9 @@ pub fn init_pool(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCore
         freeze_authority: Some(pool.key()),
     }.invoke();
 
-    // Calculate LP tokens to mint: L = sqrt(x * y)
-    let lp_to_mint = ((ix_data.token_0_amount as f64) * (ix_data.token_1_amount as f64)).sqrt() as u64;
+    // Calculate LP tokens to mint, e.g. L = sqrt(x * y) on the constant-product curve
+    let curve = PoolCurve::new(CurveType::try_from(ix_data.curve_type)?, ix_data.amp, ix_data.weight_0_bps);
+    let lp_to_mint = curve.initial_lp(ix_data.token_0_amount, ix_data.token_1_amount)?;
 
     msg!("Minting LP tokens");
 
@@ -146,11 +152,61 @@ pub fn init_pool(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCore
 
     msg!("Initializing pool state");
 
-    match Pool::init_pool(pool, ix_data) {
-        Ok(_) => {},
-        Err(_) => return Err(SolanaCoreError::InvalidInstructionData),
-    }
+    Pool::init_pool(pool, &ix_data, current_timestamp()?)?;
     
+    emit(&PoolCreatedEvent {
+        pool: *pool.key(),
+        creator: *signer.key(),
+        token_0_mint: ix_data.token_0_mint,
+        token_1_mint: ix_data.token_1_mint,
+        lp_mint: ix_data.lp_mint,
+        fees_bps: ix_data.fees_bps,
+        token_0_amount: ix_data.token_0_amount,
+        token_1_amount: ix_data.token_1_amount,
+        lp_amount: lp_to_mint,
+    })?;
+
     msg!("Pool initialized successfully");
     Ok(())
 }
+
+// Charges the config's pool creation fee to `signer`: in lamports straight from the
+// signer, or in tokens from `fee_accounts = [fee_token_account, fee_mint]`, the signer's
+// account of the fee mint and the mint itself.
+fn pay_pool_creation_fee(
+    config: &Config,
+    signer: &AccountInfo,
+    treasury: &AccountInfo,
+    fee_accounts: &[AccountInfo],
+) -> Result<(), SolanaCoreError> {
+    let fee = config.pool_creation_fee();
+    if fee == 0 {
+        return Ok(());
+    }
+
+    msg!("Paying pool creation fee");
+
+    if config.pool_creation_fee_in_lamports() {
+        return Transfer {
+            from: signer,
+            to: treasury,
+            lamports: fee,
+        }.invoke().map_err(|_| SolanaCoreError::PoolCreationFeeNotPaid);
+    }
+
+    let [fee_token_account, fee_mint, ..] = fee_accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+    if *fee_mint.key() != config.pool_creation_fee_mint {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+
+    TransferChecked {
+        from: fee_token_account,
+        mint: fee_mint,
+        to: treasury,
+        authority: signer,
+        amount: fee,
+        decimals: get_mint_decimals(fee_mint)?,
+    }.invoke().map_err(|_| SolanaCoreError::PoolCreationFeeNotPaid)
+}
@@ -0,0 +1,54 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    sysvars::{rent::Rent, Sysvar},
+};
+use pinocchio_system::instructions::CreateAccount;
+
+use crate::{
+    errors::SolanaCoreError,
+    utils::{
+        checks::{load_ix_data, DataLen},
+        validate_pda,
+    },
+    states::{
+        InitReferrer, Referrer,
+    },
+};
+
+// Creates the referral stats account of `owner` in `mint`. Anyone may pay for one; the
+// stats only count swaps that pass it.
+pub fn init_referrer(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [payer, referrer, owner, mint, _system_program] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !payer.is_signer() {
+        return Err(SolanaCoreError::MissingRequiredSignature);
+    }
+
+    let ix_data: InitReferrer = load_ix_data::<InitReferrer>(data)?;
+
+    let bump = [ix_data.referrer_bump];
+    validate_pda(&[Referrer::SEED.as_bytes(), owner.key().as_ref(), mint.key().as_ref(), &bump], referrer.key())?;
+
+    let rent = match Rent::get() {
+        Ok(rent) => rent,
+        Err(_) => return Err(SolanaCoreError::SysvarUnavailable),
+    };
+    let signer_seeds = [
+        Seed::from(Referrer::SEED.as_bytes()),
+        Seed::from(owner.key()),
+        Seed::from(mint.key()),
+        Seed::from(&bump),
+    ];
+    let _ = CreateAccount {
+        from: payer,
+        to: referrer,
+        lamports: rent.minimum_balance(Referrer::LEN),
+        space: Referrer::LEN as u64,
+        owner: &crate::ID,
+    }.invoke_signed(&[Signer::from(&signer_seeds[..])]);
+
+    Referrer::init(referrer, owner.key(), mint.key(), ix_data.referrer_bump)
+}
@@ -2,6 +2,37 @@ use crate::states::{
     InitPool
 };
 use shank::ShankInstruction;
+
+// Instruction discriminators: the first byte of the instruction data.
+pub const INIT_POOL_DISCRIMINATOR: u8 = 0;
+pub const LIQUIDATE_POOL_DISCRIMINATOR: u8 = 1;
+pub const DELIQUIDATE_POOL_DISCRIMINATOR: u8 = 2;
+pub const SWAP_DISCRIMINATOR: u8 = 3;
+pub const INCREASE_OBSERVATION_CARDINALITY_DISCRIMINATOR: u8 = 4;
+pub const OBSERVE_TWAP_DISCRIMINATOR: u8 = 5;
+pub const GET_PRICE_DISCRIMINATOR: u8 = 6;
+pub const INIT_CONFIG_DISCRIMINATOR: u8 = 7;
+pub const RAMP_AMP_DISCRIMINATOR: u8 = 8;
+pub const INIT_CL_POOL_DISCRIMINATOR: u8 = 9;
+pub const INIT_TICK_ARRAY_DISCRIMINATOR: u8 = 10;
+pub const OPEN_POSITION_DISCRIMINATOR: u8 = 11;
+pub const INCREASE_LIQUIDITY_DISCRIMINATOR: u8 = 12;
+pub const DECREASE_LIQUIDITY_DISCRIMINATOR: u8 = 13;
+pub const CL_SWAP_DISCRIMINATOR: u8 = 14;
+pub const INIT_MULTI_POOL_DISCRIMINATOR: u8 = 15;
+pub const MULTI_SWAP_DISCRIMINATOR: u8 = 16;
+pub const MULTI_DEPOSIT_DISCRIMINATOR: u8 = 17;
+pub const MULTI_WITHDRAW_DISCRIMINATOR: u8 = 18;
+pub const MULTI_DEPOSIT_SINGLE_DISCRIMINATOR: u8 = 19;
+pub const MULTI_WITHDRAW_SINGLE_DISCRIMINATOR: u8 = 20;
+pub const ROUTE_SWAP_DISCRIMINATOR: u8 = 21;
+pub const FLASH_LOAN_DISCRIMINATOR: u8 = 22;
+pub const FLASH_REPAY_DISCRIMINATOR: u8 = 23;
+pub const SET_DYNAMIC_FEE_DISCRIMINATOR: u8 = 24;
+pub const UPDATE_POOL_FEE_DISCRIMINATOR: u8 = 25;
+pub const INIT_REFERRER_DISCRIMINATOR: u8 = 26;
+pub const SET_POOL_CREATION_FEE_DISCRIMINATOR: u8 = 27;
+
 #[derive(ShankInstruction)]
 pub enum SolanaCoreInstruction {
     
@@ -18,6 +49,10 @@ pub enum SolanaCoreInstruction {
     #[account(10, name = "rent_sysvar", desc = "")]
     #[account(11, name = "system_program", desc = "")]
     #[account(12, name = "token_program", desc = "")]
+    #[account(13, name = "Config", desc = "")]
+    #[account(14, writable, name = "Treasury", desc = "Must match the config treasury")]
+    #[account(15, optional, writable, name = "Fee Token Account", desc = "Signer's account of the fee mint, for a fee in a token")]
+    #[account(16, optional, name = "Fee Mint", desc = "For a fee in a token")]
     InitPool,
     // Liquidate_pool
     #[account(0,name = "Signer")]
@@ -48,6 +83,195 @@ pub enum SolanaCoreInstruction {
     #[account(10, name = "rent_sysvar", desc = "")]
     #[account(11, name = "system_program", desc = "")]
     #[account(12, name = "token_program", desc = "")]
-    DeliquidatePool
+    DeliquidatePool,
     //SWAP
+    #[account(0, signer, name = "Signer")]
+    #[account(1, writable, name = "Pool Account", desc = "")]
+    #[account(2, writable, name = "Token_0_ATA", desc = "")]
+    #[account(3, writable, name = "Token_1_ATA", desc = "")]
+    #[account(4, name = "Token_0 Mint", desc = "")]
+    #[account(5, name = "Token_1 Mint", desc = "")]
+    #[account(6, writable, name = "Vault_0 ATA", desc = "")]
+    #[account(7, writable, name = "Vault_1 ATA", desc = "")]
+    #[account(8, name = "system_program", desc = "")]
+    #[account(9, name = "token_program", desc = "")]
+    #[account(10, optional, writable, name = "Observations", desc = "Pool observations PDA; records a TWAP observation when present")]
+    #[account(11, optional, name = "Config", desc = "Referred swaps: caps the referral fee share")]
+    #[account(12, optional, writable, name = "Referrer token account", desc = "Referred swaps: receives the referral fee, input mint")]
+    #[account(13, optional, writable, name = "Referrer", desc = "Referred swaps: referrer stats PDA, optional")]
+    Swap,
+    #[account(0, signer, writable, name = "Payer")]
+    #[account(1, name = "Pool Account", desc = "")]
+    #[account(2, writable, name = "Observations", desc = "PDA [\"observations\", pool]")]
+    #[account(3, name = "system_program", desc = "")]
+    IncreaseObservationCardinality,
+    #[account(0, name = "Pool Account", desc = "")]
+    #[account(1, name = "Observations", desc = "PDA [\"observations\", pool]")]
+    ObserveTwap,
+    #[account(0, name = "Pool Account", desc = "")]
+    #[account(1, name = "lp_mint", desc = "")]
+    GetPrice,
+    #[account(0, signer, writable, name = "Upgrade authority", desc = "Program upgrade authority, pays for the config")]
+    #[account(1, writable, name = "Config", desc = "PDA [\"config\"]")]
+    #[account(2, name = "Program data", desc = "ProgramData account of this program")]
+    #[account(3, name = "system_program", desc = "")]
+    InitConfig,
+    #[account(0, signer, name = "Admin", desc = "Config admin")]
+    #[account(1, name = "Config", desc = "")]
+    #[account(2, writable, name = "Pool Account", desc = "Stable-swap pool")]
+    RampAmp,
+    #[account(0, signer, writable, name = "Signer", desc = "Pays for the pool and its vaults")]
+    #[account(1, writable, name = "CL Pool Account", desc = "PDA [\"cl_pool\", token_0_mint, token_1_mint, fees_bps]")]
+    #[account(2, name = "Token_0 Mint", desc = "")]
+    #[account(3, name = "Token_1 Mint", desc = "")]
+    #[account(4, writable, name = "Vault_0 ATA", desc = "")]
+    #[account(5, writable, name = "Vault_1 ATA", desc = "")]
+    #[account(6, name = "system_program", desc = "")]
+    #[account(7, name = "token_program", desc = "")]
+    InitClPool,
+    #[account(0, signer, writable, name = "Payer")]
+    #[account(1, name = "CL Pool Account", desc = "")]
+    #[account(2, writable, name = "Tick Array", desc = "PDA [\"tick_array\", pool, start_tick_index]")]
+    #[account(3, name = "system_program", desc = "")]
+    InitTickArray,
+    #[account(0, signer, writable, name = "Owner", desc = "Position owner, pays for the account")]
+    #[account(1, name = "CL Pool Account", desc = "")]
+    #[account(2, writable, name = "Position", desc = "PDA [\"position\", pool, owner, tick_lower, tick_upper]")]
+    #[account(3, name = "system_program", desc = "")]
+    OpenPosition,
+    #[account(0, signer, name = "Owner", desc = "")]
+    #[account(1, writable, name = "CL Pool Account", desc = "")]
+    #[account(2, writable, name = "Position", desc = "")]
+    #[account(3, writable, name = "Tick Array Lower", desc = "Tick array holding tick_lower")]
+    #[account(4, writable, name = "Tick Array Upper", desc = "Tick array holding tick_upper")]
+    #[account(5, writable, name = "Token_0_ATA", desc = "")]
+    #[account(6, writable, name = "Token_1_ATA", desc = "")]
+    #[account(7, name = "Token_0 Mint", desc = "")]
+    #[account(8, name = "Token_1 Mint", desc = "")]
+    #[account(9, writable, name = "Vault_0 ATA", desc = "")]
+    #[account(10, writable, name = "Vault_1 ATA", desc = "")]
+    #[account(11, name = "token_program", desc = "")]
+    IncreaseLiquidity,
+    #[account(0, signer, name = "Owner", desc = "")]
+    #[account(1, writable, name = "CL Pool Account", desc = "")]
+    #[account(2, writable, name = "Position", desc = "")]
+    #[account(3, writable, name = "Tick Array Lower", desc = "Tick array holding tick_lower")]
+    #[account(4, writable, name = "Tick Array Upper", desc = "Tick array holding tick_upper")]
+    #[account(5, writable, name = "Token_0_ATA", desc = "")]
+    #[account(6, writable, name = "Token_1_ATA", desc = "")]
+    #[account(7, name = "Token_0 Mint", desc = "")]
+    #[account(8, name = "Token_1 Mint", desc = "")]
+    #[account(9, writable, name = "Vault_0 ATA", desc = "")]
+    #[account(10, writable, name = "Vault_1 ATA", desc = "")]
+    #[account(11, name = "token_program", desc = "")]
+    DecreaseLiquidity,
+    #[account(0, signer, name = "Signer")]
+    #[account(1, writable, name = "CL Pool Account", desc = "")]
+    #[account(2, writable, name = "Token_0_ATA", desc = "")]
+    #[account(3, writable, name = "Token_1_ATA", desc = "")]
+    #[account(4, name = "Token_0 Mint", desc = "")]
+    #[account(5, name = "Token_1 Mint", desc = "")]
+    #[account(6, writable, name = "Vault_0 ATA", desc = "")]
+    #[account(7, writable, name = "Vault_1 ATA", desc = "")]
+    #[account(8, name = "token_program", desc = "")]
+    #[account(9, writable, name = "Tick Array 0", desc = "Tick array holding the current tick")]
+    #[account(10, optional, writable, name = "Tick Array 1", desc = "Next tick array in the swap direction")]
+    #[account(11, optional, writable, name = "Tick Array 2", desc = "")]
+    ClSwap,
+    // Multi-token pools: per-token accounts follow the listed ones as
+    // (mint, user ATA, vault ATA) triplets in pool order
+    #[account(0, signer, writable, name = "Signer", desc = "Pays for the pool and its vaults")]
+    #[account(1, writable, name = "Multi Pool Account", desc = "PDA [\"multi_pool\", mint_0, .., mint_n, fees_bps]")]
+    #[account(2, writable, name = "LP Mint", desc = "PDA [\"lp\", pool]")]
+    #[account(3, writable, name = "LP_USER_ATA", desc = "")]
+    #[account(4, name = "rent_sysvar", desc = "")]
+    #[account(5, name = "system_program", desc = "")]
+    #[account(6, name = "token_program", desc = "")]
+    InitMultiPool,
+    #[account(0, signer, name = "Signer")]
+    #[account(1, writable, name = "Multi Pool Account", desc = "")]
+    #[account(2, writable, name = "Token_In_ATA", desc = "")]
+    #[account(3, writable, name = "Token_Out_ATA", desc = "")]
+    #[account(4, name = "Token_In Mint", desc = "")]
+    #[account(5, name = "Token_Out Mint", desc = "")]
+    #[account(6, writable, name = "Vault_In ATA", desc = "")]
+    #[account(7, writable, name = "Vault_Out ATA", desc = "")]
+    #[account(8, name = "token_program", desc = "")]
+    MultiSwap,
+    #[account(0, signer, name = "Signer")]
+    #[account(1, writable, name = "Multi Pool Account", desc = "")]
+    #[account(2, writable, name = "LP Mint", desc = "")]
+    #[account(3, writable, name = "LP_USER_ATA", desc = "")]
+    #[account(4, name = "token_program", desc = "")]
+    MultiDeposit,
+    #[account(0, signer, name = "Signer")]
+    #[account(1, writable, name = "Multi Pool Account", desc = "")]
+    #[account(2, writable, name = "LP Mint", desc = "")]
+    #[account(3, writable, name = "LP_USER_ATA", desc = "")]
+    #[account(4, name = "token_program", desc = "")]
+    MultiWithdraw,
+    #[account(0, signer, name = "Signer")]
+    #[account(1, writable, name = "Multi Pool Account", desc = "")]
+    #[account(2, writable, name = "LP Mint", desc = "")]
+    #[account(3, writable, name = "LP_USER_ATA", desc = "")]
+    #[account(4, writable, name = "Token_ATA", desc = "")]
+    #[account(5, name = "Token Mint", desc = "")]
+    #[account(6, writable, name = "Vault ATA", desc = "")]
+    #[account(7, name = "token_program", desc = "")]
+    MultiDepositSingle,
+    #[account(0, signer, name = "Signer")]
+    #[account(1, writable, name = "Multi Pool Account", desc = "")]
+    #[account(2, writable, name = "LP Mint", desc = "")]
+    #[account(3, writable, name = "LP_USER_ATA", desc = "")]
+    #[account(4, writable, name = "Token_ATA", desc = "")]
+    #[account(5, name = "Token Mint", desc = "")]
+    #[account(6, writable, name = "Vault ATA", desc = "")]
+    #[account(7, name = "token_program", desc = "")]
+    MultiWithdrawSingle,
+    // Each leg appends (pool, mint_out, vault_in, vault_out); 1 to MAX_ROUTE_LEGS legs
+    #[account(0, signer, name = "Signer")]
+    #[account(1, writable, name = "Source ATA", desc = "User account of the first leg's input mint")]
+    #[account(2, writable, name = "Destination ATA", desc = "User account of the last leg's output mint")]
+    #[account(3, name = "Source Mint", desc = "")]
+    #[account(4, name = "token_program", desc = "")]
+    RouteSwap,
+    #[account(0, signer, name = "Borrower")]
+    #[account(1, writable, name = "Pool Account", desc = "")]
+    #[account(2, writable, name = "Token_0_ATA", desc = "")]
+    #[account(3, writable, name = "Token_1_ATA", desc = "")]
+    #[account(4, name = "Token_0 Mint", desc = "")]
+    #[account(5, name = "Token_1 Mint", desc = "")]
+    #[account(6, writable, name = "Vault_0 ATA", desc = "")]
+    #[account(7, writable, name = "Vault_1 ATA", desc = "")]
+    #[account(8, name = "token_program", desc = "")]
+    #[account(9, name = "instructions_sysvar", desc = "Checked for a later FlashRepay of the same pool")]
+    FlashLoan,
+    #[account(0, signer, name = "Borrower")]
+    #[account(1, writable, name = "Pool Account", desc = "")]
+    #[account(2, writable, name = "Token_0_ATA", desc = "")]
+    #[account(3, writable, name = "Token_1_ATA", desc = "")]
+    #[account(4, name = "Token_0 Mint", desc = "")]
+    #[account(5, name = "Token_1 Mint", desc = "")]
+    #[account(6, writable, name = "Vault_0 ATA", desc = "")]
+    #[account(7, writable, name = "Vault_1 ATA", desc = "")]
+    #[account(8, name = "token_program", desc = "")]
+    FlashRepay,
+    #[account(0, signer, name = "Admin", desc = "Config admin")]
+    #[account(1, name = "Config", desc = "")]
+    #[account(2, writable, name = "Pool Account", desc = "")]
+    SetDynamicFee,
+    #[account(0, signer, name = "Admin", desc = "Config admin")]
+    #[account(1, name = "Config", desc = "")]
+    #[account(2, writable, name = "Pool Account", desc = "")]
+    UpdatePoolFee,
+    #[account(0, signer, writable, name = "Payer", desc = "Pays for the referrer account")]
+    #[account(1, writable, name = "Referrer", desc = "PDA [\"referrer\", owner, mint]")]
+    #[account(2, name = "Owner", desc = "Owner of the referrer's token accounts")]
+    #[account(3, name = "Mint", desc = "Input mint the stats are counted in")]
+    #[account(4, name = "system_program", desc = "")]
+    InitReferrer,
+    #[account(0, signer, name = "Admin", desc = "Config admin")]
+    #[account(1, writable, name = "Config", desc = "")]
+    #[account(2, name = "Treasury", desc = "Token account of the fee mint, or any account for a fee in lamports")]
+    SetPoolCreationFee,
 }
\ No newline at end of file
@@ -11,10 +11,11 @@ use pinocchio_token::instructions::{InitializeMint, TransferChecked, InitializeA
 use pinocchio_associated_token_account::instructions::Create;
 use crate::{
     errors::SolanaCoreError,
+    events::{emit, DepositEvent},
     utils::{
         checks::{load_ix_data, DataLen},
-        load_acc_mut_unchecked, validate_pda,
-        lp_to_mint
+        validate_pda, current_timestamp,
+        Curve
     },
     states::{
         Pool, LiquidatePool
@@ -25,38 +26,45 @@ pub fn liquidate_pool(accounts: &[AccountInfo], data: &[u8]) -> Result<(), Solan
     let [signer, pool, token_0_ata, token_1_ata, token_0_mint, token_1_mint, vault_0_ata, vault_1_ata, lp_mint, lp_user_ata, sysvar_rent_acc, system_program, token_program] = accounts else {
         return Err(SolanaCoreError::NotEnoughAccountKeys);
     };
-    let mut pool_acc: Pool = unsafe { *load_acc_mut_unchecked::<Pool>(pool.borrow_mut_data_unchecked()).unwrap() };
-    let ix_data: &LiquidatePool = unsafe { load_ix_data::<LiquidatePool>(data).unwrap() };
+    let pool_acc: &mut Pool = unsafe { Pool::load_mut(pool) }?;
+    pool_acc.check_unlocked()?;
+    // accrue the TWAP accumulators at the pre-trade price
+    let now = current_timestamp()?;
+    pool_acc.update_price_accumulators(now);
+    let ix_data: LiquidatePool = load_ix_data::<LiquidatePool>(data)?;
 
     let pda_bump_bytes = [pool_acc.pool_bump];
+    let fees_bps_bytes = pool_acc.fees_bps;
     let signer_seeds = [
         Seed::from(Pool::POOL_SEED.as_bytes()),
         Seed::from(pool_acc.token_0_mint.as_ref()),
         Seed::from(pool_acc.token_1_mint.as_ref()),
+        Seed::from(&fees_bps_bytes),
         Seed::from(&pda_bump_bytes[..]),
     ];
 
     let pool_signers = [Signer::from(&signer_seeds[..])];
-    let signer_seed_slices: Vec<&[u8]> = signer_seeds.iter().map(|s| s.as_ref()).collect();
-    let _ =validate_pda(&signer_seed_slices, pool.key());
+    let signer_seed_slices: [&[u8]; 5] = signer_seeds.each_ref().map(|s| s.as_ref());
+    validate_pda(&signer_seed_slices, pool.key())?;
 
     let lp_bump = [pool_acc.lp_bump];
-    let lp_signer_seeds = [
-        Seed::from(Pool::LP_SEED.as_bytes()),
-        Seed::from(pool.key().as_ref()),
-        Seed::from(&lp_bump),
-    ];
-
-    let lp_signer_seed_slices: Vec<&[u8]> = lp_signer_seeds.iter().map(|s| s.as_ref()).collect();
-    let _ = validate_pda(&lp_signer_seed_slices, lp_mint.key());
+    validate_pda(&[Pool::LP_SEED.as_bytes(), pool.key().as_ref(), &lp_bump], lp_mint.key())?;
+    pool_acc.check_liquidity_accounts(
+        token_0_mint.key(),
+        token_1_mint.key(),
+        vault_0_ata.key(),
+        vault_1_ata.key(),
+        lp_mint.key(),
+    )?;
 
-    let (lp_to_mint, max_token_0, max_token_1) = lp_to_mint(
+    let lp_supply = get_mint_supply(lp_mint)?;
+    let (lp_to_mint, max_token_0, max_token_1) = pool_acc.curve(now)?.deposit(
         ix_data.deposit_token_0_amount,
         ix_data.deposit_token_1_amount,
-        pool_acc.token_0_amount,
-        pool_acc.token_1_amount,
-        get_mint_supply(lp_mint)?
-    ).expect("Return lp_mint, max_token_0, max_token_1");
+        pool_acc.token_0_amount(),
+        pool_acc.token_1_amount(),
+        lp_supply,
+    )?;
 
     // Mint LP tokens
     let _ = MintToChecked {
@@ -88,8 +96,19 @@ pub fn liquidate_pool(accounts: &[AccountInfo], data: &[u8]) -> Result<(), Solan
     }.invoke_signed(&pool_signers);
 
     // Update pool state
-    pool_acc.token_0_amount += max_token_0;
-    pool_acc.token_1_amount += max_token_1;
+    pool_acc.set_token_0_amount(pool_acc.token_0_amount() + max_token_0);
+    pool_acc.set_token_1_amount(pool_acc.token_1_amount() + max_token_1);
+
+    emit(&DepositEvent {
+        pool: *pool.key(),
+        user: *signer.key(),
+        token_0_amount: max_token_0,
+        token_1_amount: max_token_1,
+        lp_amount: lp_to_mint,
+        reserve_0_after: pool_acc.token_0_amount(),
+        reserve_1_after: pool_acc.token_1_amount(),
+        lp_supply_after: lp_supply + lp_to_mint,
+    })?;
 
     Ok(())
 }
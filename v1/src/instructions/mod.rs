@@ -1,12 +1,31 @@
 pub mod deliquidate_pool;
+pub mod flash_loan;
+pub mod flash_repay;
+pub mod get_price;
+pub mod increase_observation_cardinality;
 pub mod ix;
 pub mod init_pool;
+pub mod init_referrer;
 pub mod liquidate_pool;
+pub mod observe_twap;
+pub mod route_swap;
 pub mod swap;
 pub mod admin;
+pub mod concentrated;
+pub mod multi;
 
+pub use admin::*;
+pub use concentrated::*;
 pub use deliquidate_pool::*;
+pub use flash_loan::*;
+pub use flash_repay::*;
+pub use get_price::*;
+pub use increase_observation_cardinality::*;
 pub use init_pool::*;
+pub use init_referrer::*;
 pub use ix::*;
 pub use liquidate_pool::*;
+pub use multi::*;
+pub use observe_twap::*;
+pub use route_swap::*;
 pub use swap::*;
\ No newline at end of file
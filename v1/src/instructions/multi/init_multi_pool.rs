@@ -0,0 +1,165 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+    sysvars::rent::Rent,
+    msg,
+};
+use pinocchio_system::instructions::CreateAccount;
+use pinocchio_token::instructions::{InitializeMint, TransferChecked, MintToChecked};
+use pinocchio_associated_token_account::instructions::Create;
+
+use crate::{
+    errors::SolanaCoreError,
+    utils::{
+        checks::{load_ix_data, DataLen},
+        validate_pda, CurveType, BPS_DENOMINATOR, MAX_AMP, MIN_AMP, MIN_WEIGHT_BPS,
+    },
+    states::{
+        InitMultiPool, MultiPool, Pool, MAX_MULTI_POOL_TOKENS,
+    },
+};
+
+// Creates a multi-token pool seeded with `amounts`, its vault ATAs and its LP mint.
+// Token accounts follow the fixed ones as `(mint, user_ata, vault_ata)` triplets,
+// mints in ascending order.
+pub fn init_multi_pool(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [signer, pool, lp_mint, lp_user_ata, sysvar_rent_acc, system_program, token_program, token_accounts @ ..] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer() {
+        return Err(SolanaCoreError::MissingRequiredSignature);
+    }
+
+    let rent = match Rent::from_account_info(sysvar_rent_acc) {
+        Ok(rent) => rent,
+        Err(_) => return Err(SolanaCoreError::InvalidAccountData),
+    };
+
+    let ix_data: InitMultiPool = load_ix_data::<InitMultiPool>(data)?;
+    let token_count = ix_data.token_count as usize;
+    if !(2..=MAX_MULTI_POOL_TOKENS).contains(&token_count) || ix_data.fees_bps > 500 {
+        return Err(SolanaCoreError::InvalidInstructionData);
+    }
+    if token_accounts.len() != 3 * token_count {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    }
+    if ix_data.amounts[token_count..].iter().chain(&ix_data.weights_bps[token_count..]).any(|value| *value != 0) {
+        return Err(SolanaCoreError::InvalidInstructionData);
+    }
+    // ascending mints make the pool address unique per basket and reject duplicates
+    let mints = token_accounts.chunks_exact(3).map(|token| token[0].key());
+    if mints.clone().zip(mints.clone().skip(1)).any(|(mint, next)| mint >= next) {
+        return Err(SolanaCoreError::InvalidInstructionData);
+    }
+
+    let weights = &ix_data.weights_bps[..token_count];
+    match CurveType::try_from(ix_data.curve_type)? {
+        CurveType::StableSwap => {
+            if !(MIN_AMP..=MAX_AMP).contains(&ix_data.amp) {
+                return Err(SolanaCoreError::InvalidAmp);
+            }
+            if weights.iter().any(|weight| *weight != 0) {
+                return Err(SolanaCoreError::InvalidWeight);
+            }
+        }
+        CurveType::Weighted => {
+            if ix_data.amp != 0 {
+                return Err(SolanaCoreError::InvalidAmp);
+            }
+            if weights.iter().any(|weight| *weight < MIN_WEIGHT_BPS) || weights.iter().sum::<u64>() != BPS_DENOMINATOR {
+                return Err(SolanaCoreError::InvalidWeight);
+            }
+        }
+        _ => return Err(SolanaCoreError::InvalidCurveType),
+    }
+
+    let pda_bump_bytes = [ix_data.pool_bump];
+    let fees_bps_bytes = ix_data.fees_bps.to_le_bytes();
+    let (seeds, seeds_len) = MultiPool::seeds(mints.map(|mint| mint.as_ref()), &fees_bps_bytes, &pda_bump_bytes)?;
+    validate_pda(&seeds[..seeds_len], pool.key())?;
+    let signer_seeds = seeds.map(Seed::from);
+    let pool_signers = [Signer::from(&signer_seeds[..seeds_len])];
+
+    let lp_bump = [ix_data.lp_bump];
+    validate_pda(&[Pool::LP_SEED.as_bytes(), pool.key().as_ref(), &lp_bump], lp_mint.key())?;
+
+    msg!("Creating multi-token pool account");
+
+    let _ = CreateAccount {
+        from: signer,
+        to: pool,
+        space: MultiPool::LEN as u64,
+        owner: &crate::ID,
+        lamports: rent.minimum_balance(MultiPool::LEN),
+    }.invoke_signed(&pool_signers);
+
+    msg!("Creating vault ATAs and transferring initial tokens");
+
+    for (token, amount) in token_accounts.chunks_exact(3).zip(ix_data.amounts) {
+        let [mint, user_ata, vault_ata] = token else {
+            return Err(SolanaCoreError::NotEnoughAccountKeys);
+        };
+
+        let _ = Create {
+            funding_account: signer,
+            account: vault_ata,
+            wallet: pool,
+            mint,
+            system_program,
+            token_program,
+        }.invoke();
+
+        let _ = TransferChecked {
+            from: user_ata,
+            mint,
+            to: vault_ata,
+            authority: signer,
+            amount,
+            decimals: 9,
+        }.invoke();
+    }
+
+    msg!("Initializing LP mint");
+
+    let _ = InitializeMint {
+        mint: lp_mint,
+        rent_sysvar: sysvar_rent_acc,
+        decimals: 9,
+        mint_authority: pool.key(),
+        freeze_authority: Some(pool.key()),
+    }.invoke();
+
+    msg!("Initializing pool state");
+
+    let pool_acc = MultiPool::init(pool)?;
+    pool_acc.lp_mint = *lp_mint.key();
+    pool_acc.fees_bps = fees_bps_bytes;
+    pool_acc.pool_bump = ix_data.pool_bump;
+    pool_acc.lp_bump = ix_data.lp_bump;
+    pool_acc.curve_type = ix_data.curve_type;
+    pool_acc.token_count = ix_data.token_count;
+    pool_acc.amp = ix_data.amp.to_le_bytes();
+    for (index, token) in token_accounts.chunks_exact(3).enumerate() {
+        let entry = &mut pool_acc.tokens[index];
+        entry.mint = *token[0].key();
+        entry.vault = *token[2].key();
+        entry.set_reserve(ix_data.amounts[index]);
+        entry.weight_bps = ix_data.weights_bps[index].to_le_bytes();
+    }
+
+    // the invariant of the seeded reserves, like `Curve::initial_lp` on two-token pools
+    let lp_to_mint = pool_acc.basket()?.initial_lp()?;
+
+    msg!("Minting LP tokens");
+
+    let _ = MintToChecked {
+        mint: lp_mint,
+        account: lp_user_ata,
+        mint_authority: pool,
+        amount: lp_to_mint,
+        decimals: 9,
+    }.invoke_signed(&pool_signers);
+
+    Ok(())
+}
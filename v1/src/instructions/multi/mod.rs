@@ -0,0 +1,39 @@
+pub mod init_multi_pool;
+pub mod multi_deposit;
+pub mod multi_deposit_single;
+pub mod multi_swap;
+pub mod multi_withdraw;
+pub mod multi_withdraw_single;
+
+pub use init_multi_pool::*;
+pub use multi_deposit::*;
+pub use multi_deposit_single::*;
+pub use multi_swap::*;
+pub use multi_withdraw::*;
+pub use multi_withdraw_single::*;
+
+use pinocchio::account_info::AccountInfo;
+use crate::{errors::SolanaCoreError, states::MultiPool};
+
+// `(mint, user_ata, vault_ata)` of every pool token, in pool order, checked against `pool_acc`.
+pub(crate) fn multi_token_accounts<'a>(
+    pool_acc: &MultiPool,
+    accounts: &'a [AccountInfo],
+) -> Result<impl Iterator<Item = (usize, &'a AccountInfo, &'a AccountInfo, &'a AccountInfo)>, SolanaCoreError> {
+    if accounts.len() != 3 * pool_acc.token_count() {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    }
+    for (index, token) in accounts.chunks_exact(3).enumerate() {
+        pool_acc.check_token(index, &token[0], &token[2])?;
+    }
+    Ok(accounts.chunks_exact(3).enumerate().map(|(index, token)| (index, &token[0], &token[1], &token[2])))
+}
+
+// The LP mint of `pool_acc`.
+#[inline(always)]
+pub(crate) fn check_lp_mint(pool_acc: &MultiPool, lp_mint: &AccountInfo) -> Result<(), SolanaCoreError> {
+    if *lp_mint.key() != pool_acc.lp_mint {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+    Ok(())
+}
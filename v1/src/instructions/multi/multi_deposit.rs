@@ -0,0 +1,84 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+};
+use pinocchio_token::instructions::{TransferChecked, MintToChecked};
+
+use crate::{
+    errors::SolanaCoreError,
+    events::{emit, MultiDepositEvent},
+    instructions::{check_lp_mint, multi_token_accounts},
+    utils::{
+        checks::load_ix_data,
+        get_mint_supply,
+    },
+    states::{
+        MultiLiquidity, MultiPool,
+    },
+};
+
+// Mints `lp_amount` LP tokens against a proportional deposit of every pool token,
+// pulling at most `amount_limits`. Token accounts follow the fixed ones as
+// `(mint, user_ata, vault_ata)` triplets in pool order.
+pub fn multi_deposit(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [signer, pool, lp_mint, lp_user_ata, _token_program, token_accounts @ ..] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer() {
+        return Err(SolanaCoreError::SignerRequired);
+    }
+
+    let ix_data: MultiLiquidity = load_ix_data::<MultiLiquidity>(data)?;
+    if ix_data.lp_amount == 0 {
+        return Err(SolanaCoreError::InvalidInstructionData);
+    }
+    let pool_acc: &mut MultiPool = unsafe { MultiPool::load_mut(pool) }?;
+    check_lp_mint(pool_acc, lp_mint)?;
+
+    let lp_supply = get_mint_supply(lp_mint)?;
+    let amounts = pool_acc.basket()?.proportional_amounts(ix_data.lp_amount, lp_supply, true)?;
+    if amounts.iter().zip(ix_data.amount_limits).any(|(amount, limit)| *amount > limit) {
+        return Err(SolanaCoreError::Slippage);
+    }
+
+    for (index, mint, user_ata, vault_ata) in multi_token_accounts(pool_acc, token_accounts)? {
+        let _ = TransferChecked {
+            from: user_ata,
+            mint,
+            to: vault_ata,
+            authority: signer,
+            amount: amounts[index],
+            decimals: 9,
+        }.invoke();
+    }
+
+    let (seeds, seeds_len) = pool_acc.pool_seeds()?;
+    let signer_seeds = seeds.map(Seed::from);
+    let pool_signers = [Signer::from(&signer_seeds[..seeds_len])];
+
+    let _ = MintToChecked {
+        mint: lp_mint,
+        account: lp_user_ata,
+        mint_authority: pool,
+        amount: ix_data.lp_amount,
+        decimals: 9,
+    }.invoke_signed(&pool_signers);
+
+    for (index, amount) in amounts.iter().enumerate().take(pool_acc.token_count()) {
+        let token = pool_acc.token_mut(index)?;
+        token.set_reserve(token.reserve().checked_add(*amount).ok_or(SolanaCoreError::OverFlowDetected)?);
+    }
+
+    emit(&MultiDepositEvent {
+        pool: *pool.key(),
+        user: *signer.key(),
+        token_count: pool_acc.token_count,
+        amounts,
+        lp_amount: ix_data.lp_amount,
+        reserves_after: pool_acc.reserves(),
+        lp_supply_after: lp_supply + ix_data.lp_amount,
+    })?;
+
+    Ok(())
+}
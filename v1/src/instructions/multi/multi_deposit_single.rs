@@ -0,0 +1,80 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+};
+use pinocchio_token::instructions::{TransferChecked, MintToChecked};
+
+use crate::{
+    errors::SolanaCoreError,
+    events::{emit, MultiDepositEvent},
+    instructions::check_lp_mint,
+    utils::{
+        checks::load_ix_data,
+        get_mint_supply,
+    },
+    states::{
+        MultiPool, MultiSingleLiquidity, MAX_MULTI_POOL_TOKENS,
+    },
+};
+
+// Deposits `amount` of a single pool token for at least `limit` LP tokens.
+pub fn multi_deposit_single(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [signer, pool, lp_mint, lp_user_ata, user_ata, mint, vault_ata, _token_program] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer() {
+        return Err(SolanaCoreError::SignerRequired);
+    }
+
+    let ix_data: MultiSingleLiquidity = load_ix_data::<MultiSingleLiquidity>(data)?;
+    let pool_acc: &mut MultiPool = unsafe { MultiPool::load_mut(pool) }?;
+    let index = ix_data.token as usize;
+    check_lp_mint(pool_acc, lp_mint)?;
+    pool_acc.check_token(index, mint, vault_ata)?;
+
+    let lp_supply = get_mint_supply(lp_mint)?;
+    let (lp_to_mint, _fee) = pool_acc.basket()?.deposit_single(index, ix_data.amount, lp_supply)?;
+    if lp_to_mint < ix_data.limit {
+        return Err(SolanaCoreError::Slippage);
+    }
+    // the fee is part of the deposit and stays in the pool
+    let reserve = pool_acc.token(index)?.reserve().checked_add(ix_data.amount).ok_or(SolanaCoreError::OverFlowDetected)?;
+
+    let _ = TransferChecked {
+        from: user_ata,
+        mint,
+        to: vault_ata,
+        authority: signer,
+        amount: ix_data.amount,
+        decimals: 9,
+    }.invoke();
+
+    let (seeds, seeds_len) = pool_acc.pool_seeds()?;
+    let signer_seeds = seeds.map(Seed::from);
+    let pool_signers = [Signer::from(&signer_seeds[..seeds_len])];
+
+    let _ = MintToChecked {
+        mint: lp_mint,
+        account: lp_user_ata,
+        mint_authority: pool,
+        amount: lp_to_mint,
+        decimals: 9,
+    }.invoke_signed(&pool_signers);
+
+    pool_acc.token_mut(index)?.set_reserve(reserve);
+
+    let mut amounts = [0; MAX_MULTI_POOL_TOKENS];
+    amounts[index] = ix_data.amount;
+    emit(&MultiDepositEvent {
+        pool: *pool.key(),
+        user: *signer.key(),
+        token_count: pool_acc.token_count,
+        amounts,
+        lp_amount: lp_to_mint,
+        reserves_after: pool_acc.reserves(),
+        lp_supply_after: lp_supply + lp_to_mint,
+    })?;
+
+    Ok(())
+}
@@ -0,0 +1,82 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+};
+use pinocchio_token::instructions::TransferChecked;
+
+use crate::{
+    errors::SolanaCoreError,
+    events::{emit, MultiSwapEvent},
+    utils::{
+        checks::load_ix_data,
+        check_slippage,
+    },
+    states::{
+        MultiPool, MultiSwap,
+    },
+};
+
+// Swaps between any two tokens of a multi-token pool.
+pub fn multi_swap(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [signer, pool, user_in_ata, user_out_ata, mint_in, mint_out, vault_in, vault_out, _token_program] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer() {
+        return Err(SolanaCoreError::SignerRequired);
+    }
+
+    let ix_data: MultiSwap = load_ix_data::<MultiSwap>(data)?;
+    let pool_acc: &mut MultiPool = unsafe { MultiPool::load_mut(pool) }?;
+    let (token_in, token_out) = (ix_data.token_in as usize, ix_data.token_out as usize);
+    pool_acc.check_token(token_in, mint_in, vault_in)?;
+    pool_acc.check_token(token_out, mint_out, vault_out)?;
+
+    let quote = pool_acc.basket()?.quote_swap(token_in, token_out, ix_data.amount, ix_data.mode)?;
+    check_slippage(ix_data.mode, quote.amount_in, quote.amount_out, ix_data.expected_amount, ix_data.slippage_bps)?;
+
+    // the whole input, fee included, stays in the pool
+    let reserve_in = pool_acc.token(token_in)?.reserve().checked_add(quote.amount_in).ok_or(SolanaCoreError::OverFlowDetected)?;
+    let reserve_out = pool_acc.token(token_out)?.reserve().checked_sub(quote.amount_out).ok_or(SolanaCoreError::MathError)?;
+
+    let (seeds, seeds_len) = pool_acc.pool_seeds()?;
+    let signer_seeds = seeds.map(Seed::from);
+    let pool_signers = [Signer::from(&signer_seeds[..seeds_len])];
+
+    //transfer user -> vault_in
+    let _ = TransferChecked {
+        from: user_in_ata,
+        mint: mint_in,
+        to: vault_in,
+        authority: signer,
+        amount: quote.amount_in,
+        decimals: 9,
+    }.invoke();
+
+    //vault_out -> user
+    let _ = TransferChecked {
+        from: vault_out,
+        mint: mint_out,
+        to: user_out_ata,
+        authority: pool,
+        amount: quote.amount_out,
+        decimals: 9,
+    }.invoke_signed(&pool_signers);
+
+    pool_acc.token_mut(token_in)?.set_reserve(reserve_in);
+    pool_acc.token_mut(token_out)?.set_reserve(reserve_out);
+
+    emit(&MultiSwapEvent {
+        pool: *pool.key(),
+        user: *signer.key(),
+        token_in: ix_data.token_in,
+        token_out: ix_data.token_out,
+        amount_in: quote.amount_in,
+        amount_out: quote.amount_out,
+        fee: quote.fee,
+        reserve_in_after: reserve_in,
+        reserve_out_after: reserve_out,
+    })?;
+
+    Ok(())
+}
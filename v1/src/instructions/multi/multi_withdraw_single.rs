@@ -0,0 +1,79 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+};
+use pinocchio_token::instructions::{TransferChecked, BurnChecked};
+
+use crate::{
+    errors::SolanaCoreError,
+    events::{emit, MultiWithdrawEvent},
+    instructions::check_lp_mint,
+    utils::{
+        checks::load_ix_data,
+        get_mint_supply,
+    },
+    states::{
+        MultiPool, MultiSingleLiquidity, MAX_MULTI_POOL_TOKENS,
+    },
+};
+
+// Burns `amount` LP tokens for at least `limit` of a single pool token.
+pub fn multi_withdraw_single(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [signer, pool, lp_mint, lp_user_ata, user_ata, mint, vault_ata, _token_program] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer() {
+        return Err(SolanaCoreError::SignerRequired);
+    }
+
+    let ix_data: MultiSingleLiquidity = load_ix_data::<MultiSingleLiquidity>(data)?;
+    let pool_acc: &mut MultiPool = unsafe { MultiPool::load_mut(pool) }?;
+    let index = ix_data.token as usize;
+    check_lp_mint(pool_acc, lp_mint)?;
+    pool_acc.check_token(index, mint, vault_ata)?;
+
+    let lp_supply = get_mint_supply(lp_mint)?;
+    let (amount_out, _fee) = pool_acc.basket()?.withdraw_single(index, ix_data.amount, lp_supply)?;
+    if amount_out < ix_data.limit {
+        return Err(SolanaCoreError::Slippage);
+    }
+    let reserve = pool_acc.token(index)?.reserve().checked_sub(amount_out).ok_or(SolanaCoreError::MathError)?;
+
+    let _ = BurnChecked {
+        account: lp_user_ata,
+        mint: lp_mint,
+        authority: signer,
+        amount: ix_data.amount,
+        decimals: 9,
+    }.invoke();
+
+    let (seeds, seeds_len) = pool_acc.pool_seeds()?;
+    let signer_seeds = seeds.map(Seed::from);
+    let pool_signers = [Signer::from(&signer_seeds[..seeds_len])];
+
+    let _ = TransferChecked {
+        from: vault_ata,
+        mint,
+        to: user_ata,
+        authority: pool,
+        amount: amount_out,
+        decimals: 9,
+    }.invoke_signed(&pool_signers);
+
+    pool_acc.token_mut(index)?.set_reserve(reserve);
+
+    let mut amounts = [0; MAX_MULTI_POOL_TOKENS];
+    amounts[index] = amount_out;
+    emit(&MultiWithdrawEvent {
+        pool: *pool.key(),
+        user: *signer.key(),
+        token_count: pool_acc.token_count,
+        amounts,
+        lp_amount: ix_data.amount,
+        reserves_after: pool_acc.reserves(),
+        lp_supply_after: lp_supply - ix_data.amount,
+    })?;
+
+    Ok(())
+}
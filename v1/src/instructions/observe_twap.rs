@@ -0,0 +1,47 @@
+use pinocchio::{account_info::AccountInfo, program::set_return_data};
+
+use crate::{
+    errors::SolanaCoreError,
+    utils::{
+        checks::load_ix_data,
+        average_liquidity, current_timestamp, twap,
+    },
+    states::{
+        Observations, ObserveTwap, Pool,
+    },
+};
+
+// Return data: price_0 TWAP (Q64.64) | price_1 TWAP (Q64.64) | average liquidity,
+// each a little-endian u128.
+pub const OBSERVE_TWAP_RETURN_DATA_LEN: usize = 16 * 3;
+
+// Read-only: returns the TWAP over the last `window_seconds` via `set_return_data`,
+// so other programs can CPI into the AMM for a manipulation-resistant price.
+pub fn observe_twap(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [pool, observations] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    let ix_data: ObserveTwap = load_ix_data::<ObserveTwap>(data)?;
+    if ix_data.window_seconds == 0 || ix_data.window_seconds > i64::MAX as u64 {
+        return Err(SolanaCoreError::InvalidInstructionData);
+    }
+
+    let pool_acc: &Pool = Pool::load(pool)?;
+    let observations = Observations::load(observations, pool.key())?;
+
+    let now = current_timestamp()?;
+    let end = pool_acc.observe(now);
+    let start = observations.observe_at(now - ix_data.window_seconds as i64, &end)?;
+
+    let (price_0, price_1) = twap(&start, &end)?;
+    let liquidity = average_liquidity(&start, &end)?;
+
+    let mut return_data = [0u8; OBSERVE_TWAP_RETURN_DATA_LEN];
+    return_data[..16].copy_from_slice(&price_0.to_le_bytes());
+    return_data[16..32].copy_from_slice(&price_1.to_le_bytes());
+    return_data[32..].copy_from_slice(&liquidity.to_le_bytes());
+    set_return_data(&return_data);
+
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use pinocchio::{
+    account_info::AccountInfo,
+    instruction::{Seed, Signer},
+};
+use pinocchio_token::instructions::TransferChecked;
+
+use crate::{
+    errors::SolanaCoreError,
+    events::{emit, SwapEvent},
+    utils::{
+        checks::load_ix_data,
+        current_timestamp, get_mint_decimals, quote_swap, SwapMode, MAX_ROUTE_LEGS,
+    },
+    states::{
+        Pool, RouteSwap,
+    },
+};
+
+// Accounts per leg: pool, mint_out, vault_in, vault_out.
+const ROUTE_LEG_ACCOUNTS: usize = 4;
+
+// Exact-in swap through up to `MAX_ROUTE_LEGS` pools. Each leg's output goes straight
+// from its vault_out to the next pool's vault_in, so no intermediate user accounts are
+// needed; only the final output is checked against `minimum_amount_out`. A leg's
+// vault_in is validated when that leg runs, after the previous leg paid into it, which
+// the transaction's atomicity makes safe.
+pub fn route_swap(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
+    let [signer, user_source_ata, user_destination_ata, source_mint, _token_program, legs @ ..] = accounts else {
+        return Err(SolanaCoreError::NotEnoughAccountKeys);
+    };
+
+    if !signer.is_signer() {
+        return Err(SolanaCoreError::SignerRequired);
+    }
+
+    let ix_data: RouteSwap = load_ix_data::<RouteSwap>(data)?;
+    let leg_count = legs.len() / ROUTE_LEG_ACCOUNTS;
+    if legs.len() % ROUTE_LEG_ACCOUNTS != 0 || !(1..=MAX_ROUTE_LEGS).contains(&leg_count) {
+        return Err(SolanaCoreError::InvalidRoute);
+    }
+
+    let now = current_timestamp()?;
+    let mut amount = ix_data.amount_in;
+    let mut mint_in = source_mint;
+
+    for (index, leg) in legs.chunks_exact(ROUTE_LEG_ACCOUNTS).enumerate() {
+        let [pool, mint_out, vault_in, vault_out] = leg else {
+            return Err(SolanaCoreError::NotEnoughAccountKeys);
+        };
+        let pool_acc: &mut Pool = unsafe { Pool::load_mut(pool) }?;
+        pool_acc.check_unlocked()?;
+        let direction = pool_acc.direction(mint_in.key(), mint_out.key())?;
+        let (pool_vault_in, pool_vault_out) = pool_acc.vaults(direction);
+        if vault_in.key() != pool_vault_in || vault_out.key() != pool_vault_out {
+            return Err(SolanaCoreError::InvalidAccountData);
+        }
+        pool_acc.update_price_accumulators(now);
+
+        let quote = quote_swap(pool_acc, amount, direction, SwapMode::ExactIn, now)?;
+
+        if index == 0 {
+            //transfer user -> vault_in
+            TransferChecked {
+                from: user_source_ata,
+                mint: mint_in,
+                to: vault_in,
+                authority: signer,
+                amount: quote.amount_in,
+                decimals: get_mint_decimals(mint_in)?,
+            }.invoke().map_err(|_| SolanaCoreError::TokenTransferFailed)?;
+        }
+
+        // the next pool's vault_in, or the user on the last leg
+        let destination = match legs.get((index + 1) * ROUTE_LEG_ACCOUNTS + 2) {
+            Some(next_vault_in) => next_vault_in,
+            None => user_destination_ata,
+        };
+
+        let pda_bump_bytes = [pool_acc.pool_bump];
+        let fees_bps_bytes = pool_acc.fees_bps;
+        let signer_seeds = [
+            Seed::from(Pool::POOL_SEED.as_bytes()),
+            Seed::from(pool_acc.token_0_mint.as_ref()),
+            Seed::from(pool_acc.token_1_mint.as_ref()),
+            Seed::from(&fees_bps_bytes),
+            Seed::from(&pda_bump_bytes[..]),
+        ];
+        let pool_signers = [Signer::from(&signer_seeds[..])];
+
+        //vault_out -> next vault_in / user
+        TransferChecked {
+            from: vault_out,
+            mint: mint_out,
+            to: destination,
+            authority: pool,
+            amount: quote.amount_out,
+            decimals: get_mint_decimals(mint_out)?,
+        }.invoke_signed(&pool_signers).map_err(|_| SolanaCoreError::TokenTransferFailed)?;
+
+        pool_acc.set_token_0_amount(quote.reserve_0_after);
+        pool_acc.set_token_1_amount(quote.reserve_1_after);
+        pool_acc.record_volatility(now, quote.price_impact_bps);
+
+        emit(&SwapEvent {
+            pool: *pool.key(),
+            user: *signer.key(),
+            direction: direction as u8,
+            amount_in: quote.amount_in,
+            amount_out: quote.amount_out,
+            fee: quote.fee,
+            reserve_0_after: quote.reserve_0_after,
+            reserve_1_after: quote.reserve_1_after,
+            fees_bps: Some(quote.fees_bps),
+        })?;
+
+        amount = quote.amount_out;
+        mint_in = mint_out;
+    }
+
+    if amount < ix_data.minimum_amount_out {
+        return Err(SolanaCoreError::Slippage);
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use pinocchio::{
     account_info::AccountInfo,
-    instruction::{Seed, Signer},
+    cpi::slice_invoke,
+    instruction::{AccountMeta, Instruction, Seed, Signer},
     pubkey::Pubkey,
     sysvars::rent::Rent,
     ProgramResult,
@@ -13,63 +14,266 @@ use pinocchio_associated_token_account::instructions::Create;
 
 use crate::{
     errors::SolanaCoreError,
+    events::{emit, ReferralFeeEvent, SwapEvent},
     utils::{
         checks::{load_ix_data, DataLen},
-        load_acc_mut_unchecked,
-        validate_pda, calculate_token_out
+        validate_pda, current_timestamp, get_mint_decimals, get_token_balance, get_token_mint_and_owner, quote_swap, referral_fee,
+        ByteWriter, InstructionData, SwapDirection,
     },
     states::{
-        Swap, Pool, InitPool
+        Swap, Pool, InitPool, Observations, Config, Referrer, SwapReferral,
     }
 };
 
+// Flash swaps: callback data is capped so the CPI payload fits a stack buffer.
+pub const MAX_FLASH_SWAP_CALLBACK_DATA_LEN: usize = 512;
+pub const MAX_FLASH_SWAP_CALLBACK_ACCOUNTS: usize = 16;
+
+// Passing a callback program after the observations slot (`remaining = [observations,
+// callback_program, callback_accounts @ ..]`) makes this a flash swap: the output is sent
+// first, then the callback is invoked with `amount_in | amount_out | callback_data`, where
+// `callback_data` is whatever follows the `Swap` payload, and must pay `amount_in` into
+// vault_in. The quote already satisfies the fee-adjusted invariant, so checking the
+// vault_in balance grew by `amount_in` during the callback is enough.
+//
+// Passing referral accounts there instead (`remaining = [observations, config,
+// referrer_token_account, referrer_stats?]`) with a `SwapReferral` after the payload pays
+// the referrer its share of the fee, capped by the config, straight from the user in the
+// input mint. That share never reaches the vault, so it comes off the LPs' part of the fee.
+// Flash swaps cannot be referred.
 pub fn swap(accounts: &[AccountInfo], data: &[u8]) -> Result<(), SolanaCoreError> {
-    let [signer, pool, token_0_ata, token_1_ata, token_0_mint, token_1_mint, vault_0_ata ,vault_1_ata, system_program, token_program] = accounts else {
+    let [signer, pool, token_0_ata, token_1_ata, token_0_mint, token_1_mint, vault_0_ata ,vault_1_ata, system_program, token_program, remaining @ ..] = accounts else {
         return Err(SolanaCoreError::NotEnoughAccountKeys.into());
     };
-    let pool_acc: &mut Pool = unsafe { load_acc_mut_unchecked::<Pool>(pool.borrow_mut_data_unchecked())}.unwrap();  
+    let pool_acc: &mut Pool = unsafe { Pool::load_mut(pool) }?;
+    pool_acc.check_unlocked()?;
+    // accrue the TWAP accumulators at the pre-trade price
+    let now = current_timestamp()?;
+    pool_acc.update_price_accumulators(now);
+
+    // optional observations account: record the pre-trade accumulators
+    if let Some(observations) = remaining.first() {
+        if !observations.data_is_empty() {
+            unsafe { Observations::load_mut(observations, pool.key()) }?.write(&pool_acc.observe(now));
+        }
+    }
     let pda_bump_bytes = [pool_acc.pool_bump];
+    let fees_bps_bytes = pool_acc.fees_bps;
     let signer_seeds = [
         Seed::from(Pool::POOL_SEED.as_bytes()),
         Seed::from(pool_acc.token_0_mint.as_ref()),
         Seed::from(pool_acc.token_1_mint.as_ref()),
+        Seed::from(&fees_bps_bytes),
         Seed::from(&pda_bump_bytes[..]),
     ];
 
     let pool_signers = [Signer::from(&signer_seeds[..])];
-    let signer_seed_slices: Vec<&[u8]> = signer_seeds.iter().map(|s| s.as_ref()).collect();
-    validate_pda(&signer_seed_slices, pool.key());
+    let signer_seed_slices: [&[u8]; 5] = signer_seeds.each_ref().map(|s| s.as_ref());
+    validate_pda(&signer_seed_slices, pool.key())?;
 
-    let ix_data: &Swap = unsafe { load_ix_data::<Swap>(data).unwrap()};
+    let (payload, callback_data) = data.split_at(data.len().min(Swap::LEN));
+    let ix_data: Swap = load_ix_data::<Swap>(payload)?;
+    let flash_callback = remaining.get(1).filter(|account| account.executable());
+    let referral_accounts = match flash_callback {
+        Some(_) => &[][..],
+        None => remaining.get(1..).unwrap_or_default(),
+    };
+    let fee_share_bps = if !referral_accounts.is_empty() {
+        load_ix_data::<SwapReferral>(callback_data)?.fee_share_bps
+    } else if flash_callback.is_none() && !callback_data.is_empty() {
+        return Err(SolanaCoreError::InvalidInstructionData);
+    } else {
+        0
+    };
 
     if !signer.is_signer() {
         return Err(SolanaCoreError::SignerRequired.into())
     }
-    let fees_token_0 = ix_data.token_0_amount * (pool_acc.fees_bps / 100);
-
-    //transfer token_0 -> vault
-    let _ = TransferChecked {
-        from: token_0_ata,
-        mint: token_0_mint,
-        to: vault_0_ata,
-        authority: signer,
-        amount: ix_data.token_0_amount,
-        decimals: 9,
-    }.invoke();
-    //Slippage_bps feature also 
-    let token_1_out = calculate_token_out(ix_data.token_0_amount - (ix_data.token_0_amount * (pool_acc.fees_bps/100)), ix_data.expected_token_1_amount, ix_data.slippage_bps, pool_acc.token_0_amount, pool_acc.token_1_amount).unwrap(); 
-    //vault_1 to token_1
-    let _ = TransferChecked {
-        from: vault_1_ata,
-        mint: token_1_mint,
-        to: token_1_ata,
-        authority: pool,
-        amount: token_1_out,
-        decimals: 9,
-    }.invoke_signed(&pool_signers);
-
-    //update pool_acc
-    pool_acc.token_0_amount += ix_data.token_0_amount;
-    pool_acc.token_1_amount -= token_1_out;
+
+    // Same quote an off-chain client gets from `quote_swap` for this pool state.
+    let quote = quote_swap(pool_acc, ix_data.amount, ix_data.direction, ix_data.mode, now)?;
+    quote.check_slippage(ix_data.mode, ix_data.expected_amount, ix_data.slippage_bps)?;
+
+    let (user_in, user_out, mint_in, mint_out, vault_in, vault_out) = match ix_data.direction {
+        SwapDirection::ZeroForOne => (token_0_ata, token_1_ata, token_0_mint, token_1_mint, vault_0_ata, vault_1_ata),
+        SwapDirection::OneForZero => (token_1_ata, token_0_ata, token_1_mint, token_0_mint, vault_1_ata, vault_0_ata),
+    };
+    // the mints and vaults must be this pool's, or vault_in could be any account the user controls
+    if pool_acc.direction(mint_in.key(), mint_out.key())? != ix_data.direction {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+    let (pool_vault_in, pool_vault_out) = pool_acc.vaults(ix_data.direction);
+    if vault_in.key() != pool_vault_in || vault_out.key() != pool_vault_out {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+    let referral = check_referral(referral_accounts, fee_share_bps, quote.amount_in, quote.fee, mint_in)?;
+    let referral_amount = referral.map_or(0, |(_, _, amount)| amount);
+
+    if let Some(callback_program) = flash_callback {
+        //vault_out -> user, before the input is paid
+        let _ = TransferChecked {
+            from: vault_out,
+            mint: mint_out,
+            to: user_out,
+            authority: pool,
+            amount: quote.amount_out,
+            decimals: 9,
+        }.invoke_signed(&pool_signers);
+
+        let balance_before = get_token_balance(vault_in)?;
+        // the callback cannot trade against or withdraw from this pool meanwhile
+        pool_acc.start_flash_loan(0, 0)?;
+        invoke_flash_swap_callback(callback_program, &remaining[2..], quote.amount_in, quote.amount_out, callback_data)?;
+        pool_acc.finish_flash_loan()?;
+
+        let paid = get_token_balance(vault_in)?.saturating_sub(balance_before);
+        if paid < quote.amount_in {
+            return Err(SolanaCoreError::FlashSwapNotPaid);
+        }
+    } else {
+        let decimals_in = get_mint_decimals(mint_in)?;
+
+        //transfer user -> vault_in, less the referral fee
+        TransferChecked {
+            from: user_in,
+            mint: mint_in,
+            to: vault_in,
+            authority: signer,
+            amount: quote.amount_in - referral_amount,
+            decimals: decimals_in,
+        }.invoke().map_err(|_| SolanaCoreError::TokenTransferFailed)?;
+
+        //user -> referrer
+        if let Some((referrer_token_account, _, amount)) = referral {
+            TransferChecked {
+                from: user_in,
+                mint: mint_in,
+                to: referrer_token_account,
+                authority: signer,
+                amount,
+                decimals: decimals_in,
+            }.invoke().map_err(|_| SolanaCoreError::TokenTransferFailed)?;
+        }
+
+        //vault_out -> user
+        TransferChecked {
+            from: vault_out,
+            mint: mint_out,
+            to: user_out,
+            authority: pool,
+            amount: quote.amount_out,
+            decimals: get_mint_decimals(mint_out)?,
+        }.invoke_signed(&pool_signers).map_err(|_| SolanaCoreError::TokenTransferFailed)?;
+    }
+
+    //update pool_acc, the referral fee having gone to the referrer instead of vault_in
+    let (reserve_0_after, reserve_1_after) = match ix_data.direction {
+        SwapDirection::ZeroForOne => (quote.reserve_0_after - referral_amount, quote.reserve_1_after),
+        SwapDirection::OneForZero => (quote.reserve_0_after, quote.reserve_1_after - referral_amount),
+    };
+    pool_acc.set_token_0_amount(reserve_0_after);
+    pool_acc.set_token_1_amount(reserve_1_after);
+    pool_acc.record_volatility(now, quote.price_impact_bps);
+
+    emit(&SwapEvent {
+        pool: *pool.key(),
+        user: *signer.key(),
+        direction: ix_data.direction as u8,
+        amount_in: quote.amount_in,
+        amount_out: quote.amount_out,
+        fee: quote.fee,
+        reserve_0_after,
+        reserve_1_after,
+        fees_bps: Some(quote.fees_bps),
+    })?;
+    if let Some((_, referrer, amount)) = referral {
+        emit(&ReferralFeeEvent {
+            pool: *pool.key(),
+            user: *signer.key(),
+            referrer,
+            mint: *mint_in.key(),
+            amount_in: quote.amount_in,
+            referral_fee: amount,
+        })?;
+    }
     Ok(())
 }
+
+// Validates the referral accounts of a referred swap and records it in the referrer's
+// stats when passed. Returns the referrer's token account, its owner and the referral fee.
+fn check_referral<'a>(
+    referral_accounts: &'a [AccountInfo],
+    fee_share_bps: u64,
+    amount_in: u64,
+    fee: u64,
+    mint_in: &AccountInfo,
+) -> Result<Option<(&'a AccountInfo, Pubkey, u64)>, SolanaCoreError> {
+    let [config, referrer_token_account, referrer_stats @ ..] = referral_accounts else {
+        return match referral_accounts {
+            [] => Ok(None),
+            _ => Err(SolanaCoreError::NotEnoughAccountKeys),
+        };
+    };
+    let fee_share_bps = fee_share_bps.min(Config::load(config)?.max_referral_fee_share_bps());
+
+    let (mint, referrer) = get_token_mint_and_owner(referrer_token_account)?;
+    if mint != *mint_in.key() {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+
+    let amount = referral_fee(fee, fee_share_bps);
+    if let Some(referrer_stats) = referrer_stats.first() {
+        unsafe { Referrer::load_mut(referrer_stats, &referrer, &mint) }?.record(amount_in, amount);
+    }
+    Ok(Some((referrer_token_account, referrer, amount)))
+}
+
+/// Writes the flash swap callback payload `amount_in | amount_out | callback_data` into
+/// `buffer`, returning the written part.
+pub fn encode_flash_swap_callback<'a>(
+    buffer: &'a mut [u8],
+    amount_in: u64,
+    amount_out: u64,
+    callback_data: &[u8],
+) -> Result<&'a [u8], SolanaCoreError> {
+    if callback_data.len() > MAX_FLASH_SWAP_CALLBACK_DATA_LEN {
+        return Err(SolanaCoreError::InvalidInstructionData);
+    }
+    let len = 8 + 8 + callback_data.len();
+    let mut writer = ByteWriter::new(buffer.get_mut(..len).ok_or(SolanaCoreError::InvalidInstructionData)?);
+    writer.write_u64(amount_in)?;
+    writer.write_u64(amount_out)?;
+    writer.write_bytes(callback_data)?;
+    Ok(&buffer[..len])
+}
+
+// Invokes `callback_program` with `callback_accounts`, keeping their signer and writable flags.
+fn invoke_flash_swap_callback(
+    callback_program: &AccountInfo,
+    callback_accounts: &[AccountInfo],
+    amount_in: u64,
+    amount_out: u64,
+    callback_data: &[u8],
+) -> Result<(), SolanaCoreError> {
+    if callback_accounts.len() > MAX_FLASH_SWAP_CALLBACK_ACCOUNTS {
+        return Err(SolanaCoreError::InvalidInstructionData);
+    }
+    let mut buffer = [0u8; 8 + 8 + MAX_FLASH_SWAP_CALLBACK_DATA_LEN];
+    let data = encode_flash_swap_callback(&mut buffer, amount_in, amount_out, callback_data)?;
+
+    // unused slots repeat the program account and are cut off below
+    let account_infos: [&AccountInfo; MAX_FLASH_SWAP_CALLBACK_ACCOUNTS] =
+        core::array::from_fn(|index| callback_accounts.get(index).unwrap_or(callback_program));
+    let account_metas: [AccountMeta; MAX_FLASH_SWAP_CALLBACK_ACCOUNTS] = core::array::from_fn(|index| {
+        let account = account_infos[index];
+        AccountMeta::new(account.key(), account.is_writable(), account.is_signer())
+    });
+    let count = callback_accounts.len();
+
+    let instruction = Instruction {
+        program_id: callback_program.key(),
+        accounts: &account_metas[..count],
+        data,
+    };
+    slice_invoke(&instruction, &account_infos[..count]).map_err(|_| SolanaCoreError::FlashSwapNotPaid)
+}
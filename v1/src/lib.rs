@@ -1,7 +1,10 @@
 #[cfg(not(feature = "no-entrypoint"))]
 mod entrypoint;
 
+#[cfg(feature = "cpi")]
+pub mod cpi;
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod states;
 pub mod utils;
@@ -25,7 +28,7 @@ mod tests {
     use alloc::vec;
 
     use crate::states::InitPool;
-    use crate::utils::to_bytes;
+    use crate::utils::InstructionData;
     use crate::errors::SolanaCoreError;
 
     pub const PROGRAM: Pubkey = pubkey!("F3djNpWTDPFvum35roNrrH1u7PtXCioD9N6KApWcgVi3");
@@ -37,11 +40,10 @@ mod tests {
     pub const TOKEN_0_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
     pub const TOKEN_1_MINT: Pubkey = pubkey!("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v");
 
-    // Instruction discriminators
-    const INIT_POOL_DISCRIMINATOR: u8 = 0;
-    const LIQUIDATE_POOL_DISCRIMINATOR: u8 = 1;
-    const DELIQUIDATE_POOL_DISCRIMINATOR: u8 = 2;
-    const SWAP_DISCRIMINATOR: u8 = 3;
+    use crate::instructions::{
+        INIT_POOL_DISCRIMINATOR, LIQUIDATE_POOL_DISCRIMINATOR, DELIQUIDATE_POOL_DISCRIMINATOR,
+        SWAP_DISCRIMINATOR,
+    };
 
     fn mollusk() -> Mollusk {
         Mollusk::new(&PROGRAM, "target/deploy/v1")
@@ -54,6 +56,22 @@ mod tests {
         }
     }
 
+    // A pinocchio `AccountInfo` over a zeroed `Account` header holding `key`; the CPI
+    // helpers only read the key to build their account metas.
+    #[cfg(feature = "cpi")]
+    fn account_info(header: &mut [u64; 11], key: &Pubkey) -> pinocchio::account_info::AccountInfo {
+        // borrow_state, is_signer, is_writable, executable and resize_delta precede the key
+        let bytes = unsafe { core::slice::from_raw_parts_mut(header.as_mut_ptr() as *mut u8, 88) };
+        bytes[8..40].copy_from_slice(key.as_ref());
+        unsafe { core::mem::transmute::<*mut u64, pinocchio::account_info::AccountInfo>(header.as_mut_ptr()) }
+    }
+
+    // `(key, is_writable, is_signer)` of each meta, in order.
+    #[cfg(feature = "cpi")]
+    fn meta_layout(metas: &[pinocchio::instruction::AccountMeta]) -> Vec<([u8; 32], bool, bool)> {
+        metas.iter().map(|meta| (*meta.pubkey, meta.is_writable, meta.is_signer)).collect()
+    }
+
     #[test]
     fn test_entrypoint_routing() {
         println!("Testing Entrypoint Instruction Routing");
@@ -131,13 +149,1048 @@ mod tests {
             fees_bps: 30,
             lp_mint: Pubkey::new_unique().to_bytes(),
             lp_bump: 253,
+            curve_type: 0,
+            amp: 0,
+            weight_0_bps: 0,
         };
 
         let mut ser_ix_data = vec![INIT_POOL_DISCRIMINATOR];
-        ser_ix_data.extend_from_slice(unsafe { to_bytes(&ix_data) });
+        ser_ix_data.extend_from_slice(&ix_data.pack());
 
         println!(" InitPool instruction data serialized successfully!");
         println!(" Discriminator: {}", INIT_POOL_DISCRIMINATOR);
         println!(" Data length: {} bytes", ser_ix_data.len());
     }
+
+    #[test]
+    fn test_init_pool_binds_accounts_to_payload() {
+        let (vault_0, vault_1, lp_mint) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let ix_data = InitPool {
+            token_0_mint: TOKEN_0_MINT.to_bytes(),
+            token_1_mint: TOKEN_1_MINT.to_bytes(),
+            token_0_amount: 1_000_000,
+            token_1_amount: 2_000_000,
+            vault_0: vault_0.to_bytes(),
+            vault_1: vault_1.to_bytes(),
+            pool_bump: 254,
+            fees_bps: 30,
+            lp_mint: lp_mint.to_bytes(),
+            lp_bump: 253,
+            curve_type: 0,
+            amp: 0,
+            weight_0_bps: 0,
+        };
+        let keys = [TOKEN_0_MINT, TOKEN_1_MINT, vault_0, vault_1, lp_mint].map(|key| key.to_bytes());
+        let check = |keys: &[[u8; 32]; 5]| ix_data.check_accounts(&keys[0], &keys[1], &keys[2], &keys[3], &keys[4]);
+        assert!(check(&keys).is_ok());
+
+        // a vault other than the one stored in the pool is rejected, as are swapped ones
+        let attacker = Pubkey::new_unique().to_bytes();
+        for i in 0..keys.len() {
+            let mut wrong = keys;
+            wrong[i] = attacker;
+            assert_eq!(check(&wrong), Err(SolanaCoreError::InvalidAccountData));
+        }
+        let mut swapped = keys;
+        swapped.swap(2, 3);
+        assert_eq!(check(&swapped), Err(SolanaCoreError::InvalidAccountData));
+    }
+
+    #[test]
+    fn test_pool_discriminator_rejects_other_accounts() {
+        use crate::states::Pool;
+        use crate::utils::{load_acc_unchecked, AccountDiscriminator, DataLen};
+
+        let mut data = vec![0u8; Pool::LEN];
+        assert_eq!(
+            unsafe { load_acc_unchecked::<Pool>(&data) }.err(),
+            Some(SolanaCoreError::InvalidAccountDiscriminator)
+        );
+
+        data[..8].copy_from_slice(&Pool::DISCRIMINATOR);
+        assert!(unsafe { load_acc_unchecked::<Pool>(&data) }.is_ok());
+    }
+
+    #[test]
+    fn test_ix_data_roundtrip() {
+        use crate::states::Swap;
+        use crate::utils::{load_ix_data, SwapDirection, SwapMode};
+
+        let swap = Swap {
+            amount: 1_000,
+            expected_amount: 1_990,
+            slippage_bps: 50,
+            direction: SwapDirection::ZeroForOne,
+            mode: SwapMode::ExactIn,
+        };
+        let data = swap.pack();
+        assert_eq!(data.len(), Swap::LEN);
+        assert_eq!(load_ix_data::<Swap>(&data), Ok(swap));
+
+        // Decoding must not depend on the alignment of the input slice.
+        let mut shifted = vec![0u8];
+        shifted.extend_from_slice(&data);
+        assert_eq!(load_ix_data::<Swap>(&shifted[1..]), Ok(swap));
+
+        assert_eq!(
+            load_ix_data::<Swap>(&data[..Swap::LEN - 1]),
+            Err(SolanaCoreError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_quote_exact_in_and_out_agree() {
+        use crate::utils::{quote_exact_in, quote_exact_out, SwapDirection};
+
+        let (reserve_0, reserve_1) = (1_000_000u64, 2_000_000u64);
+
+        let exact_in = quote_exact_in(reserve_0, reserve_1, 30, 10_000, SwapDirection::ZeroForOne).unwrap();
+        assert_eq!(exact_in.reserve_0_after, reserve_0 + exact_in.amount_in);
+        assert_eq!(exact_in.reserve_1_after, reserve_1 - exact_in.amount_out);
+        // k never decreases
+        assert!(
+            exact_in.reserve_0_after as u128 * exact_in.reserve_1_after as u128
+                >= reserve_0 as u128 * reserve_1 as u128
+        );
+
+        let exact_out =
+            quote_exact_out(reserve_0, reserve_1, 30, exact_in.amount_out, SwapDirection::ZeroForOne).unwrap();
+        assert!(exact_out.amount_in <= exact_in.amount_in);
+        assert_eq!(exact_out.amount_out, exact_in.amount_out);
+
+        let reverse = quote_exact_in(reserve_0, reserve_1, 30, 10_000, SwapDirection::OneForZero).unwrap();
+        assert_eq!(reverse.reserve_1_after, reserve_1 + reverse.amount_in);
+        assert_eq!(reverse.reserve_0_after, reserve_0 - reverse.amount_out);
+    }
+
+    #[test]
+    fn test_twap_between_observations() {
+        use crate::states::Pool;
+        use crate::utils::{price_q64, twap};
+
+        // all-bytes struct, zero is a valid value
+        let mut pool: Pool = unsafe { core::mem::zeroed() };
+        pool.set_token_0_amount(1_000);
+        pool.set_token_1_amount(2_000);
+        pool.set_last_update_timestamp(100);
+
+        let start = pool.observe(100);
+        // price 2.0 for 60s, then 4.0 for 20s
+        pool.update_price_accumulators(160);
+        pool.set_token_1_amount(4_000);
+        let end = pool.observe(180);
+
+        let (price_0, price_1) = twap(&start, &end).unwrap();
+        assert_eq!(price_0, (price_q64(2, 1) * 60 + price_q64(4, 1) * 20) / 80);
+        assert_eq!(price_1, (price_q64(1, 2) * 60 + price_q64(1, 4) * 20) / 80);
+        assert!(twap(&end, &start).is_err());
+    }
+
+    #[test]
+    fn test_stable_swap_quotes() {
+        use crate::states::Pool;
+        use crate::utils::{quote_exact_in, quote_swap, CurveType, SwapDirection, SwapMode};
+
+        // all-bytes struct, zero is a valid value
+        let mut pool: Pool = unsafe { core::mem::zeroed() };
+        pool.set_token_0_amount(1_000_000);
+        pool.set_token_1_amount(1_000_000);
+        pool.curve_type = CurveType::StableSwap as u8;
+        pool.amp_target = 100u64.to_le_bytes();
+
+        // a pegged pair trades close to 1:1, far better than on x * y = k
+        let stable = quote_swap(&pool, 10_000, SwapDirection::ZeroForOne, SwapMode::ExactIn, 0).unwrap();
+        let constant_product = quote_exact_in(1_000_000, 1_000_000, 0, 10_000, SwapDirection::ZeroForOne).unwrap();
+        assert!(stable.amount_out > constant_product.amount_out);
+        assert!(stable.amount_out < 10_000);
+        assert!(stable.price_impact_bps < constant_product.price_impact_bps);
+
+        let exact_out = quote_swap(&pool, stable.amount_out, SwapDirection::ZeroForOne, SwapMode::ExactOut, 0).unwrap();
+        assert!(exact_out.amount_in <= stable.amount_in + 1);
+
+        // A ramps linearly from 100 to 1_000 between t=1_000 and t=2_000
+        pool.set_amp_ramp(1_000, 1_000, 2_000);
+        assert_eq!(pool.amp(1_000), 100);
+        assert_eq!(pool.amp(1_500), 550);
+        assert_eq!(pool.amp(5_000), 1_000);
+    }
+
+    #[test]
+    fn test_weighted_pool_quotes() {
+        use crate::states::Pool;
+        use crate::utils::{quote_exact_in, quote_swap, CurveType, SwapDirection, SwapMode};
+
+        let mut pool: Pool = unsafe { core::mem::zeroed() };
+        pool.set_token_0_amount(1_000_000);
+        pool.set_token_1_amount(2_000_000);
+        pool.curve_type = CurveType::Weighted as u8;
+
+        // 50/50 is x * y = k
+        pool.weight_0_bps = 5_000u64.to_le_bytes();
+        let weighted = quote_swap(&pool, 10_000, SwapDirection::ZeroForOne, SwapMode::ExactIn, 0).unwrap();
+        let constant_product = quote_exact_in(1_000_000, 2_000_000, 0, 10_000, SwapDirection::ZeroForOne).unwrap();
+        assert!(weighted.amount_out <= constant_product.amount_out);
+        assert!(constant_product.amount_out - weighted.amount_out <= 1);
+
+        // 80/20: token_0 is priced at (2_000_000 / 20) / (1_000_000 / 80) = 8 token_1
+        pool.weight_0_bps = 8_000u64.to_le_bytes();
+        assert_eq!(pool.spot_prices(0).0, 8 << 64);
+        let exact_in = quote_swap(&pool, 1_000, SwapDirection::ZeroForOne, SwapMode::ExactIn, 0).unwrap();
+        assert!(exact_in.amount_out < 8_000 && exact_in.amount_out > 7_950);
+        let exact_out =
+            quote_swap(&pool, exact_in.amount_out, SwapDirection::ZeroForOne, SwapMode::ExactOut, 0).unwrap();
+        assert!(exact_out.amount_in <= exact_in.amount_in + 1);
+
+        // a single trade may not move more than 30 % of a reserve
+        assert!(quote_swap(&pool, 400_000, SwapDirection::ZeroForOne, SwapMode::ExactIn, 0).is_err());
+    }
+
+    #[test]
+    fn test_curves_quote_consistently() {
+        use crate::utils::{
+            quote_curve_exact_in, quote_curve_exact_out, ConstantProduct, ConstantSum, Curve, PoolCurve, StableSwap,
+            SwapDirection, Weighted,
+        };
+
+        let (reserve_0, reserve_1) = (1_000_000, 1_200_000);
+        let curves = [
+            PoolCurve::ConstantProduct(ConstantProduct),
+            PoolCurve::StableSwap(StableSwap { amp: 100 }),
+            PoolCurve::ConstantSum(ConstantSum),
+            PoolCurve::Weighted(Weighted { weight_0_bps: 5_000 }),
+        ];
+        for curve in curves {
+            for direction in [SwapDirection::ZeroForOne, SwapDirection::OneForZero] {
+                let exact_in = quote_curve_exact_in(&curve, reserve_0, reserve_1, 0, 10_000, direction).unwrap();
+                assert!(exact_in.amount_out > 0 && exact_in.amount_out <= 12_000, "{curve:?}");
+
+                // buying back the same output never costs less than was paid
+                let exact_out =
+                    quote_curve_exact_out(&curve, reserve_0, reserve_1, 0, exact_in.amount_out, direction).unwrap();
+                assert!(exact_out.amount_in <= exact_in.amount_in + 1, "{curve:?}");
+            }
+
+            // deposits and withdrawals are proportional on every curve
+            let lp_supply = curve.initial_lp(reserve_0, reserve_1).unwrap();
+            let (lp, amount_0, amount_1) = curve.deposit(10_000, 50_000, reserve_0, reserve_1, lp_supply).unwrap();
+            assert_eq!((amount_0, amount_1), (10_000, 12_000), "{curve:?}");
+            assert_eq!(lp, lp_supply / 100, "{curve:?}");
+        }
+
+        // a constant-sum swap never empties the output reserve
+        assert!(ConstantSum.swap_exact_in(reserve_1, reserve_0, reserve_1, SwapDirection::ZeroForOne).is_err());
+    }
+
+    #[test]
+    fn test_observations_ring_buffer() {
+        use crate::states::{Observations, ObservationsHeader};
+        use crate::utils::{DataLen, PriceObservation};
+
+        let observation = |timestamp: i64| PriceObservation {
+            timestamp,
+            price_0_cumulative: timestamp as u128 * 10,
+            price_1_cumulative: timestamp as u128 * 20,
+            liquidity_cumulative: timestamp as u128 * 30,
+        };
+
+        // three slots, first observation at t=100
+        let mut data = vec![0u8; Observations::space(3)];
+        data[ObservationsHeader::LEN - 2..ObservationsHeader::LEN].copy_from_slice(&3u16.to_le_bytes());
+        let mut observations = Observations::from_bytes_mut(&mut data).unwrap();
+        observations.header.cardinality = 1u16.to_le_bytes();
+        observations.entries[0].set(&observation(100));
+
+        // the ring grows into the allocated slots, then overwrites the oldest one
+        for timestamp in [110, 110, 120, 130] {
+            observations.write(&observation(timestamp));
+        }
+        let observations = observations.view();
+        assert_eq!(observations.cardinality(), 3);
+        assert_eq!(observations.latest().timestamp, 130);
+
+        let current = observation(140);
+        assert_eq!(observations.observe_at(115, &current).unwrap(), observation(115));
+        assert_eq!(observations.observe_at(135, &current).unwrap(), observation(135));
+        assert_eq!(
+            observations.observe_at(105, &current),
+            Err(SolanaCoreError::ObservationTooOld)
+        );
+    }
+
+    #[test]
+    fn test_concentrated_liquidity_swap_crosses_ticks() {
+        use crate::states::{ClPool, Position, TickArray};
+        use crate::utils::{
+            modify_position, sqrt_price_at_tick, swap_through_ticks, tick_at_sqrt_price, SwapDirection, SwapMode,
+        };
+
+        assert_eq!(sqrt_price_at_tick(0).unwrap(), 1 << 64);
+        for tick in [-200, -101, -100, 0, 99, 100, 443_636] {
+            assert_eq!(tick_at_sqrt_price(sqrt_price_at_tick(tick).unwrap()).unwrap(), tick);
+        }
+
+        // all-bytes structs, zero is a valid value; price 1, tick spacing 10, 0.3 % fee
+        let mut pool: ClPool = unsafe { core::mem::zeroed() };
+        pool.fees_bps = 30u64.to_le_bytes();
+        pool.tick_spacing = 10u16.to_le_bytes();
+        pool.set_sqrt_price(1 << 64);
+        let mut array_0: TickArray = unsafe { core::mem::zeroed() };
+        let mut array_below: TickArray = unsafe { core::mem::zeroed() };
+        array_below.start_tick_index = (-640i32).to_le_bytes();
+        let position = |tick_lower: i32, tick_upper: i32| {
+            let mut position: Position = unsafe { core::mem::zeroed() };
+            position.tick_lower = tick_lower.to_le_bytes();
+            position.tick_upper = tick_upper.to_le_bytes();
+            position
+        };
+
+        // `a` is in range and needs both tokens, `b` sits below the price and only holds token_1
+        let mut a = position(-100, 100);
+        let (lower, upper) = (array_below.tick_mut(-100, 10).unwrap(), array_0.tick_mut(100, 10).unwrap());
+        let (a_0, a_1) = modify_position(&mut pool, &mut a, lower, upper, 1_000_000_000).unwrap();
+        let mut b = position(-200, -100);
+        let (lower, upper) = array_below.tick_pair_mut(-200, -100, 10).unwrap();
+        let (b_0, _) = modify_position(&mut pool, &mut b, lower, upper, 1_000_000_000).unwrap();
+        assert!(a_0 > 0 && a_1 > 0);
+        assert_eq!(b_0, 0);
+        assert_eq!(pool.liquidity(), 1_000_000_000);
+
+        // selling token_0 walks the price down through tick -100, handing over from `a` to `b`
+        let result = swap_through_ticks(
+            &mut pool,
+            &mut [&mut array_0, &mut array_below],
+            6_000_000,
+            SwapDirection::ZeroForOne,
+            SwapMode::ExactIn,
+        )
+        .unwrap();
+        assert!((-200..-100).contains(&pool.tick_current()));
+        assert_eq!(pool.liquidity(), 1_000_000_000);
+        assert!(result.amount_out < result.amount_in);
+        // rounded up once per step
+        assert!((18_000..=18_002).contains(&result.fee));
+
+        // arrays must start at the current tick's array
+        assert_eq!(
+            swap_through_ticks(&mut pool, &mut [&mut array_0], 1_000, SwapDirection::ZeroForOne, SwapMode::ExactIn),
+            Err(SolanaCoreError::InvalidTickArray)
+        );
+
+        // collecting credits each position with the fees earned while it was in range
+        let (lower, upper) = (array_below.tick_mut(-100, 10).unwrap(), array_0.tick_mut(100, 10).unwrap());
+        modify_position(&mut pool, &mut a, lower, upper, 0).unwrap();
+        let (lower, upper) = array_below.tick_pair_mut(-200, -100, 10).unwrap();
+        modify_position(&mut pool, &mut b, lower, upper, 0).unwrap();
+        let (owed_a, owed_b) = (a.tokens_owed().0, b.tokens_owed().0);
+        assert!(owed_a > owed_b && owed_b > 0);
+        assert!(owed_a + owed_b <= result.fee && result.fee - (owed_a + owed_b) <= 2);
+
+        // `a` ended up below the price: withdrawing it returns token_0 only, and frees its upper tick
+        let (lower, upper) = (array_below.tick_mut(-100, 10).unwrap(), array_0.tick_mut(100, 10).unwrap());
+        let (out_0, out_1) = modify_position(&mut pool, &mut a, lower, upper, -1_000_000_000).unwrap();
+        assert!(out_0 > a_0 && out_1 == 0);
+        assert!(!array_0.tick_mut(100, 10).unwrap().is_initialized());
+    }
+
+    #[test]
+    fn test_multi_pool_basket() {
+        use crate::utils::{compute_d, compute_d_multi, Basket, CurveType, SwapMode};
+
+        // the n-token invariant agrees with the two-token one
+        let d = compute_d(100, 1_000_000, 1_200_000).unwrap();
+        let d_multi = compute_d_multi(100, &[1_000_000, 1_200_000]).unwrap();
+        assert!(d.abs_diff(d_multi) <= 1);
+
+        let mut basket = Basket {
+            curve_type: CurveType::StableSwap,
+            amp: 100,
+            fees_bps: 0,
+            token_count: 3,
+            reserves: [1_000_000, 1_000_000, 1_000_000, 0, 0, 0, 0, 0],
+            weights_bps: [0; 8],
+        };
+        let lp_supply = basket.initial_lp().unwrap();
+        assert!(lp_supply.abs_diff(3_000_000) <= 1);
+
+        for curve_type in [CurveType::StableSwap, CurveType::Weighted] {
+            basket.curve_type = curve_type;
+            basket.weights_bps = [5_000, 2_500, 2_500, 0, 0, 0, 0, 0];
+            let exact_in = basket.quote_swap(0, 2, 10_000, SwapMode::ExactIn).unwrap();
+            let exact_out = basket.quote_swap(0, 2, exact_in.amount_out, SwapMode::ExactOut).unwrap();
+            assert!(exact_out.amount_in <= exact_in.amount_in + 1);
+
+            let lp_supply = basket.initial_lp().unwrap();
+            let amounts = basket.proportional_amounts(lp_supply / 100, lp_supply, true).unwrap();
+            let (lp, _) = basket.deposit_single(1, 30_000, lp_supply).unwrap();
+            let mut after = basket;
+            after.reserves[1] += 30_000;
+            let (out, _) = after.withdraw_single(1, lp, lp_supply + lp).unwrap();
+            assert!(out <= 30_000 && out > 29_900);
+            assert!(basket.quote_swap(1, 1, 1_000, SwapMode::ExactIn).is_err());
+        }
+        assert_eq!(basket.quote_swap(0, 3, 1_000, SwapMode::ExactIn), Err(SolanaCoreError::InvalidInstructionData));
+    }
+
+    #[test]
+    fn test_route_quote_chains_legs() {
+        use crate::states::Pool;
+        use crate::utils::{quote_route, quote_swap, SwapDirection, SwapMode, MAX_ROUTE_LEGS};
+
+        let (mint_a, mint_b, mint_c) = ([1u8; 32], [2u8; 32], [3u8; 32]);
+        // constant-product pools A/B and C/B, all-bytes structs so zero is a valid value
+        let mut pool_ab: Pool = unsafe { core::mem::zeroed() };
+        pool_ab.token_0_mint = mint_a;
+        pool_ab.token_1_mint = mint_b;
+        pool_ab.set_token_0_amount(1_000_000);
+        pool_ab.set_token_1_amount(2_000_000);
+        let mut pool_cb: Pool = unsafe { core::mem::zeroed() };
+        pool_cb.token_0_mint = mint_c;
+        pool_cb.token_1_mint = mint_b;
+        pool_cb.set_token_0_amount(3_000_000);
+        pool_cb.set_token_1_amount(1_500_000);
+
+        let first = pool_ab.direction(&mint_a, &mint_b).unwrap();
+        let second = pool_cb.direction(&mint_b, &mint_c).unwrap();
+        assert_eq!((first, second), (SwapDirection::ZeroForOne, SwapDirection::OneForZero));
+        assert_eq!(pool_ab.direction(&mint_a, &mint_c), Err(SolanaCoreError::InvalidAccountData));
+
+        // A -> C equals the two legs quoted one after the other
+        let leg_1 = quote_swap(&pool_ab, 10_000, first, SwapMode::ExactIn, 0).unwrap();
+        let leg_2 = quote_swap(&pool_cb, leg_1.amount_out, second, SwapMode::ExactIn, 0).unwrap();
+        let amount_out = quote_route(&[(&pool_ab, first), (&pool_cb, second)], 10_000, 0).unwrap();
+        assert_eq!(amount_out, leg_2.amount_out);
+        assert!(amount_out > 0 && amount_out < 20_000);
+
+        assert_eq!(quote_route(&[], 10_000, 0), Err(SolanaCoreError::InvalidRoute));
+        let too_long = [(&pool_ab, first); MAX_ROUTE_LEGS + 1];
+        assert_eq!(quote_route(&too_long, 10_000, 0), Err(SolanaCoreError::InvalidRoute));
+    }
+
+    #[test]
+    fn test_flash_loan_locks_pool() {
+        use crate::states::{FlashLoan, Pool};
+
+        let mut pool: Pool = unsafe { core::mem::zeroed() };
+        pool.set_token_0_amount(1_000_000);
+        pool.set_token_1_amount(2_000_000);
+
+        assert_eq!(pool.start_flash_loan(1_000_001, 0), Err(SolanaCoreError::InsufficientLiquidity));
+        assert_eq!(pool.finish_flash_loan(), Err(SolanaCoreError::InvalidInstructionData));
+
+        pool.start_flash_loan(500_000, 2_000_000).unwrap();
+        // swaps, liquidity changes and nested loans are rejected until repaid
+        assert_eq!(pool.check_unlocked(), Err(SolanaCoreError::PoolLocked));
+        assert_eq!(pool.start_flash_loan(1, 0), Err(SolanaCoreError::PoolLocked));
+
+        assert_eq!(pool.finish_flash_loan(), Ok((500_000, 2_000_000)));
+        assert!(pool.check_unlocked().is_ok());
+        assert_eq!(pool.finish_flash_loan(), Err(SolanaCoreError::InvalidInstructionData));
+
+        let payload = FlashLoan { amount_0: 500_000, amount_1: 0 };
+        assert_eq!(FlashLoan::unpack(&payload.pack()), Ok(payload));
+    }
+
+    #[test]
+    fn test_flash_swap_callback_payload() {
+        use crate::instructions::{encode_flash_swap_callback, MAX_FLASH_SWAP_CALLBACK_DATA_LEN};
+        use crate::utils::ByteReader;
+
+        let mut buffer = [0u8; 16 + MAX_FLASH_SWAP_CALLBACK_DATA_LEN];
+        let payload = encode_flash_swap_callback(&mut buffer, 10_030, 9_871, &[7, 8, 9]).unwrap();
+        assert_eq!(payload.len(), 16 + 3);
+        let mut reader = ByteReader::new(payload);
+        assert_eq!(reader.read_u64(), Ok(10_030));
+        assert_eq!(reader.read_u64(), Ok(9_871));
+        assert_eq!(reader.read_bytes::<3>(), Ok([7, 8, 9]));
+        assert!(reader.finish().is_ok());
+
+        let too_long = [0u8; MAX_FLASH_SWAP_CALLBACK_DATA_LEN + 1];
+        assert_eq!(
+            encode_flash_swap_callback(&mut buffer, 1, 1, &too_long),
+            Err(SolanaCoreError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn test_dynamic_fee_tracks_volatility() {
+        use crate::states::Pool;
+        use crate::utils::VOLATILITY_HALF_LIFE_SECONDS;
+
+        let mut pool: Pool = unsafe { core::mem::zeroed() };
+        pool.set_fee_rate(30, 0, 0);
+        pool.record_volatility(0, 500);
+        // without bounds the fee rate applies whatever the volatility
+        assert_eq!(pool.effective_fees_bps(0), 30);
+
+        pool.set_dynamic_fee(10, 100, 0);
+        assert_eq!(pool.effective_fees_bps(0), 10);
+        pool.record_volatility(0, 500);
+        assert_eq!(pool.effective_fees_bps(0), 55);
+        pool.record_volatility(0, 600);
+        assert_eq!(pool.effective_fees_bps(0), 100);
+
+        // halves every half-life, linearly in between
+        assert_eq!(pool.volatility(VOLATILITY_HALF_LIFE_SECONDS / 2), 825);
+        assert_eq!(pool.volatility(VOLATILITY_HALF_LIFE_SECONDS), 550);
+        assert_eq!(pool.effective_fees_bps(VOLATILITY_HALF_LIFE_SECONDS), 59);
+        assert_eq!(pool.effective_fees_bps(64 * VOLATILITY_HALF_LIFE_SECONDS), 10);
+
+        pool.set_dynamic_fee(0, 0, 0);
+        assert_eq!(pool.effective_fees_bps(0), 30);
+    }
+
+    #[test]
+    fn test_update_pool_fee_schedules_rate() {
+        use crate::states::{Pool, UpdatePoolFee};
+
+        let mut pool: Pool = unsafe { core::mem::zeroed() };
+        pool.set_fees_bps(30);
+        pool.set_fee_rate(30, 0, 0);
+
+        // immediate change: the tier in the seeds is untouched
+        pool.set_fee_rate(25, 100, 100);
+        assert_eq!((pool.fee_rate_bps(100), pool.fees_bps()), (25, 30));
+        assert_eq!(pool.effective_fees_bps(100), 25);
+
+        // delayed change applies from its activation timestamp on
+        pool.set_fee_rate(50, 200, 1_000);
+        assert_eq!(pool.fee_rate_bps(999), 25);
+        assert_eq!(pool.fee_rate_bps(1_000), 50);
+
+        // a due rate is settled before the next one is scheduled
+        pool.set_fee_rate(10, 1_500, 2_000);
+        assert_eq!(pool.fee_rate_bps(1_999), 50);
+        assert_eq!(pool.fee_rate_bps(2_000), 10);
+        // and a pending one is replaced
+        pool.set_fee_rate(40, 1_600, 1_600);
+        assert_eq!(pool.fee_rate_bps(5_000), 40);
+
+        let payload = UpdatePoolFee { fee_rate_bps: 40, delay: 86_400 };
+        assert_eq!(UpdatePoolFee::unpack(&payload.pack()), Ok(payload));
+    }
+
+    #[test]
+    fn test_swap_fee_matches_bps_across_tiers() {
+        use crate::utils::{amount_before_fee, quote_exact_in, quote_exact_out, swap_fee, SwapDirection};
+
+        for fees_bps in [0u64, 1, 5, 30, 100, 500] {
+            // exact when the amount is a multiple of 10_000, rounded up otherwise
+            assert_eq!(swap_fee(1_000_000, fees_bps), Ok(100 * fees_bps));
+            let max_fee = (u64::MAX as u128 * fees_bps as u128).div_ceil(10_000) as u64;
+            assert_eq!(swap_fee(u64::MAX, fees_bps), Ok(max_fee));
+            assert_eq!(swap_fee(1, fees_bps), Ok(fees_bps.min(1)));
+
+            // the gross input for a post-fee amount is the smallest one that covers it
+            for amount_in_after_fee in [1u64, 997, 9_970, 1_000_000] {
+                let amount_in = amount_before_fee(amount_in_after_fee, fees_bps).unwrap();
+                assert!(amount_in - swap_fee(amount_in, fees_bps).unwrap() >= amount_in_after_fee);
+                let smaller = amount_in - 1;
+                assert!(smaller - swap_fee(smaller, fees_bps).unwrap() < amount_in_after_fee);
+            }
+
+            // quotes charge, and report, the configured share of the input
+            let reserve = 1_000_000_000;
+            let exact_in = quote_exact_in(reserve, reserve, fees_bps, 100_000, SwapDirection::ZeroForOne).unwrap();
+            assert_eq!((exact_in.fee, exact_in.fees_bps), (10 * fees_bps, fees_bps));
+            let exact_out =
+                quote_exact_out(reserve, reserve, fees_bps, exact_in.amount_out, SwapDirection::ZeroForOne).unwrap();
+            assert!(exact_out.fee.abs_diff(exact_in.fee) <= 1);
+        }
+
+        // a 30 bps pool used to charge nothing and a 500 bps one five times the input
+        assert_eq!(swap_fee(10_000, 30), Ok(30));
+        assert_eq!(swap_fee(10_000, 500), Ok(500));
+        assert_eq!(swap_fee(1_000, 10_000), Err(SolanaCoreError::MathError));
+        assert_eq!(amount_before_fee(1_000, 10_000), Err(SolanaCoreError::MathError));
+    }
+
+    #[test]
+    fn test_referral_fee_share() {
+        use crate::states::{Referrer, SwapReferral};
+        use crate::utils::{quote_exact_in, referral_fee, SwapDirection};
+
+        // 30 bps of 100_000 is a 300 fee, of which a 20 % share goes to the referrer
+        let quote = quote_exact_in(1_000_000_000, 1_000_000_000, 30, 100_000, SwapDirection::ZeroForOne).unwrap();
+        assert_eq!(referral_fee(quote.fee, 2_000), 60);
+        // rounded down, never more than the whole fee
+        assert_eq!(referral_fee(7, 5_000), 3);
+        assert_eq!(referral_fee(quote.fee, 20_000), quote.fee);
+        assert_eq!(referral_fee(quote.fee, 0), 0);
+
+        let mut referrer: Referrer = unsafe { core::mem::zeroed() };
+        referrer.record(quote.amount_in, 60);
+        referrer.record(50_000, 30);
+        assert_eq!((referrer.swap_count(), referrer.volume(), referrer.fees_earned()), (2, 150_000, 90));
+
+        let payload = SwapReferral { fee_share_bps: 2_000 };
+        assert_eq!(SwapReferral::unpack(&payload.pack()), Ok(payload));
+    }
+
+    #[test]
+    fn test_pool_creation_fee_config() {
+        use crate::states::{Config, InitConfig, SetPoolCreationFee};
+
+        // the treasury is set at init, with no fee until the admin sets one
+        let treasury = Pubkey::new_unique().to_bytes();
+        let init = InitConfig {
+            admin: Pubkey::new_unique().to_bytes(),
+            config_bump: 255,
+            min_fee_bps: 1,
+            max_fee_bps: 100,
+            max_referral_fee_share_bps: 2_000,
+            treasury,
+        };
+        assert_eq!(InitConfig::unpack(&init.pack()), Ok(init));
+        let mut config: Config = unsafe { core::mem::zeroed() };
+        config.treasury = init.treasury;
+        assert_eq!(config.pool_creation_fee(), 0);
+
+        // the default mint means lamports
+        config.set_pool_creation_fee(&treasury, &[0u8; 32], 100_000_000);
+        assert!(config.pool_creation_fee_in_lamports());
+        assert_eq!((config.treasury, config.pool_creation_fee()), (treasury, 100_000_000));
+
+        config.set_pool_creation_fee(&treasury, &TOKEN_1_MINT.to_bytes(), 5_000_000);
+        assert!(!config.pool_creation_fee_in_lamports());
+        assert_eq!(config.pool_creation_fee_mint, TOKEN_1_MINT.to_bytes());
+
+        let payload = SetPoolCreationFee { mint: TOKEN_1_MINT.to_bytes(), amount: 5_000_000 };
+        assert_eq!(SetPoolCreationFee::unpack(&payload.pack()), Ok(payload));
+    }
+
+    #[cfg(feature = "cpi")]
+    #[test]
+    fn test_cpi_init_pool_account_metas() {
+        use crate::cpi::{InitPoolAccounts, LiquidityAccounts};
+
+        let keys: Vec<Pubkey> = (0..17).map(|_| Pubkey::new_unique()).collect();
+        let mut headers = [[0u64; 11]; 17];
+        let infos: Vec<_> = headers.iter_mut().zip(&keys).map(|(header, key)| account_info(header, key)).collect();
+        let accounts = InitPoolAccounts {
+            liquidity: LiquidityAccounts {
+                user: &infos[0],
+                pool: &infos[1],
+                user_token_0: &infos[2],
+                user_token_1: &infos[3],
+                token_0_mint: &infos[4],
+                token_1_mint: &infos[5],
+                vault_0: &infos[6],
+                vault_1: &infos[7],
+                lp_mint: &infos[8],
+                user_lp: &infos[9],
+                rent_sysvar: &infos[10],
+                system_program: &infos[11],
+                token_program: &infos[12],
+            },
+            config: &infos[13],
+            treasury: &infos[14],
+            fee_token: None,
+        };
+
+        // the shank order: liquidity accounts, config, treasury, then the optional fee accounts
+        let writable = [true, true, true, true, false, false, true, true, true, true, false, false, false, false, true, true, false];
+        let expected: Vec<_> = keys.iter().zip(writable).enumerate()
+            .map(|(i, (key, is_writable))| (key.to_bytes(), is_writable, i == 0))
+            .collect();
+        assert_eq!(meta_layout(&accounts.metas()), expected[..15]);
+
+        assert_eq!(meta_layout(&accounts.metas_with_fee_token(&infos[15], &infos[16])), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "cpi")]
+    fn test_cpi_account_metas() {
+        use crate::cpi;
+        use crate::states::{self, SwapReferral};
+        use crate::utils::{SwapDirection, SwapMode};
+
+        let keys: Vec<Pubkey> = (0..12).map(|_| Pubkey::new_unique()).collect();
+        let mut headers = [[0u64; 11]; 12];
+        let infos: Vec<_> = headers.iter_mut().zip(&keys).map(|(header, key)| account_info(header, key)).collect();
+        // `(is_writable, is_signer)` of the first `flags.len()` keys
+        let layout = |flags: &[(bool, bool)]| -> Vec<([u8; 32], bool, bool)> {
+            keys.iter().zip(flags).map(|(key, &(is_writable, is_signer))| (key.to_bytes(), is_writable, is_signer)).collect()
+        };
+        let (w, r, ws, rs) = ((true, false), (false, false), (true, true), (false, true));
+        let swap_args = states::Swap {
+            amount: 1,
+            expected_amount: 1,
+            slippage_bps: 0,
+            direction: SwapDirection::ZeroForOne,
+            mode: SwapMode::ExactIn,
+        };
+
+        let swap = cpi::Swap {
+            user: &infos[0],
+            pool: &infos[1],
+            user_token_0: &infos[2],
+            user_token_1: &infos[3],
+            token_0_mint: &infos[4],
+            token_1_mint: &infos[5],
+            vault_0: &infos[6],
+            vault_1: &infos[7],
+            system_program: &infos[8],
+            token_program: &infos[9],
+            observations: None,
+            referral: None,
+            args: swap_args,
+        };
+        assert_eq!(meta_layout(&swap.metas()), layout(&[ws, w, w, w, r, r, w, w, r, r]));
+        let referral = cpi::SwapReferralAccounts {
+            config: &infos[0],
+            referrer_token_account: &infos[1],
+            referrer_stats: None,
+            args: SwapReferral { fee_share_bps: 10 },
+        };
+        assert_eq!(meta_layout(&referral.metas()), layout(&[r, w]));
+
+        let cl_swap = cpi::ClSwap {
+            user: &infos[0],
+            pool: &infos[1],
+            user_token_0: &infos[2],
+            user_token_1: &infos[3],
+            token_0_mint: &infos[4],
+            token_1_mint: &infos[5],
+            vault_0: &infos[6],
+            vault_1: &infos[7],
+            token_program: &infos[8],
+            tick_array: &infos[9],
+            next_tick_arrays: &[],
+            args: swap_args,
+        };
+        assert_eq!(meta_layout(&cl_swap.metas()), layout(&[rs, w, w, w, r, r, w, w, r, w]));
+
+        let increase_cardinality = cpi::IncreaseObservationCardinality {
+            payer: &infos[0],
+            pool: &infos[1],
+            observations: &infos[2],
+            system_program: &infos[3],
+            args: states::IncreaseObservationCardinality { cardinality_next: 2, observations_bump: 255 },
+        };
+        assert_eq!(meta_layout(&increase_cardinality.metas()), layout(&[ws, r, w, r]));
+
+        let observe_twap = cpi::ObserveTwap {
+            pool: &infos[0],
+            observations: &infos[1],
+            args: states::ObserveTwap { window_seconds: 60 },
+        };
+        assert_eq!(meta_layout(&observe_twap.metas()), layout(&[r, r]));
+
+        let get_price = cpi::GetPrice {
+            pool: &infos[0],
+            lp_mint: &infos[1],
+            args: states::GetPrice { amount_in: 0, direction: SwapDirection::ZeroForOne },
+        };
+        assert_eq!(meta_layout(&get_price.metas()), layout(&[r, r]));
+
+        let init_config = cpi::InitConfig {
+            authority: &infos[0],
+            config: &infos[1],
+            program_data: &infos[2],
+            system_program: &infos[3],
+            args: states::InitConfig {
+                admin: keys[0].to_bytes(),
+                config_bump: 255,
+                min_fee_bps: 1,
+                max_fee_bps: 100,
+                max_referral_fee_share_bps: 5_000,
+                treasury: keys[1].to_bytes(),
+            },
+        };
+        assert_eq!(meta_layout(&init_config.metas()), layout(&[ws, w, r, r]));
+
+        let ramp_amp = cpi::RampAmp {
+            admin: &infos[0],
+            config: &infos[1],
+            pool: &infos[2],
+            args: states::RampAmp { target_amp: 100, ramp_duration: 86_400 },
+        };
+        assert_eq!(meta_layout(&ramp_amp.metas()), layout(&[rs, r, w]));
+    }
+
+    // SPL token mint and token account data, packed by hand.
+    fn mint_account(decimals: u8) -> Account {
+        let mut data = vec![0u8; 82];
+        data[44] = decimals;
+        data[45] = 1;
+        Account { lamports: Rent::default().minimum_balance(82), data, owner: TOKEN_PROGRAM, executable: false, rent_epoch: 0 }
+    }
+
+    fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+        let mut data = vec![0u8; 165];
+        data[..32].copy_from_slice(mint.as_ref());
+        data[32..64].copy_from_slice(owner.as_ref());
+        data[64..72].copy_from_slice(&amount.to_le_bytes());
+        data[108] = 1;
+        Account { lamports: Rent::default().minimum_balance(165), data, owner: TOKEN_PROGRAM, executable: false, rent_epoch: 0 }
+    }
+
+    fn token_amount(account: &Account) -> u64 {
+        u64::from_le_bytes(account.data[64..72].try_into().unwrap())
+    }
+
+    // A constant-product pool of TOKEN_0_MINT/TOKEN_1_MINT at its PDA, with fresh vault and LP mint keys.
+    fn test_pool(fees_bps: u64, reserve_0: u64, reserve_1: u64) -> (Pubkey, crate::states::Pool) {
+        use crate::states::Pool;
+        use crate::utils::AccountDiscriminator;
+
+        let (pool, pool_bump) = Pubkey::find_program_address(
+            &[Pool::POOL_SEED.as_bytes(), TOKEN_0_MINT.as_ref(), TOKEN_1_MINT.as_ref(), &fees_bps.to_le_bytes()],
+            &PROGRAM,
+        );
+        let mut pool_state: Pool = unsafe { core::mem::zeroed() };
+        pool_state.discriminator = Pool::DISCRIMINATOR;
+        pool_state.token_0_mint = TOKEN_0_MINT.to_bytes();
+        pool_state.token_1_mint = TOKEN_1_MINT.to_bytes();
+        pool_state.vault_0 = Pubkey::new_unique().to_bytes();
+        pool_state.vault_1 = Pubkey::new_unique().to_bytes();
+        pool_state.lp_mint = Pubkey::new_unique().to_bytes();
+        pool_state.pool_bump = pool_bump;
+        pool_state.set_token_0_amount(reserve_0);
+        pool_state.set_token_1_amount(reserve_1);
+        pool_state.set_fees_bps(fees_bps);
+        pool_state.set_fee_rate(fees_bps, 0, 0);
+        (pool, pool_state)
+    }
+
+    fn pool_account(pool_state: &crate::states::Pool) -> Account {
+        use crate::states::Pool;
+        use crate::utils::DataLen;
+
+        let data = unsafe { core::slice::from_raw_parts(pool_state as *const Pool as *const u8, Pool::LEN) }.to_vec();
+        Account { lamports: Rent::default().minimum_balance(Pool::LEN), data, owner: PROGRAM, executable: false, rent_epoch: 0 }
+    }
+
+    // The Instructions sysvar of a transaction made of `instructions`, executing the first one.
+    fn instructions_sysvar(instructions: &[&Instruction]) -> Account {
+        use solana_sdk::instruction::{BorrowedAccountMeta, BorrowedInstruction};
+        use solana_sdk::sysvar::instructions::construct_instructions_data;
+
+        let borrowed: Vec<_> = instructions
+            .iter()
+            .map(|ix| BorrowedInstruction {
+                program_id: &ix.program_id,
+                accounts: ix
+                    .accounts
+                    .iter()
+                    .map(|meta| BorrowedAccountMeta {
+                        pubkey: &meta.pubkey,
+                        is_signer: meta.is_signer,
+                        is_writable: meta.is_writable,
+                    })
+                    .collect(),
+                data: &ix.data,
+            })
+            .collect();
+        Account {
+            lamports: 1,
+            data: construct_instructions_data(&borrowed),
+            owner: solana_sdk::sysvar::ID,
+            executable: false,
+            rent_epoch: 0,
+        }
+    }
+
+    #[test]
+    fn test_flash_loan_and_repay_instructions() {
+        use crate::instructions::{FLASH_LOAN_DISCRIMINATOR, FLASH_REPAY_DISCRIMINATOR};
+        use crate::states::{FlashLoan, Pool};
+
+        let mut mollusk = mollusk();
+        mollusk_svm_programs_token::token::add_program(&mut mollusk);
+
+        let (pool, pool_state) = test_pool(30, 1_000_000, 2_000_000);
+        let pool_account = pool_account(&pool_state);
+        let (vault_0, vault_1) = (Pubkey::from(pool_state.vault_0), Pubkey::from(pool_state.vault_1));
+        let (borrower_0, borrower_1) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        let token_accounts = vec![
+            AccountMeta::new(PAYER, true),
+            AccountMeta::new(pool, false),
+            AccountMeta::new(borrower_0, false),
+            AccountMeta::new(borrower_1, false),
+            AccountMeta::new_readonly(TOKEN_0_MINT, false),
+            AccountMeta::new_readonly(TOKEN_1_MINT, false),
+            AccountMeta::new(vault_0, false),
+            AccountMeta::new(vault_1, false),
+            AccountMeta::new_readonly(TOKEN_PROGRAM, false),
+        ];
+        let mut loan_accounts = token_accounts.clone();
+        loan_accounts.push(AccountMeta::new_readonly(solana_sdk::sysvar::instructions::ID, false));
+        let mut loan_data = vec![FLASH_LOAN_DISCRIMINATOR];
+        loan_data.extend_from_slice(&FlashLoan { amount_0: 100_000, amount_1: 0 }.pack());
+        let loan = Instruction::new_with_bytes(PROGRAM, &loan_data, loan_accounts);
+        let repay = Instruction::new_with_bytes(PROGRAM, &[FLASH_REPAY_DISCRIMINATOR], token_accounts);
+
+        // the borrower holds enough for the 300 fee on 100_000 at 30 bps, or not
+        let accounts = |borrower_balance: u64, instructions: &[&Instruction]| {
+            vec![
+                (PAYER, Account::new(LAMPORTS_PER_SOL, 0, &SYSTEM_PROGRAM)),
+                (pool, pool_account.clone()),
+                (borrower_0, token_account(&TOKEN_0_MINT, &PAYER, borrower_balance)),
+                (borrower_1, token_account(&TOKEN_1_MINT, &PAYER, 0)),
+                (TOKEN_0_MINT, mint_account(6)),
+                (TOKEN_1_MINT, mint_account(9)),
+                (vault_0, token_account(&TOKEN_0_MINT, &pool, 1_000_000)),
+                (vault_1, token_account(&TOKEN_1_MINT, &pool, 2_000_000)),
+                mollusk_svm_programs_token::token::keyed_account(),
+                (solana_sdk::sysvar::instructions::ID, instructions_sysvar(instructions)),
+            ]
+        };
+
+        // repaid with the fee: the fee is credited to the reserves and the pool unlocked
+        let result = mollusk.process_instruction_chain(&[loan.clone(), repay.clone()], &accounts(1_000, &[&loan, &repay]));
+        assert!(matches!(result.program_result, ProgramResult::Success), "{:?}", result.program_result);
+        assert_eq!(token_amount(result.get_account(&vault_0).unwrap()), 1_000_300);
+        assert_eq!(token_amount(result.get_account(&borrower_0).unwrap()), 700);
+        let pool_after = unsafe { crate::utils::load_acc_unchecked::<Pool>(&result.get_account(&pool).unwrap().data) }.unwrap();
+        assert_eq!((pool_after.token_0_amount(), pool_after.token_1_amount()), (1_000_300, 2_000_000));
+        assert!(pool_after.check_unlocked().is_ok());
+
+        // a borrower unable to pay the fee fails the repayment, and with it the loan
+        let result = mollusk.process_instruction_chain(&[loan.clone(), repay.clone()], &accounts(0, &[&loan, &repay]));
+        assert_eq!(
+            result.program_result,
+            ProgramResult::Failure(solana_sdk::program_error::ProgramError::Custom(
+                SolanaCoreError::FlashLoanNotRepaid as u32,
+            ))
+        );
+
+        // without a later flash_repay nothing is lent
+        mollusk.process_and_validate_instruction(
+            &loan,
+            &accounts(1_000, &[&loan]),
+            &[Check::err(solana_sdk::program_error::ProgramError::Custom(
+                SolanaCoreError::FlashLoanNotRepaid as u32,
+            ))],
+        );
+    }
+
+    #[test]
+    fn test_get_price_instruction() {
+        use crate::instructions::{PriceQuote, GET_PRICE_DISCRIMINATOR};
+        use crate::states::GetPrice;
+        use crate::utils::{quote_swap, SwapDirection, SwapMode};
+
+        let mollusk = mollusk();
+        let (pool, pool_state) = test_pool(30, 1_000_000, 2_000_000);
+        let lp_mint = Pubkey::from(pool_state.lp_mint);
+        let mut lp_mint_account = mint_account(9);
+        lp_mint_account.data[36..44].copy_from_slice(&1_414_213u64.to_le_bytes());
+        let now = mollusk.sysvars.clock.unix_timestamp;
+
+        let get_price = |amount_in: u64| {
+            let mut data = vec![GET_PRICE_DISCRIMINATOR];
+            data.extend_from_slice(&GetPrice { amount_in, direction: SwapDirection::ZeroForOne }.pack());
+            let instruction = Instruction::new_with_bytes(
+                PROGRAM,
+                &data,
+                vec![AccountMeta::new_readonly(pool, false), AccountMeta::new_readonly(lp_mint, false)],
+            );
+            let result = mollusk.process_instruction(
+                &instruction,
+                &[(pool, pool_account(&pool_state)), (lp_mint, lp_mint_account.clone())],
+            );
+            assert!(matches!(result.program_result, ProgramResult::Success), "{:?}", result.program_result);
+            PriceQuote::unpack(&result.return_data).unwrap()
+        };
+
+        // 2 token_1 per token_0 and 0.5 back, in Q64.64
+        let price = get_price(0);
+        assert_eq!((price.price_0, price.price_1), (2 << 64, 1 << 63));
+        assert_eq!((price.reserve_0, price.reserve_1, price.lp_supply, price.fees_bps), (1_000_000, 2_000_000, 1_414_213, 30));
+        assert_eq!((price.amount_in, price.amount_out, price.fee, price.price_impact_bps), (0, 0, 0, 0));
+
+        // the quote is the one `swap` would execute
+        let price = get_price(10_000);
+        let quote = quote_swap(&pool_state, 10_000, SwapDirection::ZeroForOne, SwapMode::ExactIn, now).unwrap();
+        assert_eq!(
+            (price.amount_in, price.amount_out, price.fee, price.price_impact_bps),
+            (quote.amount_in, quote.amount_out, quote.fee, quote.price_impact_bps)
+        );
+        assert!(quote.amount_out > 0);
+    }
+
+    #[test]
+    fn test_pool_field_offsets() {
+        use crate::states::Pool;
+        use crate::utils::{AccountDiscriminator, DataLen};
+
+        let mut pool: Pool = unsafe { core::mem::zeroed() };
+        pool.discriminator = Pool::DISCRIMINATOR;
+        pool.token_0_mint = [1; 32];
+        pool.token_1_mint = [2; 32];
+        pool.vault_0 = [3; 32];
+        pool.vault_1 = [4; 32];
+        pool.lp_mint = [5; 32];
+        pool.set_token_0_amount(6);
+        pool.set_token_1_amount(7);
+        pool.set_fees_bps(8);
+        pool.pool_bump = 9;
+        pool.lp_bump = 10;
+        pool.price_0_cumulative = 11u128.to_le_bytes();
+        pool.price_1_cumulative = 12u128.to_le_bytes();
+        pool.last_update_timestamp = 13i64.to_le_bytes();
+        pool.liquidity_cumulative = 14u128.to_le_bytes();
+        pool.curve_type = 15;
+        pool.amp_initial = 16u64.to_le_bytes();
+        pool.amp_target = 17u64.to_le_bytes();
+        pool.amp_start_timestamp = 18i64.to_le_bytes();
+        pool.amp_end_timestamp = 19i64.to_le_bytes();
+        pool.weight_0_bps = 20u64.to_le_bytes();
+        pool.flash_loan_lock = 21;
+        pool.flash_loan_amount_0 = 22u64.to_le_bytes();
+        pool.flash_loan_amount_1 = 23u64.to_le_bytes();
+        pool.min_fee_bps = 24u64.to_le_bytes();
+        pool.max_fee_bps = 25u64.to_le_bytes();
+        pool.volatility_accumulator = 26u64.to_le_bytes();
+        pool.volatility_timestamp = 27i64.to_le_bytes();
+        pool.fee_rate_bps = 28u64.to_le_bytes();
+        pool.pending_fee_rate_bps = 29u64.to_le_bytes();
+        pool.pending_fee_rate_timestamp = 30i64.to_le_bytes();
+        let data = unsafe { core::slice::from_raw_parts(&pool as *const Pool as *const u8, Pool::LEN) };
+
+        // `(offset, bytes written there)`, in field order
+        let fields: [(usize, &[u8]); 31] = [
+            (Pool::DISCRIMINATOR_OFFSET, &Pool::DISCRIMINATOR),
+            (Pool::TOKEN_0_MINT_OFFSET, &[1; 32]),
+            (Pool::TOKEN_1_MINT_OFFSET, &[2; 32]),
+            (Pool::VAULT_0_OFFSET, &[3; 32]),
+            (Pool::VAULT_1_OFFSET, &[4; 32]),
+            (Pool::LP_MINT_OFFSET, &[5; 32]),
+            (Pool::TOKEN_0_AMOUNT_OFFSET, &6u64.to_le_bytes()),
+            (Pool::TOKEN_1_AMOUNT_OFFSET, &7u64.to_le_bytes()),
+            (Pool::FEES_BPS_OFFSET, &8u64.to_le_bytes()),
+            (Pool::POOL_BUMP_OFFSET, &[9]),
+            (Pool::LP_BUMP_OFFSET, &[10]),
+            (Pool::PRICE_0_CUMULATIVE_OFFSET, &11u128.to_le_bytes()),
+            (Pool::PRICE_1_CUMULATIVE_OFFSET, &12u128.to_le_bytes()),
+            (Pool::LAST_UPDATE_TIMESTAMP_OFFSET, &13i64.to_le_bytes()),
+            (Pool::LIQUIDITY_CUMULATIVE_OFFSET, &14u128.to_le_bytes()),
+            (Pool::CURVE_TYPE_OFFSET, &[15]),
+            (Pool::AMP_INITIAL_OFFSET, &16u64.to_le_bytes()),
+            (Pool::AMP_TARGET_OFFSET, &17u64.to_le_bytes()),
+            (Pool::AMP_START_TIMESTAMP_OFFSET, &18i64.to_le_bytes()),
+            (Pool::AMP_END_TIMESTAMP_OFFSET, &19i64.to_le_bytes()),
+            (Pool::WEIGHT_0_BPS_OFFSET, &20u64.to_le_bytes()),
+            (Pool::FLASH_LOAN_LOCK_OFFSET, &[21]),
+            (Pool::FLASH_LOAN_AMOUNT_0_OFFSET, &22u64.to_le_bytes()),
+            (Pool::FLASH_LOAN_AMOUNT_1_OFFSET, &23u64.to_le_bytes()),
+            (Pool::MIN_FEE_BPS_OFFSET, &24u64.to_le_bytes()),
+            (Pool::MAX_FEE_BPS_OFFSET, &25u64.to_le_bytes()),
+            (Pool::VOLATILITY_ACCUMULATOR_OFFSET, &26u64.to_le_bytes()),
+            (Pool::VOLATILITY_TIMESTAMP_OFFSET, &27i64.to_le_bytes()),
+            (Pool::FEE_RATE_BPS_OFFSET, &28u64.to_le_bytes()),
+            (Pool::PENDING_FEE_RATE_BPS_OFFSET, &29u64.to_le_bytes()),
+            (Pool::PENDING_FEE_RATE_TIMESTAMP_OFFSET, &30i64.to_le_bytes()),
+        ];
+        // each field reads back at its offset, and the fields cover the account without gaps
+        let mut end = 0;
+        for (offset, bytes) in fields {
+            assert_eq!(offset, end);
+            assert_eq!(&data[offset..][..bytes.len()], bytes);
+            end = offset + bytes.len();
+        }
+        assert_eq!(end, Pool::LEN);
+    }
 }
@@ -0,0 +1,158 @@
+use core::mem::offset_of;
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey};
+use shank::ShankAccount;
+use crate::{
+    errors::SolanaCoreError,
+    utils::{init_acc_mut_unchecked, load_acc, load_acc_mut, AccountDiscriminator, DataLen, MAX_TICK, MIN_TICK},
+};
+
+// Concentrated-liquidity pool, PDA `["cl_pool", token_0_mint, token_1_mint, fees_bps, bump]`,
+// living alongside the full-range `Pool`. Vaults are the pool PDA's ATAs, like `Pool`.
+// Liquidity is provided by `Position`s over tick ranges, and the ticks they reference
+// are stored in `TickArray`s. See `utils::tick_math` for the price representation.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, ShankAccount)]
+pub struct ClPool {
+    //account type tag, see `AccountDiscriminator`
+    pub discriminator: [u8; 8],
+
+    //token_mint
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+
+    //vault
+    pub vault_0: Pubkey,
+    pub vault_1: Pubkey,
+
+    //fees(unit: bps, u64 little-endian)
+    pub fees_bps: [u8; 8],
+
+    //positions and tick arrays must be aligned to this many ticks (u16, little-endian)
+    pub tick_spacing: [u8; 2],
+
+    //bump for the pool(program derived address)
+    pub pool_bump: u8,
+
+    //current sqrt(price) as Q64.64 (u128, little-endian)
+    pub sqrt_price: [u8; 16],
+
+    //greatest tick at or below the current price (i32, little-endian)
+    pub tick_current: [u8; 4],
+
+    //liquidity of the positions in range (u128, little-endian)
+    pub liquidity: [u8; 16],
+
+    //fees earned per unit of liquidity since creation, Q64.64 (u128, little-endian, wrapping)
+    pub fee_growth_global_0: [u8; 16],
+    pub fee_growth_global_1: [u8; 16],
+}
+
+impl DataLen for ClPool {
+    const LEN: usize = core::mem::size_of::<ClPool>();
+}
+
+impl AccountDiscriminator for ClPool {
+    // sha256("account:ClPool")[..8]
+    const DISCRIMINATOR: [u8; 8] = [106, 203, 220, 44, 175, 135, 152, 114];
+}
+
+const _: () = assert!(core::mem::align_of::<ClPool>() == 1);
+const _: () = assert!(ClPool::LEN == 215);
+
+impl ClPool {
+    pub const SEED: &'static str = "cl_pool";
+    // keeps tick arrays at a sensible width and positions addressable
+    pub const MAX_TICK_SPACING: u16 = 16_384;
+
+    pub const TOKEN_0_MINT_OFFSET: usize = offset_of!(ClPool, token_0_mint);
+    pub const TOKEN_1_MINT_OFFSET: usize = offset_of!(ClPool, token_1_mint);
+    pub const SQRT_PRICE_OFFSET: usize = offset_of!(ClPool, sqrt_price);
+    pub const LIQUIDITY_OFFSET: usize = offset_of!(ClPool, liquidity);
+
+    /// Loads a concentrated-liquidity pool, checking owner and discriminator.
+    #[inline(always)]
+    pub fn load(pool: &AccountInfo) -> Result<&ClPool, SolanaCoreError> {
+        load_acc::<ClPool>(pool)
+    }
+
+    /// Same as [`ClPool::load`], but also requires the account to be writable.
+    ///
+    /// # Safety
+    ///
+    /// See [`load_acc_mut`](crate::utils::load_acc_mut): no other reference to the account's data may be live.
+    #[inline(always)]
+    pub unsafe fn load_mut(pool: &AccountInfo) -> Result<&mut ClPool, SolanaCoreError> {
+        unsafe { load_acc_mut::<ClPool>(pool) }
+    }
+
+    /// Tags a freshly created, zeroed account as a `ClPool`.
+    #[inline(always)]
+    pub fn init(pool: &AccountInfo) -> Result<&mut ClPool, SolanaCoreError> {
+        unsafe { init_acc_mut_unchecked::<ClPool>(pool.borrow_mut_data_unchecked()) }
+    }
+
+    #[inline(always)]
+    pub fn fees_bps(&self) -> u64 {
+        u64::from_le_bytes(self.fees_bps)
+    }
+
+    #[inline(always)]
+    pub fn tick_spacing(&self) -> u16 {
+        u16::from_le_bytes(self.tick_spacing)
+    }
+
+    #[inline(always)]
+    pub fn sqrt_price(&self) -> u128 {
+        u128::from_le_bytes(self.sqrt_price)
+    }
+
+    #[inline(always)]
+    pub fn set_sqrt_price(&mut self, value: u128) {
+        self.sqrt_price = value.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn tick_current(&self) -> i32 {
+        i32::from_le_bytes(self.tick_current)
+    }
+
+    #[inline(always)]
+    pub fn set_tick_current(&mut self, value: i32) {
+        self.tick_current = value.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn liquidity(&self) -> u128 {
+        u128::from_le_bytes(self.liquidity)
+    }
+
+    #[inline(always)]
+    pub fn set_liquidity(&mut self, value: u128) {
+        self.liquidity = value.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn fee_growth_global_0(&self) -> u128 {
+        u128::from_le_bytes(self.fee_growth_global_0)
+    }
+
+    #[inline(always)]
+    pub fn fee_growth_global_1(&self) -> u128 {
+        u128::from_le_bytes(self.fee_growth_global_1)
+    }
+
+    #[inline(always)]
+    pub fn set_fee_growth_global(&mut self, fee_growth_0: u128, fee_growth_1: u128) {
+        self.fee_growth_global_0 = fee_growth_0.to_le_bytes();
+        self.fee_growth_global_1 = fee_growth_1.to_le_bytes();
+    }
+
+    /// Requires `tick` to be a usable tick for this pool's spacing.
+    #[inline(always)]
+    pub fn check_tick(&self, tick: i32) -> Result<(), SolanaCoreError> {
+        if !(MIN_TICK..=MAX_TICK).contains(&tick) || tick % self.tick_spacing() as i32 != 0 {
+            return Err(SolanaCoreError::InvalidTick);
+        }
+        Ok(())
+    }
+}
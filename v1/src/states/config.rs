@@ -0,0 +1,144 @@
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey};
+use shank::ShankAccount;
+use crate::{
+    errors::SolanaCoreError,
+    states::InitConfig,
+    utils::{init_acc_mut_unchecked, load_acc, load_acc_mut, AccountDiscriminator, DataLen},
+};
+
+// Program-wide settings, PDA `["config"]`. Created once by the program's upgrade
+// authority; `admin` then signs every privileged instruction.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, ShankAccount)]
+pub struct Config {
+    //account type tag, see `AccountDiscriminator`
+    pub discriminator: [u8; 8],
+
+    //signer of admin instructions
+    pub admin: Pubkey,
+
+    //bump for the config(program derived address)
+    pub bump: u8,
+
+    //bounds on the fee rate `update_pool_fee` may set (bps, u64 little-endian)
+    pub min_fee_bps: [u8; 8],
+    pub max_fee_bps: [u8; 8],
+
+    //cap on the share of a swap fee paid to a referrer (bps of the fee, u64 little-endian)
+    pub max_referral_fee_share_bps: [u8; 8],
+
+    //receives pool creation fees: any account for fees in lamports, a token account of
+    // `pool_creation_fee_mint` otherwise
+    pub treasury: Pubkey,
+
+    //mint of the pool creation fee, all zeros for lamports
+    pub pool_creation_fee_mint: Pubkey,
+
+    //charged by `init_pool`, zero for none (u64, little-endian)
+    pub pool_creation_fee: [u8; 8],
+}
+
+impl DataLen for Config {
+    const LEN: usize = core::mem::size_of::<Config>();
+}
+
+impl AccountDiscriminator for Config {
+    // sha256("account:Config")[..8]
+    const DISCRIMINATOR: [u8; 8] = [155, 12, 170, 224, 30, 250, 204, 130];
+}
+
+const _: () = assert!(core::mem::align_of::<Config>() == 1);
+
+impl Config {
+    pub const SEED: &'static str = "config";
+
+    /// Loads the config account, checking that it is owned by this program and tagged as a `Config`.
+    /// Only `init_config` creates `Config` accounts, and only at the PDA, so this is the config.
+    #[inline(always)]
+    pub fn load(config: &AccountInfo) -> Result<&Config, SolanaCoreError> {
+        load_acc::<Config>(config)
+    }
+
+    /// Same as [`Config::load`], but also requires the account to be writable.
+    ///
+    /// # Safety
+    ///
+    /// See [`load_acc_mut`](crate::utils::load_acc_mut): no other reference to the account's data may be live.
+    #[inline(always)]
+    pub unsafe fn load_mut(config: &AccountInfo) -> Result<&mut Config, SolanaCoreError> {
+        unsafe { load_acc_mut::<Config>(config) }
+    }
+
+    /// Initializes a freshly created, zeroed config account.
+    #[inline(always)]
+    pub fn init(config: &AccountInfo, ix_data: &InitConfig) -> Result<(), SolanaCoreError> {
+        let config_acc = unsafe { init_acc_mut_unchecked::<Config>(config.borrow_mut_data_unchecked()) }?;
+        config_acc.admin = ix_data.admin;
+        config_acc.bump = ix_data.config_bump;
+        config_acc.min_fee_bps = ix_data.min_fee_bps.to_le_bytes();
+        config_acc.max_fee_bps = ix_data.max_fee_bps.to_le_bytes();
+        config_acc.max_referral_fee_share_bps = ix_data.max_referral_fee_share_bps.to_le_bytes();
+        // no fee until `set_pool_creation_fee`, but `init_pool` checks the treasury from the start
+        config_acc.treasury = ix_data.treasury;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn min_fee_bps(&self) -> u64 {
+        u64::from_le_bytes(self.min_fee_bps)
+    }
+
+    #[inline(always)]
+    pub fn max_fee_bps(&self) -> u64 {
+        u64::from_le_bytes(self.max_fee_bps)
+    }
+
+    #[inline(always)]
+    pub fn max_referral_fee_share_bps(&self) -> u64 {
+        u64::from_le_bytes(self.max_referral_fee_share_bps)
+    }
+
+    #[inline(always)]
+    pub fn pool_creation_fee(&self) -> u64 {
+        u64::from_le_bytes(self.pool_creation_fee)
+    }
+
+    #[inline(always)]
+    pub fn pool_creation_fee_in_lamports(&self) -> bool {
+        self.pool_creation_fee_mint == Pubkey::default()
+    }
+
+    #[inline(always)]
+    pub fn set_pool_creation_fee(&mut self, treasury: &Pubkey, mint: &Pubkey, amount: u64) {
+        self.treasury = *treasury;
+        self.pool_creation_fee_mint = *mint;
+        self.pool_creation_fee = amount.to_le_bytes();
+    }
+
+    /// Requires `treasury` to be the configured treasury.
+    #[inline(always)]
+    pub fn check_treasury(&self, treasury: &AccountInfo) -> Result<(), SolanaCoreError> {
+        if *treasury.key() != self.treasury {
+            return Err(SolanaCoreError::InvalidTreasury);
+        }
+        Ok(())
+    }
+
+    /// Requires `fee_rate_bps` to lie within the configured fee bounds.
+    #[inline(always)]
+    pub fn check_fee_rate(&self, fee_rate_bps: u64) -> Result<(), SolanaCoreError> {
+        if fee_rate_bps < self.min_fee_bps() || fee_rate_bps > self.max_fee_bps() {
+            return Err(SolanaCoreError::InvalidFee);
+        }
+        Ok(())
+    }
+
+    /// Requires `admin` to be the config admin and to have signed.
+    #[inline(always)]
+    pub fn check_admin(&self, admin: &AccountInfo) -> Result<(), SolanaCoreError> {
+        if !admin.is_signer() || *admin.key() != self.admin {
+            return Err(SolanaCoreError::Unauthorized);
+        }
+        Ok(())
+    }
+}
@@ -2,4 +2,25 @@ pub mod pool;
 pub use pool::*;
 
 pub mod state;
-pub use state::*;
\ No newline at end of file
+pub use state::*;
+
+pub mod observations;
+pub use observations::*;
+
+pub mod config;
+pub use config::*;
+
+pub mod cl_pool;
+pub use cl_pool::*;
+
+pub mod tick_array;
+pub use tick_array::*;
+
+pub mod position;
+pub use position::*;
+
+pub mod multi_pool;
+pub use multi_pool::*;
+
+pub mod referrer;
+pub use referrer::*;
@@ -0,0 +1,212 @@
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey};
+use shank::ShankAccount;
+use crate::{
+    errors::SolanaCoreError,
+    utils::{init_acc_mut_unchecked, load_acc, load_acc_mut, AccountDiscriminator, Basket, CurveType, DataLen},
+};
+
+// Pool of two to `MAX_MULTI_POOL_TOKENS` tokens on a stable-swap or weighted invariant,
+// PDA `["multi_pool", mint_0, .., mint_n, fees_bps, bump]` with the mints in ascending
+// byte order. Like `Pool`, vaults are the pool PDA's ATAs and the LP mint is the PDA
+// `["lp", pool]`, shared by the whole basket.
+
+pub const MAX_MULTI_POOL_TOKENS: usize = 8;
+// "multi_pool", the mints, fees_bps and the bump
+pub const MAX_MULTI_POOL_SEEDS: usize = MAX_MULTI_POOL_TOKENS + 3;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MultiPoolToken {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+
+    //token_amount (u64, little-endian)
+    pub reserve: [u8; 8],
+
+    //weighted pools: share of the pool value in bps, all weights sum to 10_000
+    // (u64, little-endian, zero for stable-swap pools)
+    pub weight_bps: [u8; 8],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, ShankAccount)]
+pub struct MultiPool {
+    //account type tag, see `AccountDiscriminator`
+    pub discriminator: [u8; 8],
+
+    //The mint address of the lp_token
+    pub lp_mint: Pubkey,
+
+    //fees(unit: bps, u64 little-endian)
+    pub fees_bps: [u8; 8],
+
+    pub pool_bump: u8,
+    pub lp_bump: u8,
+
+    //`CurveType` as u8, `StableSwap` or `Weighted`
+    pub curve_type: u8,
+
+    //number of used entries in `tokens`
+    pub token_count: u8,
+
+    //stable-swap amplification coefficient (u64, little-endian, zero for weighted pools)
+    pub amp: [u8; 8],
+
+    pub tokens: [MultiPoolToken; MAX_MULTI_POOL_TOKENS],
+}
+
+impl DataLen for MultiPool {
+    const LEN: usize = core::mem::size_of::<MultiPool>();
+}
+
+impl AccountDiscriminator for MultiPool {
+    // sha256("account:MultiPool")[..8]
+    const DISCRIMINATOR: [u8; 8] = [244, 223, 68, 238, 85, 162, 221, 210];
+}
+
+const _: () = assert!(core::mem::align_of::<MultiPool>() == 1);
+const _: () = assert!(MultiPool::LEN == 700);
+
+impl MultiPoolToken {
+    #[inline(always)]
+    pub fn reserve(&self) -> u64 {
+        u64::from_le_bytes(self.reserve)
+    }
+
+    #[inline(always)]
+    pub fn set_reserve(&mut self, amount: u64) {
+        self.reserve = amount.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn weight_bps(&self) -> u64 {
+        u64::from_le_bytes(self.weight_bps)
+    }
+}
+
+impl MultiPool {
+    pub const SEED: &'static str = "multi_pool";
+
+    /// Loads a multi-token pool, checking owner and discriminator.
+    #[inline(always)]
+    pub fn load(pool: &AccountInfo) -> Result<&MultiPool, SolanaCoreError> {
+        load_acc::<MultiPool>(pool)
+    }
+
+    /// Same as [`MultiPool::load`], but also requires the account to be writable.
+    ///
+    /// # Safety
+    ///
+    /// See [`load_acc_mut`](crate::utils::load_acc_mut): no other reference to the account's data may be live.
+    #[inline(always)]
+    pub unsafe fn load_mut(pool: &AccountInfo) -> Result<&mut MultiPool, SolanaCoreError> {
+        unsafe { load_acc_mut::<MultiPool>(pool) }
+    }
+
+    /// Tags a freshly created, zeroed account as a `MultiPool`.
+    #[inline(always)]
+    pub fn init(pool: &AccountInfo) -> Result<&mut MultiPool, SolanaCoreError> {
+        unsafe { init_acc_mut_unchecked::<MultiPool>(pool.borrow_mut_data_unchecked()) }
+    }
+
+    /// Pool PDA seeds for `mints`; only the first `len` entries of the array are used.
+    pub fn seeds<'a>(
+        mints: impl Iterator<Item = &'a [u8]>,
+        fees_bps: &'a [u8; 8],
+        bump: &'a [u8],
+    ) -> Result<([&'a [u8]; MAX_MULTI_POOL_SEEDS], usize), SolanaCoreError> {
+        let mut seeds: [&[u8]; MAX_MULTI_POOL_SEEDS] = [&[]; MAX_MULTI_POOL_SEEDS];
+        seeds[0] = Self::SEED.as_bytes();
+        let mut len = 1;
+        for mint in mints {
+            if len > MAX_MULTI_POOL_TOKENS {
+                return Err(SolanaCoreError::InvalidInstructionData);
+            }
+            seeds[len] = mint;
+            len += 1;
+        }
+        seeds[len] = fees_bps;
+        seeds[len + 1] = bump;
+        Ok((seeds, len + 2))
+    }
+
+    /// This pool's PDA seeds, see [`MultiPool::seeds`].
+    #[inline(always)]
+    pub fn pool_seeds(&self) -> Result<([&[u8]; MAX_MULTI_POOL_SEEDS], usize), SolanaCoreError> {
+        Self::seeds(
+            self.tokens().iter().map(|token| token.mint.as_ref()),
+            &self.fees_bps,
+            core::slice::from_ref(&self.pool_bump),
+        )
+    }
+
+    #[inline(always)]
+    pub fn fees_bps(&self) -> u64 {
+        u64::from_le_bytes(self.fees_bps)
+    }
+
+    #[inline(always)]
+    pub fn amp(&self) -> u64 {
+        u64::from_le_bytes(self.amp)
+    }
+
+    #[inline(always)]
+    pub fn token_count(&self) -> usize {
+        (self.token_count as usize).min(MAX_MULTI_POOL_TOKENS)
+    }
+
+    /// The used entries of `tokens`.
+    #[inline(always)]
+    pub fn tokens(&self) -> &[MultiPoolToken] {
+        &self.tokens[..self.token_count()]
+    }
+
+    /// Reserves in pool token order, zero past `token_count`.
+    #[inline(always)]
+    pub fn reserves(&self) -> [u64; MAX_MULTI_POOL_TOKENS] {
+        let mut reserves = [0; MAX_MULTI_POOL_TOKENS];
+        for (reserve, token) in reserves.iter_mut().zip(self.tokens()) {
+            *reserve = token.reserve();
+        }
+        reserves
+    }
+
+    #[inline(always)]
+    pub fn token(&self, index: usize) -> Result<&MultiPoolToken, SolanaCoreError> {
+        self.tokens().get(index).ok_or(SolanaCoreError::InvalidInstructionData)
+    }
+
+    #[inline(always)]
+    pub fn token_mut(&mut self, index: usize) -> Result<&mut MultiPoolToken, SolanaCoreError> {
+        let count = self.token_count();
+        self.tokens[..count].get_mut(index).ok_or(SolanaCoreError::InvalidInstructionData)
+    }
+
+    /// Requires `mint` and `vault` to be those of token `index`.
+    #[inline(always)]
+    pub fn check_token(&self, index: usize, mint: &AccountInfo, vault: &AccountInfo) -> Result<(), SolanaCoreError> {
+        let token = self.token(index)?;
+        if *mint.key() != token.mint || *vault.key() != token.vault {
+            return Err(SolanaCoreError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    /// Reserves, weights and curve parameters for the pool math in `utils::multi`.
+    #[inline(always)]
+    pub fn basket(&self) -> Result<Basket, SolanaCoreError> {
+        let mut basket = Basket {
+            curve_type: CurveType::try_from(self.curve_type)?,
+            amp: self.amp(),
+            fees_bps: self.fees_bps(),
+            token_count: self.token_count(),
+            reserves: [0; MAX_MULTI_POOL_TOKENS],
+            weights_bps: [0; MAX_MULTI_POOL_TOKENS],
+        };
+        for (index, token) in self.tokens().iter().enumerate() {
+            basket.reserves[index] = token.reserve();
+            basket.weights_bps[index] = token.weight_bps();
+        }
+        Ok(basket)
+    }
+}
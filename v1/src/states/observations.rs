@@ -0,0 +1,319 @@
+use core::mem::{offset_of, size_of};
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey};
+use crate::{
+    errors::SolanaCoreError,
+    utils::{AccountDiscriminator, DataLen, PriceObservation, DISCRIMINATOR_LEN},
+};
+
+// Per-pool ring buffer of accumulator snapshots, PDA `["observations", pool]`.
+//
+// The account is an `ObservationsHeader` followed by `cardinality_next` `Observation`
+// slots. Like Uniswap v3, growing the buffer only allocates slots: they join the ring
+// (`cardinality`) once the write index wraps around to them, so the ring always stays
+// in chronological order.
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObservationsHeader {
+    pub discriminator: [u8; 8],
+
+    //pool these observations belong to
+    pub pool: Pubkey,
+
+    pub bump: u8,
+
+    //slot holding the most recent observation (u16, little-endian)
+    pub index: [u8; 2],
+
+    //slots currently part of the ring (u16, little-endian)
+    pub cardinality: [u8; 2],
+
+    //slots allocated in the account (u16, little-endian)
+    pub cardinality_next: [u8; 2],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Observation {
+    pub timestamp: [u8; 8],
+    pub price_0_cumulative: [u8; 16],
+    pub price_1_cumulative: [u8; 16],
+    pub liquidity_cumulative: [u8; 16],
+}
+
+impl DataLen for ObservationsHeader {
+    const LEN: usize = size_of::<ObservationsHeader>();
+}
+
+impl DataLen for Observation {
+    const LEN: usize = size_of::<Observation>();
+}
+
+impl AccountDiscriminator for ObservationsHeader {
+    // sha256("account:Observations")[..8]
+    const DISCRIMINATOR: [u8; 8] = [119, 205, 13, 6, 93, 29, 178, 203];
+}
+
+const _: () = assert!(core::mem::align_of::<ObservationsHeader>() == 1);
+const _: () = assert!(core::mem::align_of::<Observation>() == 1);
+
+impl Observation {
+    #[inline(always)]
+    pub fn get(&self) -> PriceObservation {
+        PriceObservation {
+            timestamp: i64::from_le_bytes(self.timestamp),
+            price_0_cumulative: u128::from_le_bytes(self.price_0_cumulative),
+            price_1_cumulative: u128::from_le_bytes(self.price_1_cumulative),
+            liquidity_cumulative: u128::from_le_bytes(self.liquidity_cumulative),
+        }
+    }
+
+    #[inline(always)]
+    pub fn set(&mut self, observation: &PriceObservation) {
+        self.timestamp = observation.timestamp.to_le_bytes();
+        self.price_0_cumulative = observation.price_0_cumulative.to_le_bytes();
+        self.price_1_cumulative = observation.price_1_cumulative.to_le_bytes();
+        self.liquidity_cumulative = observation.liquidity_cumulative.to_le_bytes();
+    }
+}
+
+/// Read-only view of an observations account.
+pub struct Observations<'a> {
+    pub header: &'a ObservationsHeader,
+    pub entries: &'a [Observation],
+}
+
+/// Writable view of an observations account, from [`Observations::load_mut`] or [`Observations::init`].
+pub struct ObservationsMut<'a> {
+    pub header: &'a mut ObservationsHeader,
+    pub entries: &'a mut [Observation],
+}
+
+// Number of slots in `bytes`, checking it matches the header's `cardinality_next`.
+#[inline(always)]
+fn slot_count(bytes: &[u8]) -> Result<usize, SolanaCoreError> {
+    if bytes.len() < ObservationsHeader::LEN
+        || (bytes.len() - ObservationsHeader::LEN) % Observation::LEN != 0
+    {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+    let len = (bytes.len() - ObservationsHeader::LEN) / Observation::LEN;
+    let offset = offset_of!(ObservationsHeader, cardinality_next);
+    if u16::from_le_bytes([bytes[offset], bytes[offset + 1]]) as usize != len {
+        return Err(SolanaCoreError::InvalidAccountData);
+    }
+    Ok(len)
+}
+
+// Owner, discriminator and length checks shared by `load` and `load_mut`.
+#[inline(always)]
+fn check_account(account: &AccountInfo) -> Result<(), SolanaCoreError> {
+    if !account.is_owned_by(&crate::ID) {
+        return Err(SolanaCoreError::InvalidOwner);
+    }
+    let bytes = unsafe { account.borrow_data_unchecked() };
+    if bytes.len() < DISCRIMINATOR_LEN || bytes[..DISCRIMINATOR_LEN] != ObservationsHeader::DISCRIMINATOR {
+        return Err(SolanaCoreError::InvalidAccountDiscriminator);
+    }
+    Ok(())
+}
+
+impl<'a> Observations<'a> {
+    pub const SEED: &'static str = "observations";
+    // hard cap on the ring size, keeps searches and the account size bounded
+    pub const MAX_CARDINALITY: u16 = 1024;
+
+    #[inline(always)]
+    pub fn space(cardinality: u16) -> usize {
+        ObservationsHeader::LEN + cardinality as usize * Observation::LEN
+    }
+
+    #[inline(always)]
+    pub(crate) fn from_bytes(bytes: &'a [u8]) -> Result<Self, SolanaCoreError> {
+        let len = slot_count(bytes)?;
+        let (header, entries) = bytes.split_at(ObservationsHeader::LEN);
+        // Both types are byte arrays only (alignment 1) and the lengths were checked above.
+        Ok(Self {
+            header: unsafe { &*(header.as_ptr() as *const ObservationsHeader) },
+            entries: unsafe { core::slice::from_raw_parts(entries.as_ptr() as *const Observation, len) },
+        })
+    }
+
+    #[inline(always)]
+    pub(crate) fn from_bytes_mut(bytes: &'a mut [u8]) -> Result<ObservationsMut<'a>, SolanaCoreError> {
+        let len = slot_count(bytes)?;
+        let (header, entries) = bytes.split_at_mut(ObservationsHeader::LEN);
+        // Both types are byte arrays only (alignment 1) and the lengths were checked above.
+        Ok(ObservationsMut {
+            header: unsafe { &mut *(header.as_mut_ptr() as *mut ObservationsHeader) },
+            entries: unsafe { core::slice::from_raw_parts_mut(entries.as_mut_ptr() as *mut Observation, len) },
+        })
+    }
+
+    /// Loads the observations of `pool`, checking owner, discriminator and pool binding.
+    #[inline(always)]
+    pub fn load(account: &'a AccountInfo, pool: &Pubkey) -> Result<Self, SolanaCoreError> {
+        check_account(account)?;
+        let observations = Self::from_bytes(unsafe { account.borrow_data_unchecked() })?;
+        if observations.header.pool != *pool {
+            return Err(SolanaCoreError::InvalidAccountData);
+        }
+        Ok(observations)
+    }
+
+    /// Writable [`Observations::load`], also checking the account is writable.
+    ///
+    /// # Safety
+    ///
+    /// See [`load_acc_mut`](crate::utils::load_acc_mut): no other reference to the account's data may be live.
+    #[inline(always)]
+    pub unsafe fn load_mut(account: &'a AccountInfo, pool: &Pubkey) -> Result<ObservationsMut<'a>, SolanaCoreError> {
+        if !account.is_writable() {
+            return Err(SolanaCoreError::AccountNotWritable);
+        }
+        check_account(account)?;
+        let observations = Self::from_bytes_mut(unsafe { account.borrow_mut_data_unchecked() })?;
+        if observations.header.pool != *pool {
+            return Err(SolanaCoreError::InvalidAccountData);
+        }
+        Ok(observations)
+    }
+
+    /// Initializes a freshly created, zeroed account with a first observation.
+    #[inline(always)]
+    pub fn init(
+        account: &'a AccountInfo,
+        pool: &Pubkey,
+        bump: u8,
+        first: &PriceObservation,
+    ) -> Result<ObservationsMut<'a>, SolanaCoreError> {
+        let bytes = unsafe { account.borrow_mut_data_unchecked() };
+        if bytes.len() < DISCRIMINATOR_LEN {
+            return Err(SolanaCoreError::InvalidAccountData);
+        }
+        if bytes[..DISCRIMINATOR_LEN] != [0u8; DISCRIMINATOR_LEN] {
+            return Err(SolanaCoreError::AccountAlreadyInitialized);
+        }
+        let cardinality = ((bytes.len() - ObservationsHeader::LEN) / Observation::LEN) as u16;
+        bytes[..DISCRIMINATOR_LEN].copy_from_slice(&ObservationsHeader::DISCRIMINATOR);
+        bytes[ObservationsHeader::LEN - 2..ObservationsHeader::LEN].copy_from_slice(&cardinality.to_le_bytes());
+
+        let observations = Self::from_bytes_mut(bytes)?;
+        observations.header.pool = *pool;
+        observations.header.bump = bump;
+        observations.header.index = 0u16.to_le_bytes();
+        observations.header.cardinality = 1u16.to_le_bytes();
+        observations.entries[0].set(first);
+        Ok(observations)
+    }
+
+    #[inline(always)]
+    pub fn index(&self) -> u16 {
+        u16::from_le_bytes(self.header.index)
+    }
+
+    #[inline(always)]
+    pub fn cardinality(&self) -> u16 {
+        u16::from_le_bytes(self.header.cardinality)
+    }
+
+    #[inline(always)]
+    pub fn cardinality_next(&self) -> u16 {
+        u16::from_le_bytes(self.header.cardinality_next)
+    }
+
+    #[inline(always)]
+    pub fn latest(&self) -> PriceObservation {
+        self.entries[self.index() as usize].get()
+    }
+
+    // i-th observation counting from the oldest one in the ring
+    #[inline(always)]
+    fn at(&self, i: u16) -> PriceObservation {
+        let cardinality = self.cardinality();
+        let oldest = (self.index() + 1) % cardinality;
+        let slot = (oldest + i) % cardinality;
+        self.entries[slot as usize].get()
+    }
+
+    /// Accumulators at `target`, interpolated between the recorded observations and
+    /// `current` (the pool's own accumulators at `current.timestamp`).
+    pub fn observe_at(&self, target: i64, current: &PriceObservation) -> Result<PriceObservation, SolanaCoreError> {
+        if target > current.timestamp {
+            return Err(SolanaCoreError::MathError);
+        }
+        let latest = self.latest();
+        if target >= latest.timestamp {
+            return Ok(interpolate(&latest, current, target));
+        }
+
+        // Slots that were never written are zeroed; until the ring has wrapped, the
+        // oldest observation is slot 0.
+        let cardinality = self.cardinality();
+        let mut low = if self.at(0).timestamp == 0 {
+            cardinality - self.index() - 1
+        } else {
+            0
+        };
+        if target < self.at(low).timestamp {
+            return Err(SolanaCoreError::ObservationTooOld);
+        }
+
+        // binary search for the last observation at or before `target`
+        let mut high = cardinality - 1;
+        while low < high {
+            let mid = low + (high - low + 1) / 2;
+            if self.at(mid).timestamp <= target {
+                low = mid;
+            } else {
+                high = mid - 1;
+            }
+        }
+        let before = self.at(low);
+        let after = self.at(low + 1);
+        Ok(interpolate(&before, &after, target))
+    }
+}
+
+impl ObservationsMut<'_> {
+    /// Read-only view of the same account.
+    #[inline(always)]
+    pub fn view(&self) -> Observations<'_> {
+        Observations { header: self.header, entries: self.entries }
+    }
+
+    /// Records `observation`; at most one observation per second is kept.
+    #[inline(always)]
+    pub fn write(&mut self, observation: &PriceObservation) {
+        let view = self.view();
+        if observation.timestamp <= view.latest().timestamp {
+            return;
+        }
+        let (index, mut cardinality, cardinality_next) = (view.index(), view.cardinality(), view.cardinality_next());
+        // grown slots join the ring only once the write index reaches the end of it
+        if index == cardinality - 1 && cardinality_next > cardinality {
+            cardinality = cardinality_next;
+            self.header.cardinality = cardinality.to_le_bytes();
+        }
+        let next = (index + 1) % cardinality;
+        self.entries[next as usize].set(observation);
+        self.header.index = next.to_le_bytes();
+    }
+}
+
+// Linear interpolation of the accumulators between two observations.
+#[inline(always)]
+fn interpolate(before: &PriceObservation, after: &PriceObservation, target: i64) -> PriceObservation {
+    let span = (after.timestamp - before.timestamp) as u128;
+    let elapsed = (target - before.timestamp) as u128;
+    if span == 0 || elapsed == 0 {
+        return PriceObservation { timestamp: target, ..*before };
+    }
+    let step = |start: u128, end: u128| start.wrapping_add(end.wrapping_sub(start) / span * elapsed);
+    PriceObservation {
+        timestamp: target,
+        price_0_cumulative: step(before.price_0_cumulative, after.price_0_cumulative),
+        price_1_cumulative: step(before.price_1_cumulative, after.price_1_cumulative),
+        liquidity_cumulative: step(before.liquidity_cumulative, after.liquidity_cumulative),
+    }
+}
@@ -1,59 +1,463 @@
+use core::mem::offset_of;
 use pinocchio::{
     account_info::AccountInfo,
-    program_error::ProgramError,
     pubkey::{self, Pubkey},
-    ProgramResult,
 };
 use shank::ShankAccount;
 use crate::utils::validate_pda;
-use crate::{utils::{load_acc_mut_unchecked, DataLen}, errors::SolanaCoreError, states::InitPool};
+use crate::{
+    utils::{
+        decay_volatility, dynamic_fee_bps, init_acc_mut_unchecked, load_acc, load_acc_mut, AccountDiscriminator,
+        CurveType, DataLen, SwapDirection, BPS_DENOMINATOR, MAX_AMP, MAX_FEES_BPS, MIN_AMP, MIN_WEIGHT_BPS,
+    },
+    errors::SolanaCoreError,
+    states::InitPool,
+};
 
+// Every field is a byte array, so `Pool` has an alignment of 1 and no implicit
+// padding: the on-chain layout is exactly the field order below, independent of
+// the compiler's alignment rules. Integers are stored little-endian and accessed
+// through the getters/setters in `impl Pool`.
 #[repr(C)]
 #[derive(Clone, Copy, Debug, PartialEq, ShankAccount)]
 pub struct Pool {
+    //account type tag, see `AccountDiscriminator`
+    pub discriminator: [u8; 8],
+
     //token_mint
     pub token_0_mint: Pubkey,
     pub token_1_mint: Pubkey,
 
-    //token_amount
-    pub token_0_amount: u64,
-    pub token_1_amount: u64,
-
     //vault
     pub vault_0: Pubkey,
     pub vault_1: Pubkey,
 
+    //The mint address of the lp_token
+    pub lp_mint: Pubkey,
+
+    //token_amount (u64, little-endian)
+    pub token_0_amount: [u8; 8],
+    pub token_1_amount: [u8; 8],
+
+    //fee tier(unit: bps, u64 little-endian), part of the pool seeds and never changed;
+    // the rate actually charged is `fee_rate_bps`
+    // eg. 50 bps = 0.5 %(50/10_000)
+    pub fees_bps: [u8; 8],
+
     //bump for the pool(program derived address)
     pub pool_bump: u8,
 
-    //fees(unit: bps)
-    // eg. 50 bps = 0.05 %(50/100)
-    pub fees_bps: u64,
+    pub lp_bump: u8,
 
-    //The mint address of the lp_token
-    pub lp_mint: Pubkey,
+    //TWAP oracle accumulators (u128 Q64.64 price x seconds, little-endian, wrapping)
+    // price_0 = token_1 per token_0, price_1 = token_0 per token_1
+    pub price_0_cumulative: [u8; 16],
+    pub price_1_cumulative: [u8; 16],
 
-    pub lp_bump: u8,
+    //unix timestamp of the last accumulator update (i64, little-endian)
+    pub last_update_timestamp: [u8; 8],
+
+    //sqrt(reserve_0 * reserve_1) x seconds (u128, little-endian, wrapping)
+    pub liquidity_cumulative: [u8; 16],
+
+    //`CurveType` as u8
+    pub curve_type: u8,
+
+    //stable-swap amplification coefficient, ramped linearly from `amp_initial` at
+    // `amp_start_timestamp` to `amp_target` at `amp_end_timestamp` (u64/i64, little-endian)
+    pub amp_initial: [u8; 8],
+    pub amp_target: [u8; 8],
+    pub amp_start_timestamp: [u8; 8],
+    pub amp_end_timestamp: [u8; 8],
+
+    //weighted pools: weight of token_0 in bps, token_1 carries `10_000 - weight_0_bps`
+    // (u64, little-endian, zero for other curves)
+    pub weight_0_bps: [u8; 8],
+
+    //non-zero between `flash_loan` and its `flash_repay`; swaps and liquidity changes
+    // are rejected meanwhile
+    pub flash_loan_lock: u8,
+
+    //outstanding flash loan principal (u64, little-endian)
+    pub flash_loan_amount_0: [u8; 8],
+    pub flash_loan_amount_1: [u8; 8],
+
+    //dynamic fee mode, on while `max_fee_bps` is non-zero: the fee follows recent volatility
+    // between `min_fee_bps` and `max_fee_bps` instead of `fees_bps` (u64, little-endian)
+    pub min_fee_bps: [u8; 8],
+    pub max_fee_bps: [u8; 8],
+
+    //recent price impact in bps, see `utils::dynamic_fee` (u64, little-endian), and the
+    // unix timestamp it was last decayed to (i64, little-endian)
+    pub volatility_accumulator: [u8; 8],
+    pub volatility_timestamp: [u8; 8],
+
+    //live fee rate, starts at the tier and is changed by `update_pool_fee` (bps, u64 little-endian)
+    pub fee_rate_bps: [u8; 8],
+
+    //fee rate scheduled by a delayed `update_pool_fee` (bps, u64 little-endian) and the unix
+    // timestamp it applies from (i64, little-endian), zero when nothing is scheduled
+    pub pending_fee_rate_bps: [u8; 8],
+    pub pending_fee_rate_timestamp: [u8; 8],
 }
 
 impl DataLen for Pool {
     const LEN: usize = core::mem::size_of::<Pool>();
 }
 
+impl AccountDiscriminator for Pool {
+    // sha256("account:Pool")[..8]
+    const DISCRIMINATOR: [u8; 8] = [241, 154, 109, 4, 17, 177, 109, 188];
+}
+
+const _: () = assert!(core::mem::align_of::<Pool>() == 1);
+const _: () = assert!(Pool::LEN == 364);
+
 impl Pool {
     //pool_seed
     pub const POOL_SEED: &'static str = "pool";
     //lp_seed
     pub const LP_SEED: &'static str = "lp";
 
+    // Byte offsets of each field inside the account data. Use these for
+    // `getProgramAccounts` memcmp filters, e.g. all pools for a given mint:
+    // `{ memcmp: { offset: Pool::TOKEN_0_MINT_OFFSET, bytes: <mint> } }`.
+    pub const DISCRIMINATOR_OFFSET: usize = offset_of!(Pool, discriminator);
+    pub const TOKEN_0_MINT_OFFSET: usize = offset_of!(Pool, token_0_mint);
+    pub const TOKEN_1_MINT_OFFSET: usize = offset_of!(Pool, token_1_mint);
+    pub const VAULT_0_OFFSET: usize = offset_of!(Pool, vault_0);
+    pub const VAULT_1_OFFSET: usize = offset_of!(Pool, vault_1);
+    pub const LP_MINT_OFFSET: usize = offset_of!(Pool, lp_mint);
+    pub const TOKEN_0_AMOUNT_OFFSET: usize = offset_of!(Pool, token_0_amount);
+    pub const TOKEN_1_AMOUNT_OFFSET: usize = offset_of!(Pool, token_1_amount);
+    pub const FEES_BPS_OFFSET: usize = offset_of!(Pool, fees_bps);
+    pub const POOL_BUMP_OFFSET: usize = offset_of!(Pool, pool_bump);
+    pub const LP_BUMP_OFFSET: usize = offset_of!(Pool, lp_bump);
+    pub const PRICE_0_CUMULATIVE_OFFSET: usize = offset_of!(Pool, price_0_cumulative);
+    pub const PRICE_1_CUMULATIVE_OFFSET: usize = offset_of!(Pool, price_1_cumulative);
+    pub const LAST_UPDATE_TIMESTAMP_OFFSET: usize = offset_of!(Pool, last_update_timestamp);
+    pub const LIQUIDITY_CUMULATIVE_OFFSET: usize = offset_of!(Pool, liquidity_cumulative);
+    pub const CURVE_TYPE_OFFSET: usize = offset_of!(Pool, curve_type);
+    pub const AMP_INITIAL_OFFSET: usize = offset_of!(Pool, amp_initial);
+    pub const AMP_TARGET_OFFSET: usize = offset_of!(Pool, amp_target);
+    pub const AMP_START_TIMESTAMP_OFFSET: usize = offset_of!(Pool, amp_start_timestamp);
+    pub const AMP_END_TIMESTAMP_OFFSET: usize = offset_of!(Pool, amp_end_timestamp);
+    pub const WEIGHT_0_BPS_OFFSET: usize = offset_of!(Pool, weight_0_bps);
+    pub const FLASH_LOAN_LOCK_OFFSET: usize = offset_of!(Pool, flash_loan_lock);
+    pub const FLASH_LOAN_AMOUNT_0_OFFSET: usize = offset_of!(Pool, flash_loan_amount_0);
+    pub const FLASH_LOAN_AMOUNT_1_OFFSET: usize = offset_of!(Pool, flash_loan_amount_1);
+    pub const MIN_FEE_BPS_OFFSET: usize = offset_of!(Pool, min_fee_bps);
+    pub const MAX_FEE_BPS_OFFSET: usize = offset_of!(Pool, max_fee_bps);
+    pub const VOLATILITY_ACCUMULATOR_OFFSET: usize = offset_of!(Pool, volatility_accumulator);
+    pub const VOLATILITY_TIMESTAMP_OFFSET: usize = offset_of!(Pool, volatility_timestamp);
+    pub const FEE_RATE_BPS_OFFSET: usize = offset_of!(Pool, fee_rate_bps);
+    pub const PENDING_FEE_RATE_BPS_OFFSET: usize = offset_of!(Pool, pending_fee_rate_bps);
+    pub const PENDING_FEE_RATE_TIMESTAMP_OFFSET: usize = offset_of!(Pool, pending_fee_rate_timestamp);
+
+    /// Loads a pool account, checking that it is owned by this program and tagged as a `Pool`.
     #[inline(always)]
-    pub fn init_pool(pool: &AccountInfo, ix_data: &InitPool) -> ProgramResult {
-        let pool_acc = unsafe { 
-            match load_acc_mut_unchecked::<Pool>(pool.borrow_mut_data_unchecked()) {
-                Ok(acc) => acc,
-                Err(_) => return Err(SolanaCoreError::InvalidAccountData.into()),
-            }
-        };
+    pub fn load(pool: &AccountInfo) -> Result<&Pool, SolanaCoreError> {
+        load_acc::<Pool>(pool)
+    }
+
+    /// Same as [`Pool::load`], but also requires the account to be writable.
+    ///
+    /// # Safety
+    ///
+    /// See [`load_acc_mut`](crate::utils::load_acc_mut): no other reference to the account's data may be live.
+    #[inline(always)]
+    pub unsafe fn load_mut(pool: &AccountInfo) -> Result<&mut Pool, SolanaCoreError> {
+        unsafe { load_acc_mut::<Pool>(pool) }
+    }
+
+    #[inline(always)]
+    pub fn token_0_amount(&self) -> u64 {
+        u64::from_le_bytes(self.token_0_amount)
+    }
+
+    #[inline(always)]
+    pub fn set_token_0_amount(&mut self, amount: u64) {
+        self.token_0_amount = amount.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn token_1_amount(&self) -> u64 {
+        u64::from_le_bytes(self.token_1_amount)
+    }
+
+    #[inline(always)]
+    pub fn set_token_1_amount(&mut self, amount: u64) {
+        self.token_1_amount = amount.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn fees_bps(&self) -> u64 {
+        u64::from_le_bytes(self.fees_bps)
+    }
+
+    #[inline(always)]
+    pub fn set_fees_bps(&mut self, fees_bps: u64) {
+        self.fees_bps = fees_bps.to_le_bytes();
+    }
+
+    /// Fee rate in force at `now`, a scheduled rate included once it is due.
+    #[inline(always)]
+    pub fn fee_rate_bps(&self, now: i64) -> u64 {
+        let activation = i64::from_le_bytes(self.pending_fee_rate_timestamp);
+        if activation != 0 && now >= activation {
+            u64::from_le_bytes(self.pending_fee_rate_bps)
+        } else {
+            u64::from_le_bytes(self.fee_rate_bps)
+        }
+    }
+
+    /// Changes the fee rate to `fee_rate_bps` from `activation` on, right away when it is not
+    /// after `now`. A rate already due is settled first; one still pending is replaced.
+    #[inline(always)]
+    pub fn set_fee_rate(&mut self, fee_rate_bps: u64, now: i64, activation: i64) {
+        if activation <= now {
+            self.fee_rate_bps = fee_rate_bps.to_le_bytes();
+            self.pending_fee_rate_bps = [0; 8];
+            self.pending_fee_rate_timestamp = [0; 8];
+        } else {
+            self.fee_rate_bps = self.fee_rate_bps(now).to_le_bytes();
+            self.pending_fee_rate_bps = fee_rate_bps.to_le_bytes();
+            self.pending_fee_rate_timestamp = activation.to_le_bytes();
+        }
+    }
+
+    #[inline(always)]
+    pub fn price_0_cumulative(&self) -> u128 {
+        u128::from_le_bytes(self.price_0_cumulative)
+    }
+
+    #[inline(always)]
+    pub fn set_price_0_cumulative(&mut self, value: u128) {
+        self.price_0_cumulative = value.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn price_1_cumulative(&self) -> u128 {
+        u128::from_le_bytes(self.price_1_cumulative)
+    }
+
+    #[inline(always)]
+    pub fn set_price_1_cumulative(&mut self, value: u128) {
+        self.price_1_cumulative = value.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn last_update_timestamp(&self) -> i64 {
+        i64::from_le_bytes(self.last_update_timestamp)
+    }
+
+    #[inline(always)]
+    pub fn set_last_update_timestamp(&mut self, timestamp: i64) {
+        self.last_update_timestamp = timestamp.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn liquidity_cumulative(&self) -> u128 {
+        u128::from_le_bytes(self.liquidity_cumulative)
+    }
+
+    #[inline(always)]
+    pub fn set_liquidity_cumulative(&mut self, value: u128) {
+        self.liquidity_cumulative = value.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn curve_type(&self) -> Result<CurveType, SolanaCoreError> {
+        CurveType::try_from(self.curve_type)
+    }
+
+    #[inline(always)]
+    pub fn amp_target(&self) -> u64 {
+        u64::from_le_bytes(self.amp_target)
+    }
+
+    #[inline(always)]
+    pub fn amp_start_timestamp(&self) -> i64 {
+        i64::from_le_bytes(self.amp_start_timestamp)
+    }
+
+    #[inline(always)]
+    pub fn amp_end_timestamp(&self) -> i64 {
+        i64::from_le_bytes(self.amp_end_timestamp)
+    }
+
+    #[inline(always)]
+    pub fn weight_0_bps(&self) -> u64 {
+        u64::from_le_bytes(self.weight_0_bps)
+    }
+
+    /// Amplification coefficient in effect at `now`.
+    #[inline(always)]
+    pub fn amp(&self, now: i64) -> u64 {
+        let (initial, target) = (u64::from_le_bytes(self.amp_initial), self.amp_target());
+        let (start, end) = (self.amp_start_timestamp(), self.amp_end_timestamp());
+        if now >= end || end <= start {
+            return target;
+        }
+        if now <= start {
+            return initial;
+        }
+        let elapsed = (now - start) as u128;
+        let duration = (end - start) as u128;
+        if target > initial {
+            initial + ((target - initial) as u128 * elapsed / duration) as u64
+        } else {
+            initial - ((initial - target) as u128 * elapsed / duration) as u64
+        }
+    }
+
+    /// Starts a linear ramp from the current coefficient to `target` over `[now, end]`.
+    #[inline(always)]
+    pub fn set_amp_ramp(&mut self, now: i64, target: u64, end: i64) {
+        self.amp_initial = self.amp(now).to_le_bytes();
+        self.amp_target = target.to_le_bytes();
+        self.amp_start_timestamp = now.to_le_bytes();
+        self.amp_end_timestamp = end.to_le_bytes();
+    }
+
+    #[inline(always)]
+    pub fn min_fee_bps(&self) -> u64 {
+        u64::from_le_bytes(self.min_fee_bps)
+    }
+
+    #[inline(always)]
+    pub fn max_fee_bps(&self) -> u64 {
+        u64::from_le_bytes(self.max_fee_bps)
+    }
+
+    #[inline(always)]
+    pub fn dynamic_fee_enabled(&self) -> bool {
+        self.max_fee_bps() != 0
+    }
+
+    /// Turns dynamic fee mode on with the given bounds, or off when `max_fee_bps` is zero.
+    #[inline(always)]
+    pub fn set_dynamic_fee(&mut self, min_fee_bps: u64, max_fee_bps: u64, now: i64) {
+        self.min_fee_bps = min_fee_bps.to_le_bytes();
+        self.max_fee_bps = max_fee_bps.to_le_bytes();
+        self.volatility_accumulator = [0; 8];
+        self.volatility_timestamp = now.to_le_bytes();
+    }
+
+    /// Volatility accumulator decayed to `now`.
+    #[inline(always)]
+    pub fn volatility(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(i64::from_le_bytes(self.volatility_timestamp));
+        decay_volatility(u64::from_le_bytes(self.volatility_accumulator), elapsed)
+    }
+
+    /// Adds a swap's price impact to the volatility accumulator.
+    #[inline(always)]
+    pub fn record_volatility(&mut self, now: i64, price_impact_bps: u64) {
+        let volatility = self.volatility(now).saturating_add(price_impact_bps);
+        self.volatility_accumulator = volatility.to_le_bytes();
+        self.volatility_timestamp = now.to_le_bytes();
+    }
+
+    /// Fee rate charged on a swap at `now`: the dynamic fee in dynamic fee mode, the live fee rate otherwise.
+    #[inline(always)]
+    pub fn effective_fees_bps(&self, now: i64) -> u64 {
+        if self.dynamic_fee_enabled() {
+            dynamic_fee_bps(self.min_fee_bps(), self.max_fee_bps(), self.volatility(now))
+        } else {
+            self.fee_rate_bps(now)
+        }
+    }
+
+    /// Rejects the pool while a flash loan is outstanding.
+    #[inline(always)]
+    pub fn check_unlocked(&self) -> Result<(), SolanaCoreError> {
+        if self.flash_loan_lock != 0 {
+            return Err(SolanaCoreError::PoolLocked);
+        }
+        Ok(())
+    }
+
+    /// Locks the pool for a flash loan of `amount_0` / `amount_1` out of the reserves.
+    #[inline(always)]
+    pub fn start_flash_loan(&mut self, amount_0: u64, amount_1: u64) -> Result<(), SolanaCoreError> {
+        self.check_unlocked()?;
+        if amount_0 > self.token_0_amount() || amount_1 > self.token_1_amount() {
+            return Err(SolanaCoreError::InsufficientLiquidity);
+        }
+        self.flash_loan_lock = 1;
+        self.flash_loan_amount_0 = amount_0.to_le_bytes();
+        self.flash_loan_amount_1 = amount_1.to_le_bytes();
+        Ok(())
+    }
+
+    /// Principal of the outstanding flash loan; errors when there is none.
+    #[inline(always)]
+    pub fn outstanding_flash_loan(&self) -> Result<(u64, u64), SolanaCoreError> {
+        if self.flash_loan_lock == 0 {
+            return Err(SolanaCoreError::InvalidInstructionData);
+        }
+        Ok((u64::from_le_bytes(self.flash_loan_amount_0), u64::from_le_bytes(self.flash_loan_amount_1)))
+    }
+
+    /// Unlocks the pool, returning the principal of the outstanding flash loan.
+    #[inline(always)]
+    pub fn finish_flash_loan(&mut self) -> Result<(u64, u64), SolanaCoreError> {
+        let amounts = self.outstanding_flash_loan()?;
+        self.flash_loan_lock = 0;
+        self.flash_loan_amount_0 = [0; 8];
+        self.flash_loan_amount_1 = [0; 8];
+        Ok(amounts)
+    }
+
+    /// Direction of a swap from `mint_in` to `mint_out`; errors unless they are this pool's two mints.
+    #[inline(always)]
+    pub fn direction(&self, mint_in: &Pubkey, mint_out: &Pubkey) -> Result<SwapDirection, SolanaCoreError> {
+        if *mint_in == self.token_0_mint && *mint_out == self.token_1_mint {
+            Ok(SwapDirection::ZeroForOne)
+        } else if *mint_in == self.token_1_mint && *mint_out == self.token_0_mint {
+            Ok(SwapDirection::OneForZero)
+        } else {
+            Err(SolanaCoreError::InvalidAccountData)
+        }
+    }
+
+    /// `(vault_in, vault_out)` of a swap in `direction`.
+    #[inline(always)]
+    pub fn vaults(&self, direction: SwapDirection) -> (&Pubkey, &Pubkey) {
+        match direction {
+            SwapDirection::ZeroForOne => (&self.vault_0, &self.vault_1),
+            SwapDirection::OneForZero => (&self.vault_1, &self.vault_0),
+        }
+    }
+
+    /// Errors unless the mints, vaults and LP mint passed to a deposit or withdrawal are this pool's.
+    #[inline(always)]
+    pub fn check_liquidity_accounts(
+        &self,
+        token_0_mint: &Pubkey,
+        token_1_mint: &Pubkey,
+        vault_0: &Pubkey,
+        vault_1: &Pubkey,
+        lp_mint: &Pubkey,
+    ) -> Result<(), SolanaCoreError> {
+        if *token_0_mint != self.token_0_mint
+            || *token_1_mint != self.token_1_mint
+            || *vault_0 != self.vault_0
+            || *vault_1 != self.vault_1
+            || *lp_mint != self.lp_mint
+        {
+            return Err(SolanaCoreError::InvalidAccountData);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn init_pool(pool: &AccountInfo, ix_data: &InitPool, now: i64) -> Result<(), SolanaCoreError> {
+        if !pool.is_owned_by(&crate::ID) {
+            return Err(SolanaCoreError::InvalidOwner);
+        }
+        let pool_acc = unsafe { init_acc_mut_unchecked::<Pool>(pool.borrow_mut_data_unchecked()) }?;
 
         let fees_bps_bytes = ix_data.fees_bps.to_le_bytes();
         let pool_seeds: &[&[u8]] = &[
@@ -63,35 +467,61 @@ impl Pool {
             &fees_bps_bytes,
             &[ix_data.pool_bump],
         ];
-        
+
         match validate_pda(pool_seeds, pool.key()) {
             Ok(_) => {},
-            Err(_) => return Err(SolanaCoreError::PdaMismatch.into()),
+            Err(_) => return Err(SolanaCoreError::PdaMismatch),
         }
 
         pool_acc.token_0_mint = ix_data.token_0_mint;
         pool_acc.token_1_mint = ix_data.token_1_mint;
-        pool_acc.token_0_amount = ix_data.token_0_amount;
-        pool_acc.token_1_amount = ix_data.token_1_amount;
+        pool_acc.set_token_0_amount(ix_data.token_0_amount);
+        pool_acc.set_token_1_amount(ix_data.token_1_amount);
         pool_acc.vault_0 = ix_data.vault_0;
         pool_acc.vault_1 = ix_data.vault_1;
         pool_acc.pool_bump = ix_data.pool_bump;
-        
-        if ix_data.fees_bps > 500 {
-            return Err(SolanaCoreError::InvalidInstructionData.into());
+
+        if ix_data.fees_bps > MAX_FEES_BPS {
+            return Err(SolanaCoreError::InvalidInstructionData);
         }
-        pool_acc.fees_bps = ix_data.fees_bps;
-        
+        pool_acc.set_fees_bps(ix_data.fees_bps);
+        pool_acc.set_fee_rate(ix_data.fees_bps, now, now);
+
         // lp_mint(is_pda)
         let lp_seeds = &[Self::LP_SEED.as_bytes(), pool.key().as_ref(), &[ix_data.lp_bump]];
         match validate_pda(lp_seeds, &ix_data.lp_mint) {
             Ok(_) => {},
-            Err(_) => return Err(SolanaCoreError::PdaMismatch.into()),
+            Err(_) => return Err(SolanaCoreError::PdaMismatch),
         }
-        
+
         pool_acc.lp_mint = ix_data.lp_mint;
         pool_acc.lp_bump = ix_data.lp_bump;
-        
+
+        // accumulators start at zero; the first trade accrues from pool creation
+        pool_acc.set_last_update_timestamp(now);
+
+        let curve_type = CurveType::try_from(ix_data.curve_type)?;
+        let amp_valid = match curve_type {
+            CurveType::StableSwap => (MIN_AMP..=MAX_AMP).contains(&ix_data.amp),
+            _ => ix_data.amp == 0,
+        };
+        if !amp_valid {
+            return Err(SolanaCoreError::InvalidAmp);
+        }
+        let weight_valid = match curve_type {
+            CurveType::Weighted => (MIN_WEIGHT_BPS..=BPS_DENOMINATOR - MIN_WEIGHT_BPS).contains(&ix_data.weight_0_bps),
+            _ => ix_data.weight_0_bps == 0,
+        };
+        if !weight_valid {
+            return Err(SolanaCoreError::InvalidWeight);
+        }
+        pool_acc.curve_type = ix_data.curve_type;
+        pool_acc.amp_initial = ix_data.amp.to_le_bytes();
+        pool_acc.amp_target = ix_data.amp.to_le_bytes();
+        pool_acc.amp_start_timestamp = now.to_le_bytes();
+        pool_acc.amp_end_timestamp = now.to_le_bytes();
+        pool_acc.weight_0_bps = ix_data.weight_0_bps.to_le_bytes();
+
         Ok(())
     }
 }
@@ -0,0 +1,143 @@
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey};
+use shank::ShankAccount;
+use crate::{
+    errors::SolanaCoreError,
+    utils::{init_acc_mut_unchecked, load_acc_mut, AccountDiscriminator, DataLen, U256},
+};
+
+// Liquidity of one owner in one tick range of a `ClPool`, PDA
+// `["position", pool, owner, tick_lower, tick_upper]`.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, ShankAccount)]
+pub struct Position {
+    //account type tag, see `AccountDiscriminator`
+    pub discriminator: [u8; 8],
+
+    pub pool: Pubkey,
+
+    //only the owner may change the position or collect its fees
+    pub owner: Pubkey,
+
+    //range bounds (i32, little-endian)
+    pub tick_lower: [u8; 4],
+    pub tick_upper: [u8; 4],
+
+    //(u128, little-endian)
+    pub liquidity: [u8; 16],
+
+    //fee growth inside the range at the last update, Q64.64 (u128, little-endian)
+    pub fee_growth_inside_0_last: [u8; 16],
+    pub fee_growth_inside_1_last: [u8; 16],
+
+    //fees earned and not yet collected (u64, little-endian)
+    pub tokens_owed_0: [u8; 8],
+    pub tokens_owed_1: [u8; 8],
+
+    pub bump: u8,
+}
+
+impl DataLen for Position {
+    const LEN: usize = core::mem::size_of::<Position>();
+}
+
+impl AccountDiscriminator for Position {
+    // sha256("account:Position")[..8]
+    const DISCRIMINATOR: [u8; 8] = [170, 188, 143, 228, 122, 64, 247, 208];
+}
+
+const _: () = assert!(core::mem::align_of::<Position>() == 1);
+const _: () = assert!(Position::LEN == 145);
+
+impl Position {
+    pub const SEED: &'static str = "position";
+
+    /// Loads a position of `pool`, checking owner, discriminator and pool binding.
+    ///
+    /// # Safety
+    ///
+    /// See [`load_acc_mut`](crate::utils::load_acc_mut): no other reference to the account's data may be live.
+    #[inline(always)]
+    pub unsafe fn load_mut<'a>(position: &'a AccountInfo, pool: &Pubkey) -> Result<&'a mut Position, SolanaCoreError> {
+        let position = unsafe { load_acc_mut::<Position>(position) }?;
+        if position.pool != *pool {
+            return Err(SolanaCoreError::InvalidAccountData);
+        }
+        Ok(position)
+    }
+
+    /// Initializes a freshly created, zeroed position account.
+    #[inline(always)]
+    pub fn init(
+        position: &AccountInfo,
+        pool: &Pubkey,
+        owner: &Pubkey,
+        tick_lower: i32,
+        tick_upper: i32,
+        bump: u8,
+    ) -> Result<(), SolanaCoreError> {
+        let position = unsafe { init_acc_mut_unchecked::<Position>(position.borrow_mut_data_unchecked()) }?;
+        position.pool = *pool;
+        position.owner = *owner;
+        position.tick_lower = tick_lower.to_le_bytes();
+        position.tick_upper = tick_upper.to_le_bytes();
+        position.bump = bump;
+        Ok(())
+    }
+
+    /// Requires `owner` to be the position owner and to have signed.
+    #[inline(always)]
+    pub fn check_owner(&self, owner: &AccountInfo) -> Result<(), SolanaCoreError> {
+        if !owner.is_signer() || *owner.key() != self.owner {
+            return Err(SolanaCoreError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn tick_lower(&self) -> i32 {
+        i32::from_le_bytes(self.tick_lower)
+    }
+
+    #[inline(always)]
+    pub fn tick_upper(&self) -> i32 {
+        i32::from_le_bytes(self.tick_upper)
+    }
+
+    #[inline(always)]
+    pub fn liquidity(&self) -> u128 {
+        u128::from_le_bytes(self.liquidity)
+    }
+
+    #[inline(always)]
+    pub fn tokens_owed(&self) -> (u64, u64) {
+        (u64::from_le_bytes(self.tokens_owed_0), u64::from_le_bytes(self.tokens_owed_1))
+    }
+
+    #[inline(always)]
+    pub fn set_tokens_owed(&mut self, owed_0: u64, owed_1: u64) {
+        self.tokens_owed_0 = owed_0.to_le_bytes();
+        self.tokens_owed_1 = owed_1.to_le_bytes();
+    }
+
+    /// Credits fees earned since the last update, then applies `liquidity_delta`.
+    pub fn update(&mut self, liquidity_delta: i128, fee_growth_inside: (u128, u128)) -> Result<(), SolanaCoreError> {
+        let liquidity = self.liquidity();
+        let earned = |inside: u128, last: [u8; 16]| {
+            let growth = inside.wrapping_sub(u128::from_le_bytes(last));
+            // Q64.64 growth per unit of liquidity; a u64 owed balance saturates rather than wraps
+            let fees = (U256::from(growth) * U256::from(liquidity)) >> 64;
+            if fees > U256::from(u64::MAX) { u64::MAX } else { fees.as_u64() }
+        };
+        let (owed_0, owed_1) = self.tokens_owed();
+        self.set_tokens_owed(
+            owed_0.saturating_add(earned(fee_growth_inside.0, self.fee_growth_inside_0_last)),
+            owed_1.saturating_add(earned(fee_growth_inside.1, self.fee_growth_inside_1_last)),
+        );
+        self.fee_growth_inside_0_last = fee_growth_inside.0.to_le_bytes();
+        self.fee_growth_inside_1_last = fee_growth_inside.1.to_le_bytes();
+
+        let liquidity = liquidity.checked_add_signed(liquidity_delta).ok_or(SolanaCoreError::InsufficientLiquidity)?;
+        self.liquidity = liquidity.to_le_bytes();
+        Ok(())
+    }
+}
@@ -0,0 +1,99 @@
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey};
+use shank::ShankAccount;
+use crate::{
+    errors::SolanaCoreError,
+    utils::{init_acc_mut_unchecked, load_acc_mut, AccountDiscriminator, DataLen},
+};
+
+// Referral stats of one referrer in one input mint, PDA `["referrer", owner, mint]`.
+// Optional: referred swaps pay the referrer's token account either way and only
+// accumulate here when the account is passed.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, ShankAccount)]
+pub struct Referrer {
+    //account type tag, see `AccountDiscriminator`
+    pub discriminator: [u8; 8],
+
+    //owner of the referrer's token accounts
+    pub owner: Pubkey,
+
+    //input mint the stats are counted in
+    pub mint: Pubkey,
+
+    //(u64, little-endian)
+    pub swap_count: [u8; 8],
+
+    //gross input of the referred swaps and referral fees received, in `mint` units (u64, little-endian)
+    pub volume: [u8; 8],
+    pub fees_earned: [u8; 8],
+
+    pub bump: u8,
+}
+
+impl DataLen for Referrer {
+    const LEN: usize = core::mem::size_of::<Referrer>();
+}
+
+impl AccountDiscriminator for Referrer {
+    // sha256("account:Referrer")[..8]
+    const DISCRIMINATOR: [u8; 8] = [99, 150, 214, 66, 111, 120, 49, 126];
+}
+
+const _: () = assert!(core::mem::align_of::<Referrer>() == 1);
+const _: () = assert!(Referrer::LEN == 97);
+
+impl Referrer {
+    pub const SEED: &'static str = "referrer";
+
+    /// Loads the stats of `owner` in `mint`, checking owner, discriminator and binding.
+    ///
+    /// # Safety
+    ///
+    /// See [`load_acc_mut`](crate::utils::load_acc_mut): no other reference to the account's data may be live.
+    #[inline(always)]
+    pub unsafe fn load_mut<'a>(
+        referrer: &'a AccountInfo,
+        owner: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<&'a mut Referrer, SolanaCoreError> {
+        let referrer = unsafe { load_acc_mut::<Referrer>(referrer) }?;
+        if referrer.owner != *owner || referrer.mint != *mint {
+            return Err(SolanaCoreError::InvalidAccountData);
+        }
+        Ok(referrer)
+    }
+
+    /// Initializes a freshly created, zeroed referrer account.
+    #[inline(always)]
+    pub fn init(referrer: &AccountInfo, owner: &Pubkey, mint: &Pubkey, bump: u8) -> Result<(), SolanaCoreError> {
+        let referrer = unsafe { init_acc_mut_unchecked::<Referrer>(referrer.borrow_mut_data_unchecked()) }?;
+        referrer.owner = *owner;
+        referrer.mint = *mint;
+        referrer.bump = bump;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn swap_count(&self) -> u64 {
+        u64::from_le_bytes(self.swap_count)
+    }
+
+    #[inline(always)]
+    pub fn volume(&self) -> u64 {
+        u64::from_le_bytes(self.volume)
+    }
+
+    #[inline(always)]
+    pub fn fees_earned(&self) -> u64 {
+        u64::from_le_bytes(self.fees_earned)
+    }
+
+    /// Counts one referred swap of `amount_in` that paid `referral_fee`. Saturates, the
+    /// stats are informational.
+    #[inline(always)]
+    pub fn record(&mut self, amount_in: u64, referral_fee: u64) {
+        self.swap_count = self.swap_count().saturating_add(1).to_le_bytes();
+        self.volume = self.volume().saturating_add(amount_in).to_le_bytes();
+        self.fees_earned = self.fees_earned().saturating_add(referral_fee).to_le_bytes();
+    }
+}
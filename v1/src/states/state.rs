@@ -1,55 +1,810 @@
 use pinocchio::pubkey::Pubkey;
-use crate::utils::DataLen;
+use crate::errors::SolanaCoreError;
+use crate::states::MAX_MULTI_POOL_TOKENS;
+use crate::utils::{ByteReader, ByteWriter, InstructionData, SwapDirection, SwapMode};
+
 // Init_pool
-    pub struct InitPool{
-        pub token_0_mint: Pubkey,
-        pub token_1_mint: Pubkey,
-        pub token_0_amount: u64,
-        pub token_1_amount: u64,
-        pub vault_0: Pubkey,
-        pub vault_1: Pubkey,
-        pub pool_bump: u8,
-        pub fees_bps: u64,
-        pub lp_mint:Pubkey,
-        pub lp_bump: u8
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InitPool {
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub token_0_amount: u64,
+    pub token_1_amount: u64,
+    pub vault_0: Pubkey,
+    pub vault_1: Pubkey,
+    pub pool_bump: u8,
+    pub fees_bps: u64,
+    pub lp_mint: Pubkey,
+    pub lp_bump: u8,
+    // `CurveType` as u8
+    pub curve_type: u8,
+    // stable-swap amplification coefficient, zero for other curves
+    pub amp: u64,
+    // weighted pools: token_0 weight in bps, zero for other curves
+    pub weight_0_bps: u64,
+}
+
+impl InstructionData for InitPool {
+    const LEN: usize = 32 + 32 + 8 + 8 + 32 + 32 + 1 + 8 + 32 + 1 + 1 + 8 + 8;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            token_0_mint: reader.read_pubkey()?,
+            token_1_mint: reader.read_pubkey()?,
+            token_0_amount: reader.read_u64()?,
+            token_1_amount: reader.read_u64()?,
+            vault_0: reader.read_pubkey()?,
+            vault_1: reader.read_pubkey()?,
+            pool_bump: reader.read_u8()?,
+            fees_bps: reader.read_u64()?,
+            lp_mint: reader.read_pubkey()?,
+            lp_bump: reader.read_u8()?,
+            curve_type: reader.read_u8()?,
+            amp: reader.read_u64()?,
+            weight_0_bps: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
     }
 
-    impl DataLen for InitPool {
-    const LEN: usize = core::mem::size_of::<InitPool>();
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_pubkey(&self.token_0_mint)?;
+        writer.write_pubkey(&self.token_1_mint)?;
+        writer.write_u64(self.token_0_amount)?;
+        writer.write_u64(self.token_1_amount)?;
+        writer.write_pubkey(&self.vault_0)?;
+        writer.write_pubkey(&self.vault_1)?;
+        writer.write_u8(self.pool_bump)?;
+        writer.write_u64(self.fees_bps)?;
+        writer.write_pubkey(&self.lp_mint)?;
+        writer.write_u8(self.lp_bump)?;
+        writer.write_u8(self.curve_type)?;
+        writer.write_u64(self.amp)?;
+        writer.write_u64(self.weight_0_bps)
     }
+}
 
+impl InitPool {
+    /// Rejects mint, vault or LP mint accounts that differ from the keys in the payload,
+    /// which `Pool::init_pool` stores as the pool's own.
+    pub fn check_accounts(
+        &self,
+        token_0_mint: &Pubkey,
+        token_1_mint: &Pubkey,
+        vault_0: &Pubkey,
+        vault_1: &Pubkey,
+        lp_mint: &Pubkey,
+    ) -> Result<(), SolanaCoreError> {
+        if *token_0_mint != self.token_0_mint
+            || *token_1_mint != self.token_1_mint
+            || *vault_0 != self.vault_0
+            || *vault_1 != self.vault_1
+            || *lp_mint != self.lp_mint
+        {
+            return Err(SolanaCoreError::InvalidAccountData);
+        }
+        Ok(())
+    }
+}
 
 // Liquidate_pool
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct LiquidatePool {
     pub pool_key: Pubkey,
     pub deposit_token_0_amount: u64,
     pub deposit_token_1_amount: u64,
 }
- 
-impl DataLen for LiquidatePool {
-    const LEN: usize = core::mem::size_of::<LiquidatePool>();
+
+impl InstructionData for LiquidatePool {
+    const LEN: usize = 32 + 8 + 8;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            pool_key: reader.read_pubkey()?,
+            deposit_token_0_amount: reader.read_u64()?,
+            deposit_token_1_amount: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
     }
 
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_pubkey(&self.pool_key)?;
+        writer.write_u64(self.deposit_token_0_amount)?;
+        writer.write_u64(self.deposit_token_1_amount)
+    }
+}
+
 // DeLiquidate_pool
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct DeliquidatePool {
     pub pool_key: Pubkey,
-    pub withdraw_token_0_amount:u64,
-    pub withdraw_token_1_amount:u64,  
+    pub withdraw_token_0_amount: u64,
+    pub withdraw_token_1_amount: u64,
+}
+
+impl InstructionData for DeliquidatePool {
+    const LEN: usize = 32 + 8 + 8;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            pool_key: reader.read_pubkey()?,
+            withdraw_token_0_amount: reader.read_u64()?,
+            withdraw_token_1_amount: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_pubkey(&self.pool_key)?;
+        writer.write_u64(self.withdraw_token_0_amount)?;
+        writer.write_u64(self.withdraw_token_1_amount)
+    }
+}
+
+// Swap
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Swap {
+    // input amount (fee included) for `ExactIn`, desired output for `ExactOut`
+    pub amount: u64,
+    // quoted counter amount: output for `ExactIn`, input for `ExactOut`
+    pub expected_amount: u64,
+    pub slippage_bps: u64,
+    pub direction: SwapDirection,
+    pub mode: SwapMode,
+}
+
+impl InstructionData for Swap {
+    const LEN: usize = 8 + 8 + 8 + 1 + 1;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            amount: reader.read_u64()?,
+            expected_amount: reader.read_u64()?,
+            slippage_bps: reader.read_u64()?,
+            direction: SwapDirection::try_from(reader.read_u8()?)?,
+            mode: SwapMode::try_from(reader.read_u8()?)?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u64(self.amount)?;
+        writer.write_u64(self.expected_amount)?;
+        writer.write_u64(self.slippage_bps)?;
+        writer.write_u8(self.direction as u8)?;
+        writer.write_u8(self.mode as u8)
+    }
+}
+
+// IncreaseObservationCardinality
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct IncreaseObservationCardinality {
+    pub cardinality_next: u16,
+    pub observations_bump: u8,
+}
+
+impl InstructionData for IncreaseObservationCardinality {
+    const LEN: usize = 2 + 1;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            cardinality_next: reader.read_u16()?,
+            observations_bump: reader.read_u8()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u16(self.cardinality_next)?;
+        writer.write_u8(self.observations_bump)
+    }
+}
+
+// ObserveTwap
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ObserveTwap {
+    // TWAP window ending now, in seconds
+    pub window_seconds: u64,
+}
+
+impl InstructionData for ObserveTwap {
+    const LEN: usize = 8;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            window_seconds: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        ByteWriter::new(dst).write_u64(self.window_seconds)
+    }
+}
+
+// GetPrice
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GetPrice {
+    // input amount (fee included) to quote; zero skips the quote
+    pub amount_in: u64,
+    pub direction: SwapDirection,
+}
+
+impl InstructionData for GetPrice {
+    const LEN: usize = 8 + 1;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            amount_in: reader.read_u64()?,
+            direction: SwapDirection::try_from(reader.read_u8()?)?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u64(self.amount_in)?;
+        writer.write_u8(self.direction as u8)
+    }
+}
+
+// InitConfig
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InitConfig {
+    pub admin: Pubkey,
+    pub config_bump: u8,
+    // bounds on the fee rate of existing pools, see `UpdatePoolFee`
+    pub min_fee_bps: u64,
+    pub max_fee_bps: u64,
+    // cap on the share of a swap fee paid to referrers, see `SwapReferral`
+    pub max_referral_fee_share_bps: u64,
+    // receives pool creation fees, see `SetPoolCreationFee`
+    pub treasury: Pubkey,
+}
+
+impl InstructionData for InitConfig {
+    const LEN: usize = 32 + 1 + 8 + 8 + 8 + 32;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            admin: reader.read_pubkey()?,
+            config_bump: reader.read_u8()?,
+            min_fee_bps: reader.read_u64()?,
+            max_fee_bps: reader.read_u64()?,
+            max_referral_fee_share_bps: reader.read_u64()?,
+            treasury: reader.read_pubkey()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_pubkey(&self.admin)?;
+        writer.write_u8(self.config_bump)?;
+        writer.write_u64(self.min_fee_bps)?;
+        writer.write_u64(self.max_fee_bps)?;
+        writer.write_u64(self.max_referral_fee_share_bps)?;
+        writer.write_pubkey(&self.treasury)
+    }
+}
+
+// RampAmp
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RampAmp {
+    pub target_amp: u64,
+    // seconds from now until `target_amp` is reached
+    pub ramp_duration: u64,
+}
+
+impl InstructionData for RampAmp {
+    const LEN: usize = 8 + 8;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            target_amp: reader.read_u64()?,
+            ramp_duration: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u64(self.target_amp)?;
+        writer.write_u64(self.ramp_duration)
+    }
+}
+
+// SetDynamicFee
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SetDynamicFee {
+    pub min_fee_bps: u64,
+    // zero turns dynamic fee mode off
+    pub max_fee_bps: u64,
+}
+
+impl InstructionData for SetDynamicFee {
+    const LEN: usize = 8 + 8;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            min_fee_bps: reader.read_u64()?,
+            max_fee_bps: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u64(self.min_fee_bps)?;
+        writer.write_u64(self.max_fee_bps)
+    }
+}
+
+// UpdatePoolFee
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UpdatePoolFee {
+    pub fee_rate_bps: u64,
+    // seconds from now until the new rate applies, zero for immediately
+    pub delay: u64,
+}
+
+impl InstructionData for UpdatePoolFee {
+    const LEN: usize = 8 + 8;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            fee_rate_bps: reader.read_u64()?,
+            delay: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u64(self.fee_rate_bps)?;
+        writer.write_u64(self.delay)
+    }
+}
+
+// InitClPool
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InitClPool {
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    pub fees_bps: u64,
+    pub tick_spacing: u16,
+    // initial sqrt(price) as Q64.64
+    pub sqrt_price: u128,
+    pub pool_bump: u8,
+}
+
+impl InstructionData for InitClPool {
+    const LEN: usize = 32 + 32 + 8 + 2 + 16 + 1;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            token_0_mint: reader.read_pubkey()?,
+            token_1_mint: reader.read_pubkey()?,
+            fees_bps: reader.read_u64()?,
+            tick_spacing: reader.read_u16()?,
+            sqrt_price: reader.read_u128()?,
+            pool_bump: reader.read_u8()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_pubkey(&self.token_0_mint)?;
+        writer.write_pubkey(&self.token_1_mint)?;
+        writer.write_u64(self.fees_bps)?;
+        writer.write_u16(self.tick_spacing)?;
+        writer.write_u128(self.sqrt_price)?;
+        writer.write_u8(self.pool_bump)
+    }
 }
 
+// InitTickArray
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InitTickArray {
+    pub start_tick_index: i32,
+    pub tick_array_bump: u8,
+}
+
+impl InstructionData for InitTickArray {
+    const LEN: usize = 4 + 1;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            start_tick_index: reader.read_i32()?,
+            tick_array_bump: reader.read_u8()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_i32(self.start_tick_index)?;
+        writer.write_u8(self.tick_array_bump)
+    }
+}
+
+// OpenPosition
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OpenPosition {
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub position_bump: u8,
+}
+
+impl InstructionData for OpenPosition {
+    const LEN: usize = 4 + 4 + 1;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            tick_lower: reader.read_i32()?,
+            tick_upper: reader.read_i32()?,
+            position_bump: reader.read_u8()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_i32(self.tick_lower)?;
+        writer.write_i32(self.tick_upper)?;
+        writer.write_u8(self.position_bump)
+    }
+}
+
+// IncreaseLiquidity / DecreaseLiquidity
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ModifyLiquidity {
+    pub liquidity: u128,
+    // maximum amounts to deposit when increasing, minimum amounts to receive when decreasing
+    pub amount_0_limit: u64,
+    pub amount_1_limit: u64,
+}
+
+impl InstructionData for ModifyLiquidity {
+    const LEN: usize = 16 + 8 + 8;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            liquidity: reader.read_u128()?,
+            amount_0_limit: reader.read_u64()?,
+            amount_1_limit: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u128(self.liquidity)?;
+        writer.write_u64(self.amount_0_limit)?;
+        writer.write_u64(self.amount_1_limit)
+    }
+}
+
+// InitMultiPool
+// The mints come from the accounts, in ascending order; entries past `token_count` are zero.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InitMultiPool {
+    pub fees_bps: u64,
+    // `CurveType` as u8, `StableSwap` or `Weighted`
+    pub curve_type: u8,
+    // stable-swap amplification coefficient, zero for weighted pools
+    pub amp: u64,
+    pub token_count: u8,
+    pub pool_bump: u8,
+    pub lp_bump: u8,
+    // weighted pools: per-token weights in bps summing to 10_000, zero for stable-swap pools
+    pub weights_bps: [u64; MAX_MULTI_POOL_TOKENS],
+    // initial reserves
+    pub amounts: [u64; MAX_MULTI_POOL_TOKENS],
+}
+
+impl InstructionData for InitMultiPool {
+    const LEN: usize = 8 + 1 + 8 + 1 + 1 + 1 + 8 * MAX_MULTI_POOL_TOKENS + 8 * MAX_MULTI_POOL_TOKENS;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            fees_bps: reader.read_u64()?,
+            curve_type: reader.read_u8()?,
+            amp: reader.read_u64()?,
+            token_count: reader.read_u8()?,
+            pool_bump: reader.read_u8()?,
+            lp_bump: reader.read_u8()?,
+            weights_bps: reader.read_u64_array()?,
+            amounts: reader.read_u64_array()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u64(self.fees_bps)?;
+        writer.write_u8(self.curve_type)?;
+        writer.write_u64(self.amp)?;
+        writer.write_u8(self.token_count)?;
+        writer.write_u8(self.pool_bump)?;
+        writer.write_u8(self.lp_bump)?;
+        writer.write_u64_array(&self.weights_bps)?;
+        writer.write_u64_array(&self.amounts)
+    }
+}
+
+// MultiSwap
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MultiSwap {
+    // indices into the pool's tokens
+    pub token_in: u8,
+    pub token_out: u8,
+    // input amount (fee included) for `ExactIn`, desired output for `ExactOut`
+    pub amount: u64,
+    // quoted counter amount: output for `ExactIn`, input for `ExactOut`
+    pub expected_amount: u64,
+    pub slippage_bps: u64,
+    pub mode: SwapMode,
+}
+
+impl InstructionData for MultiSwap {
+    const LEN: usize = 1 + 1 + 8 + 8 + 8 + 1;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            token_in: reader.read_u8()?,
+            token_out: reader.read_u8()?,
+            amount: reader.read_u64()?,
+            expected_amount: reader.read_u64()?,
+            slippage_bps: reader.read_u64()?,
+            mode: SwapMode::try_from(reader.read_u8()?)?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u8(self.token_in)?;
+        writer.write_u8(self.token_out)?;
+        writer.write_u64(self.amount)?;
+        writer.write_u64(self.expected_amount)?;
+        writer.write_u64(self.slippage_bps)?;
+        writer.write_u8(self.mode as u8)
+    }
+}
+
+// MultiDeposit / MultiWithdraw
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MultiLiquidity {
+    // LP tokens to mint or burn
+    pub lp_amount: u64,
+    // per token: maximum to deposit when depositing, minimum to receive when withdrawing
+    pub amount_limits: [u64; MAX_MULTI_POOL_TOKENS],
+}
+
+impl InstructionData for MultiLiquidity {
+    const LEN: usize = 8 + 8 * MAX_MULTI_POOL_TOKENS;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            lp_amount: reader.read_u64()?,
+            amount_limits: reader.read_u64_array()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u64(self.lp_amount)?;
+        writer.write_u64_array(&self.amount_limits)
+    }
+}
+
+// MultiDepositSingle / MultiWithdrawSingle
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MultiSingleLiquidity {
+    // index into the pool's tokens
+    pub token: u8,
+    // token amount to deposit, or LP tokens to burn
+    pub amount: u64,
+    // minimum LP tokens to receive, or minimum token amount to receive
+    pub limit: u64,
+}
+
+impl InstructionData for MultiSingleLiquidity {
+    const LEN: usize = 1 + 8 + 8;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            token: reader.read_u8()?,
+            amount: reader.read_u64()?,
+            limit: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
 
-impl DataLen for DeliquidatePool {
-    const LEN: usize = core::mem::size_of::<DeliquidatePool>();
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u8(self.token)?;
+        writer.write_u64(self.amount)?;
+        writer.write_u64(self.limit)
     }
+}
+
+// RouteSwap
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RouteSwap {
+    // input of the first leg, fee included
+    pub amount_in: u64,
+    // checked once, on the output of the last leg
+    pub minimum_amount_out: u64,
+}
+
+impl InstructionData for RouteSwap {
+    const LEN: usize = 8 + 8;
 
-    pub struct Swap {
-        pub token_0_amount:u64,
-        pub expected_token_1_amount: u64,
-        pub slippage_bps:u64
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            amount_in: reader.read_u64()?,
+            minimum_amount_out: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
     }
 
-    
-impl DataLen for Swap {
-    
-    const LEN: usize = core::mem::size_of::<Swap>();
-}
\ No newline at end of file
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u64(self.amount_in)?;
+        writer.write_u64(self.minimum_amount_out)
+    }
+}
+
+// FlashLoan
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlashLoan {
+    // borrowed from vault_0 / vault_1, either may be zero
+    pub amount_0: u64,
+    pub amount_1: u64,
+}
+
+impl InstructionData for FlashLoan {
+    const LEN: usize = 8 + 8;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            amount_0: reader.read_u64()?,
+            amount_1: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u64(self.amount_0)?;
+        writer.write_u64(self.amount_1)
+    }
+}
+
+// SwapReferral: trailing payload of a referred `Swap`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SwapReferral {
+    // share of the swap fee paid to the referrer, capped by the config
+    pub fee_share_bps: u64,
+}
+
+impl InstructionData for SwapReferral {
+    const LEN: usize = 8;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            fee_share_bps: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u64(self.fee_share_bps)
+    }
+}
+
+// InitReferrer
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InitReferrer {
+    pub referrer_bump: u8,
+}
+
+impl InstructionData for InitReferrer {
+    const LEN: usize = 1;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            referrer_bump: reader.read_u8()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_u8(self.referrer_bump)
+    }
+}
+
+// SetPoolCreationFee
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SetPoolCreationFee {
+    // all zeros for a fee in lamports
+    pub mint: Pubkey,
+    // zero turns the fee off
+    pub amount: u64,
+}
+
+impl InstructionData for SetPoolCreationFee {
+    const LEN: usize = 32 + 8;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError> {
+        let mut reader = ByteReader::new(data);
+        let ix_data = Self {
+            mint: reader.read_pubkey()?,
+            amount: reader.read_u64()?,
+        };
+        reader.finish()?;
+        Ok(ix_data)
+    }
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError> {
+        let mut writer = ByteWriter::new(dst);
+        writer.write_pubkey(&self.mint)?;
+        writer.write_u64(self.amount)
+    }
+}
@@ -0,0 +1,257 @@
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey};
+use shank::ShankAccount;
+use crate::{
+    errors::SolanaCoreError,
+    utils::{init_acc_mut_unchecked, load_acc_mut, AccountDiscriminator, DataLen},
+};
+
+// `TICK_ARRAY_SIZE` consecutive usable ticks of a `ClPool`, PDA
+// `["tick_array", pool, start_tick_index]`. Array `i` covers ticks
+// `[start, start + TICK_ARRAY_SIZE * tick_spacing)` in steps of `tick_spacing`, with
+// `start` a multiple of `TICK_ARRAY_SIZE * tick_spacing`. A swap walks the arrays it
+// is given in order, so it can only cross ticks stored in them.
+
+pub const TICK_ARRAY_SIZE: usize = 64;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Tick {
+    //liquidity added when the price crosses this tick upwards (i128, little-endian)
+    pub liquidity_net: [u8; 16],
+
+    //total liquidity referencing this tick; zero means uninitialized (u128, little-endian)
+    pub liquidity_gross: [u8; 16],
+
+    //fee growth on the other side of this tick from the current price, Q64.64
+    // (u128, little-endian, wrapping)
+    pub fee_growth_outside_0: [u8; 16],
+    pub fee_growth_outside_1: [u8; 16],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, ShankAccount)]
+pub struct TickArray {
+    //account type tag, see `AccountDiscriminator`
+    pub discriminator: [u8; 8],
+
+    //pool these ticks belong to
+    pub pool: Pubkey,
+
+    //first tick of the array (i32, little-endian)
+    pub start_tick_index: [u8; 4],
+
+    pub bump: u8,
+
+    pub ticks: [Tick; TICK_ARRAY_SIZE],
+}
+
+impl DataLen for TickArray {
+    const LEN: usize = core::mem::size_of::<TickArray>();
+}
+
+impl AccountDiscriminator for TickArray {
+    // sha256("account:TickArray")[..8]
+    const DISCRIMINATOR: [u8; 8] = [69, 97, 189, 190, 110, 7, 66, 187];
+}
+
+const _: () = assert!(core::mem::align_of::<TickArray>() == 1);
+const _: () = assert!(TickArray::LEN == 8 + 32 + 4 + 1 + 64 * TICK_ARRAY_SIZE);
+
+impl Tick {
+    #[inline(always)]
+    pub fn liquidity_net(&self) -> i128 {
+        i128::from_le_bytes(self.liquidity_net)
+    }
+
+    #[inline(always)]
+    pub fn liquidity_gross(&self) -> u128 {
+        u128::from_le_bytes(self.liquidity_gross)
+    }
+
+    #[inline(always)]
+    pub fn fee_growth_outside(&self) -> (u128, u128) {
+        (u128::from_le_bytes(self.fee_growth_outside_0), u128::from_le_bytes(self.fee_growth_outside_1))
+    }
+
+    #[inline(always)]
+    pub fn is_initialized(&self) -> bool {
+        self.liquidity_gross() != 0
+    }
+
+    /// Adds `liquidity_delta` referenced by a position's lower (or `upper`) bound at `tick`.
+    /// Fee growth below a freshly initialized tick is attributed to the past, like Uniswap v3.
+    /// A tick left without liquidity keeps its data until [`Tick::clear_if_unused`].
+    pub fn update(
+        &mut self,
+        tick: i32,
+        tick_current: i32,
+        liquidity_delta: i128,
+        fee_growth_global: (u128, u128),
+        upper: bool,
+    ) -> Result<(), SolanaCoreError> {
+        let gross = self
+            .liquidity_gross()
+            .checked_add_signed(liquidity_delta)
+            .ok_or(SolanaCoreError::OverFlowDetected)?;
+        if !self.is_initialized() && tick <= tick_current {
+            self.fee_growth_outside_0 = fee_growth_global.0.to_le_bytes();
+            self.fee_growth_outside_1 = fee_growth_global.1.to_le_bytes();
+        }
+        let net_delta = if upper { liquidity_delta.checked_neg() } else { Some(liquidity_delta) };
+        let net = net_delta
+            .and_then(|delta| self.liquidity_net().checked_add(delta))
+            .ok_or(SolanaCoreError::OverFlowDetected)?;
+        self.liquidity_net = net.to_le_bytes();
+        self.liquidity_gross = gross.to_le_bytes();
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn clear_if_unused(&mut self) {
+        if !self.is_initialized() {
+            *self = Tick::default();
+        }
+    }
+
+    /// Flips the outside fee growth as the price crosses this tick; returns `liquidity_net`.
+    #[inline(always)]
+    pub fn cross(&mut self, fee_growth_global: (u128, u128)) -> i128 {
+        let (outside_0, outside_1) = self.fee_growth_outside();
+        self.fee_growth_outside_0 = fee_growth_global.0.wrapping_sub(outside_0).to_le_bytes();
+        self.fee_growth_outside_1 = fee_growth_global.1.wrapping_sub(outside_1).to_le_bytes();
+        self.liquidity_net()
+    }
+}
+
+/// Fee growth per unit of liquidity inside `[tick_lower, tick_upper)`, Q64.64 (wrapping).
+pub fn fee_growth_inside(
+    lower: &Tick,
+    upper: &Tick,
+    tick_lower: i32,
+    tick_upper: i32,
+    tick_current: i32,
+    fee_growth_global: (u128, u128),
+) -> (u128, u128) {
+    let (global_0, global_1) = fee_growth_global;
+    let (lower_0, lower_1) = lower.fee_growth_outside();
+    let (upper_0, upper_1) = upper.fee_growth_outside();
+    let (below_0, below_1) = if tick_current >= tick_lower {
+        (lower_0, lower_1)
+    } else {
+        (global_0.wrapping_sub(lower_0), global_1.wrapping_sub(lower_1))
+    };
+    let (above_0, above_1) = if tick_current < tick_upper {
+        (upper_0, upper_1)
+    } else {
+        (global_0.wrapping_sub(upper_0), global_1.wrapping_sub(upper_1))
+    };
+    (
+        global_0.wrapping_sub(below_0).wrapping_sub(above_0),
+        global_1.wrapping_sub(below_1).wrapping_sub(above_1),
+    )
+}
+
+impl TickArray {
+    pub const SEED: &'static str = "tick_array";
+
+    /// Loads a tick array of `pool`, checking owner, discriminator and pool binding.
+    ///
+    /// # Safety
+    ///
+    /// See [`load_acc_mut`](crate::utils::load_acc_mut): no other reference to the account's data may be live.
+    #[inline(always)]
+    pub unsafe fn load_mut<'a>(tick_array: &'a AccountInfo, pool: &Pubkey) -> Result<&'a mut TickArray, SolanaCoreError> {
+        let tick_array = unsafe { load_acc_mut::<TickArray>(tick_array) }?;
+        if tick_array.pool != *pool {
+            return Err(SolanaCoreError::InvalidTickArray);
+        }
+        Ok(tick_array)
+    }
+
+    /// Initializes a freshly created, zeroed tick array.
+    #[inline(always)]
+    pub fn init(tick_array: &AccountInfo, pool: &Pubkey, start_tick_index: i32, bump: u8) -> Result<(), SolanaCoreError> {
+        let tick_array = unsafe { init_acc_mut_unchecked::<TickArray>(tick_array.borrow_mut_data_unchecked()) }?;
+        tick_array.pool = *pool;
+        tick_array.start_tick_index = start_tick_index.to_le_bytes();
+        tick_array.bump = bump;
+        Ok(())
+    }
+
+    /// Number of ticks covered by one array.
+    #[inline(always)]
+    pub fn ticks_per_array(tick_spacing: u16) -> i32 {
+        TICK_ARRAY_SIZE as i32 * tick_spacing as i32
+    }
+
+    /// Start of the array holding `tick`.
+    #[inline(always)]
+    pub fn start_tick_index_for(tick: i32, tick_spacing: u16) -> i32 {
+        tick.div_euclid(Self::ticks_per_array(tick_spacing)) * Self::ticks_per_array(tick_spacing)
+    }
+
+    #[inline(always)]
+    pub fn start_tick_index(&self) -> i32 {
+        i32::from_le_bytes(self.start_tick_index)
+    }
+
+    // slot of `tick` in this array, if the array covers it
+    #[inline(always)]
+    fn slot(&self, tick: i32, tick_spacing: u16) -> Option<usize> {
+        let offset = tick.checked_sub(self.start_tick_index())?;
+        if offset < 0 || offset % tick_spacing as i32 != 0 || offset >= Self::ticks_per_array(tick_spacing) {
+            return None;
+        }
+        Some((offset / tick_spacing as i32) as usize)
+    }
+
+    #[inline(always)]
+    pub fn tick_mut(&mut self, tick: i32, tick_spacing: u16) -> Result<&mut Tick, SolanaCoreError> {
+        let slot = self.slot(tick, tick_spacing).ok_or(SolanaCoreError::InvalidTickArray)?;
+        Ok(&mut self.ticks[slot])
+    }
+
+    /// Both bounds of a position whose ticks live in this same array.
+    #[inline(always)]
+    pub fn tick_pair_mut(
+        &mut self,
+        tick_lower: i32,
+        tick_upper: i32,
+        tick_spacing: u16,
+    ) -> Result<(&mut Tick, &mut Tick), SolanaCoreError> {
+        let lower = self.slot(tick_lower, tick_spacing).ok_or(SolanaCoreError::InvalidTickArray)?;
+        let upper = self.slot(tick_upper, tick_spacing).ok_or(SolanaCoreError::InvalidTickArray)?;
+        if lower >= upper {
+            return Err(SolanaCoreError::InvalidTick);
+        }
+        let (below, above) = self.ticks.split_at_mut(upper);
+        Ok((&mut below[lower], &mut above[0]))
+    }
+
+    /// Next tick to move the price to from `tick_current` within this array: the nearest
+    /// initialized tick in the swap direction, or the array's last tick in that direction.
+    /// `None` when the array lies entirely behind the price.
+    pub fn next_tick(&self, tick_current: i32, tick_spacing: u16, zero_for_one: bool) -> Option<(i32, bool)> {
+        let spacing = tick_spacing as i32;
+        let start = self.start_tick_index();
+        let tick_at = |slot: usize| start + slot as i32 * spacing;
+        let offset = tick_current.checked_sub(start)?;
+        if zero_for_one {
+            // initialized tick at or below the current one
+            if offset < 0 {
+                return None;
+            }
+            let first = (offset / spacing).min(TICK_ARRAY_SIZE as i32 - 1) as usize;
+            let found = (0..=first).rev().find(|slot| self.ticks[*slot].is_initialized());
+            Some(found.map_or((start, false), |slot| (tick_at(slot), true)))
+        } else {
+            // initialized tick strictly above the current one
+            let first = if offset < 0 { 0 } else { (offset / spacing + 1) as usize };
+            if first >= TICK_ARRAY_SIZE {
+                return None;
+            }
+            let found = (first..TICK_ARRAY_SIZE).find(|slot| self.ticks[*slot].is_initialized());
+            Some(found.map_or((tick_at(TICK_ARRAY_SIZE - 1), false), |slot| (tick_at(slot), true)))
+        }
+    }
+}
@@ -1,33 +1,98 @@
+use pinocchio::account_info::AccountInfo;
 use pinocchio::program_error::ProgramError;
 use pinocchio::pubkey;
 use pinocchio::pubkey::Pubkey;
 use crate::errors::SolanaCoreError;
+use crate::utils::InstructionData;
 pub trait DataLen {
     const LEN: usize;
 }
 
+/// Length of the type tag stored in the first bytes of every program-owned account.
+pub const DISCRIMINATOR_LEN: usize = 8;
+
+/// Implemented by every account type owned by the program. The discriminator is
+/// written once at initialization and checked on every load, so an account of one
+/// type can never be reinterpreted as another, even when both have the same length.
+pub trait AccountDiscriminator {
+    const DISCRIMINATOR: [u8; DISCRIMINATOR_LEN];
+}
+
+#[inline(always)]
+fn check_discriminator<T: AccountDiscriminator>(bytes: &[u8]) -> Result<(), SolanaCoreError> {
+    if bytes[..DISCRIMINATOR_LEN] != T::DISCRIMINATOR {
+        return Err(SolanaCoreError::InvalidAccountDiscriminator);
+    }
+    Ok(())
+}
+
 #[inline(always)]
-pub unsafe fn load_acc_unchecked<T: DataLen>(bytes: &[u8]) -> Result<&T, SolanaCoreError> {
+pub unsafe fn load_acc_unchecked<T: DataLen + AccountDiscriminator>(bytes: &[u8]) -> Result<&T, SolanaCoreError> {
     if bytes.len() != T::LEN {
         return Err(SolanaCoreError::InvalidAccountData);
     }
+    check_discriminator::<T>(bytes)?;
     Ok(&*(bytes.as_ptr() as *const T))
 }
 
 #[inline(always)]
-pub unsafe fn load_acc_mut_unchecked<T: DataLen>(bytes: &mut [u8]) -> Result<&mut T, SolanaCoreError> {
+pub unsafe fn load_acc_mut_unchecked<T: DataLen + AccountDiscriminator>(bytes: &mut [u8]) -> Result<&mut T, SolanaCoreError> {
     if bytes.len() != T::LEN {
         return Err(SolanaCoreError::InvalidAccountData);
     }
+    check_discriminator::<T>(bytes)?;
     Ok(&mut *(bytes.as_mut_ptr() as *mut T))
 }
 
+/// Loads a freshly created account for initialization. The account must still be
+/// zeroed (no discriminator yet); the discriminator is written before returning.
 #[inline(always)]
-pub unsafe fn load_ix_data<T: DataLen>(bytes: &[u8]) -> Result<&T, SolanaCoreError> {
+pub unsafe fn init_acc_mut_unchecked<T: DataLen + AccountDiscriminator>(bytes: &mut [u8]) -> Result<&mut T, SolanaCoreError> {
     if bytes.len() != T::LEN {
-        return Err(SolanaCoreError::InvalidInstructionData.into());
+        return Err(SolanaCoreError::InvalidAccountData);
     }
-    Ok(&*(bytes.as_ptr() as *const T))
+    if bytes[..DISCRIMINATOR_LEN] != [0u8; DISCRIMINATOR_LEN] {
+        return Err(SolanaCoreError::AccountAlreadyInitialized);
+    }
+    bytes[..DISCRIMINATOR_LEN].copy_from_slice(&T::DISCRIMINATOR);
+    Ok(&mut *(bytes.as_mut_ptr() as *mut T))
+}
+
+/// Loads a program-owned account, checking owner, length and discriminator.
+#[inline(always)]
+pub fn load_acc<T: DataLen + AccountDiscriminator>(account: &AccountInfo) -> Result<&T, SolanaCoreError> {
+    if !account.is_owned_by(&crate::ID) {
+        return Err(SolanaCoreError::InvalidOwner);
+    }
+    unsafe { load_acc_unchecked::<T>(account.borrow_data_unchecked()) }
+}
+
+/// Mutable counterpart of [`load_acc`]; additionally requires the account to be writable.
+///
+/// # Safety
+///
+/// The data is borrowed without the runtime borrow flag, since a held `RefMut` would make
+/// every later CPI naming the account fail. No other reference to the same account's data
+/// may be live alongside the returned one: load each account mutably at most once per
+/// instruction, and not through [`load_acc`] at the same time.
+#[inline(always)]
+pub unsafe fn load_acc_mut<T: DataLen + AccountDiscriminator>(account: &AccountInfo) -> Result<&mut T, SolanaCoreError> {
+    if !account.is_owned_by(&crate::ID) {
+        return Err(SolanaCoreError::InvalidOwner);
+    }
+    if !account.is_writable() {
+        return Err(SolanaCoreError::AccountNotWritable);
+    }
+    unsafe { load_acc_mut_unchecked::<T>(account.borrow_mut_data_unchecked()) }
+}
+
+/// Decodes an instruction payload after checking it has exactly the expected length.
+#[inline(always)]
+pub fn load_ix_data<T: InstructionData>(bytes: &[u8]) -> Result<T, SolanaCoreError> {
+    if bytes.len() != T::LEN {
+        return Err(SolanaCoreError::InvalidInstructionData);
+    }
+    T::unpack(bytes)
 }
 
 #[inline(always)]
@@ -0,0 +1,157 @@
+use pinocchio::pubkey::Pubkey;
+use crate::errors::SolanaCoreError;
+
+/// Fixed-size little-endian encoding for instruction payloads.
+///
+/// Instruction data is never cast in place: the byte slice handed to the program
+/// has no alignment guarantee, so every payload is decoded field by field into an
+/// owned value. `pack_into` is the exact inverse and is what clients use to build
+/// instruction data.
+pub trait InstructionData: Sized {
+    /// Encoded size in bytes, excluding the instruction discriminator.
+    const LEN: usize;
+
+    fn unpack(data: &[u8]) -> Result<Self, SolanaCoreError>;
+
+    fn pack_into(&self, dst: &mut [u8]) -> Result<(), SolanaCoreError>;
+
+    fn pack(&self) -> Vec<u8> {
+        let mut data = vec![0u8; Self::LEN];
+        // `data` is exactly `LEN` bytes, so this cannot fail.
+        let _ = self.pack_into(&mut data);
+        data
+    }
+}
+
+/// Sequential little-endian reader over a byte slice.
+pub struct ByteReader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    #[inline(always)]
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    #[inline(always)]
+    pub fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N], SolanaCoreError> {
+        let end = self.offset.checked_add(N).ok_or(SolanaCoreError::InvalidInstructionData)?;
+        let bytes = self
+            .data
+            .get(self.offset..end)
+            .ok_or(SolanaCoreError::InvalidInstructionData)?;
+        self.offset = end;
+        let mut out = [0u8; N];
+        out.copy_from_slice(bytes);
+        Ok(out)
+    }
+
+    #[inline(always)]
+    pub fn read_u8(&mut self) -> Result<u8, SolanaCoreError> {
+        Ok(self.read_bytes::<1>()?[0])
+    }
+
+    #[inline(always)]
+    pub fn read_u16(&mut self) -> Result<u16, SolanaCoreError> {
+        Ok(u16::from_le_bytes(self.read_bytes::<2>()?))
+    }
+
+    #[inline(always)]
+    pub fn read_i32(&mut self) -> Result<i32, SolanaCoreError> {
+        Ok(i32::from_le_bytes(self.read_bytes::<4>()?))
+    }
+
+    #[inline(always)]
+    pub fn read_u64(&mut self) -> Result<u64, SolanaCoreError> {
+        Ok(u64::from_le_bytes(self.read_bytes::<8>()?))
+    }
+
+    #[inline(always)]
+    pub fn read_u128(&mut self) -> Result<u128, SolanaCoreError> {
+        Ok(u128::from_le_bytes(self.read_bytes::<16>()?))
+    }
+
+    #[inline(always)]
+    pub fn read_pubkey(&mut self) -> Result<Pubkey, SolanaCoreError> {
+        self.read_bytes::<32>()
+    }
+
+    #[inline(always)]
+    pub fn read_u64_array<const N: usize>(&mut self) -> Result<[u64; N], SolanaCoreError> {
+        let mut out = [0u64; N];
+        for value in out.iter_mut() {
+            *value = self.read_u64()?;
+        }
+        Ok(out)
+    }
+
+    /// Fails if any input is left unread, so trailing garbage is rejected.
+    #[inline(always)]
+    pub fn finish(self) -> Result<(), SolanaCoreError> {
+        if self.offset != self.data.len() {
+            return Err(SolanaCoreError::InvalidInstructionData);
+        }
+        Ok(())
+    }
+}
+
+/// Sequential little-endian writer into a byte slice.
+pub struct ByteWriter<'a> {
+    data: &'a mut [u8],
+    offset: usize,
+}
+
+impl<'a> ByteWriter<'a> {
+    #[inline(always)]
+    pub fn new(data: &'a mut [u8]) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    #[inline(always)]
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), SolanaCoreError> {
+        let end = self.offset.checked_add(bytes.len()).ok_or(SolanaCoreError::InvalidInstructionData)?;
+        self.data
+            .get_mut(self.offset..end)
+            .ok_or(SolanaCoreError::InvalidInstructionData)?
+            .copy_from_slice(bytes);
+        self.offset = end;
+        Ok(())
+    }
+
+    #[inline(always)]
+    pub fn write_u8(&mut self, value: u8) -> Result<(), SolanaCoreError> {
+        self.write_bytes(&[value])
+    }
+
+    #[inline(always)]
+    pub fn write_u16(&mut self, value: u16) -> Result<(), SolanaCoreError> {
+        self.write_bytes(&value.to_le_bytes())
+    }
+
+    #[inline(always)]
+    pub fn write_i32(&mut self, value: i32) -> Result<(), SolanaCoreError> {
+        self.write_bytes(&value.to_le_bytes())
+    }
+
+    #[inline(always)]
+    pub fn write_u64(&mut self, value: u64) -> Result<(), SolanaCoreError> {
+        self.write_bytes(&value.to_le_bytes())
+    }
+
+    #[inline(always)]
+    pub fn write_u128(&mut self, value: u128) -> Result<(), SolanaCoreError> {
+        self.write_bytes(&value.to_le_bytes())
+    }
+
+    #[inline(always)]
+    pub fn write_pubkey(&mut self, value: &Pubkey) -> Result<(), SolanaCoreError> {
+        self.write_bytes(value)
+    }
+
+    #[inline(always)]
+    pub fn write_u64_array(&mut self, values: &[u64]) -> Result<(), SolanaCoreError> {
+        values.iter().try_for_each(|value| self.write_u64(*value))
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{
+    errors::SolanaCoreError,
+    states::{fee_growth_inside, ClPool, Position, Tick, TickArray},
+    utils::{
+        amounts_for_liquidity, compute_swap_step, sqrt_price_at_tick, tick_at_sqrt_price, SwapDirection, SwapMode,
+        MAX_SQRT_PRICE, MAX_TICK, MIN_SQRT_PRICE, MIN_TICK,
+    },
+};
+
+// Concentrated-liquidity state transitions on `ClPool`, shared by the handlers and by
+// off-chain quoting on decoded copies of the accounts.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClSwapResult {
+    // gross input transferred by the user, fee included
+    pub amount_in: u64,
+    pub amount_out: u64,
+    // part of `amount_in` credited to in-range positions
+    pub fee: u64,
+}
+
+// Tick arrays must start with the one holding the current tick and follow each other
+// in the swap direction, so no initialized tick can be skipped.
+fn check_tick_arrays(pool: &ClPool, tick_arrays: &[&mut TickArray], zero_for_one: bool) -> Result<(), SolanaCoreError> {
+    let spacing = pool.tick_spacing();
+    let step = if zero_for_one { -TickArray::ticks_per_array(spacing) } else { TickArray::ticks_per_array(spacing) };
+    let mut expected = TickArray::start_tick_index_for(pool.tick_current(), spacing);
+    for tick_array in tick_arrays {
+        if tick_array.start_tick_index() != expected {
+            return Err(SolanaCoreError::InvalidTickArray);
+        }
+        expected += step;
+    }
+    Ok(())
+}
+
+/// Swaps `amount` (input for exact-in, output for exact-out) through `pool`, crossing the
+/// initialized ticks of `tick_arrays` on the way, and writes the new price, tick, liquidity
+/// and fee growth back to the pool. Fails if the arrays run out before the swap is filled.
+pub fn swap_through_ticks(
+    pool: &mut ClPool,
+    tick_arrays: &mut [&mut TickArray],
+    amount: u64,
+    direction: SwapDirection,
+    mode: SwapMode,
+) -> Result<ClSwapResult, SolanaCoreError> {
+    let zero_for_one = direction == SwapDirection::ZeroForOne;
+    let exact_in = mode == SwapMode::ExactIn;
+    if amount == 0 || tick_arrays.is_empty() {
+        return Err(SolanaCoreError::MathError);
+    }
+    check_tick_arrays(pool, tick_arrays, zero_for_one)?;
+
+    let (spacing, fees_bps) = (pool.tick_spacing(), pool.fees_bps());
+    let (mut sqrt_price, mut tick, mut liquidity) = (pool.sqrt_price(), pool.tick_current(), pool.liquidity());
+    let mut fee_growth = (pool.fee_growth_global_0(), pool.fee_growth_global_1());
+    let (mut remaining, mut calculated, mut fee) = (amount, 0u64, 0u64);
+    let mut index = 0;
+
+    while remaining > 0 {
+        let tick_array = tick_arrays.get_mut(index).ok_or(SolanaCoreError::InvalidTickArray)?;
+        let Some((tick_next, initialized)) = tick_array.next_tick(tick, spacing, zero_for_one) else {
+            index += 1;
+            continue;
+        };
+        if (zero_for_one && sqrt_price <= MIN_SQRT_PRICE) || (!zero_for_one && sqrt_price >= MAX_SQRT_PRICE) {
+            return Err(SolanaCoreError::InsufficientLiquidity);
+        }
+        let tick_next = tick_next.clamp(MIN_TICK, MAX_TICK);
+        let sqrt_price_target = sqrt_price_at_tick(tick_next)?;
+
+        let step = compute_swap_step(sqrt_price, sqrt_price_target, liquidity, remaining, fees_bps, exact_in)?;
+        let step_in = step.amount_in.checked_add(step.fee).ok_or(SolanaCoreError::OverFlowDetected)?;
+        let (consumed, produced) = if exact_in { (step_in, step.amount_out) } else { (step.amount_out, step_in) };
+        remaining = remaining.checked_sub(consumed).ok_or(SolanaCoreError::MathError)?;
+        calculated = calculated.checked_add(produced).ok_or(SolanaCoreError::OverFlowDetected)?;
+        fee += step.fee;
+
+        // fees accrue to the liquidity in range during this step, in the input token
+        if liquidity > 0 {
+            let growth = ((step.fee as u128) << 64) / liquidity;
+            if zero_for_one {
+                fee_growth.0 = fee_growth.0.wrapping_add(growth);
+            } else {
+                fee_growth.1 = fee_growth.1.wrapping_add(growth);
+            }
+        }
+
+        if step.sqrt_price_next == sqrt_price_target {
+            if initialized {
+                let liquidity_net = tick_array.tick_mut(tick_next, spacing)?.cross(fee_growth);
+                let liquidity_net = if zero_for_one { -liquidity_net } else { liquidity_net };
+                liquidity = liquidity.checked_add_signed(liquidity_net).ok_or(SolanaCoreError::OverFlowDetected)?;
+            }
+            tick = if zero_for_one { tick_next - 1 } else { tick_next };
+        } else if step.sqrt_price_next != sqrt_price {
+            tick = tick_at_sqrt_price(step.sqrt_price_next)?;
+        }
+        sqrt_price = step.sqrt_price_next;
+    }
+
+    let (amount_in, amount_out) = if exact_in { (amount, calculated) } else { (calculated, amount) };
+    if amount_out == 0 {
+        return Err(SolanaCoreError::MathError);
+    }
+
+    pool.set_sqrt_price(sqrt_price);
+    pool.set_tick_current(tick);
+    pool.set_liquidity(liquidity);
+    pool.set_fee_growth_global(fee_growth.0, fee_growth.1);
+    Ok(ClSwapResult { amount_in, amount_out, fee })
+}
+
+/// Adds (positive) or removes (negative) `liquidity_delta` to `position`, whose bounds are
+/// `lower` and `upper`, and credits the fees earned since its last update. Returns the token
+/// amounts to deposit (rounded up) or to withdraw (rounded down).
+pub fn modify_position(
+    pool: &mut ClPool,
+    position: &mut Position,
+    lower: &mut Tick,
+    upper: &mut Tick,
+    liquidity_delta: i128,
+) -> Result<(u64, u64), SolanaCoreError> {
+    let (tick_lower, tick_upper, tick_current) = (position.tick_lower(), position.tick_upper(), pool.tick_current());
+    let fee_growth = (pool.fee_growth_global_0(), pool.fee_growth_global_1());
+
+    if liquidity_delta != 0 {
+        lower.update(tick_lower, tick_current, liquidity_delta, fee_growth, false)?;
+        upper.update(tick_upper, tick_current, liquidity_delta, fee_growth, true)?;
+    }
+    let inside = fee_growth_inside(lower, upper, tick_lower, tick_upper, tick_current, fee_growth);
+    position.update(liquidity_delta, inside)?;
+    lower.clear_if_unused();
+    upper.clear_if_unused();
+
+    if (tick_lower..tick_upper).contains(&tick_current) {
+        let liquidity = pool
+            .liquidity()
+            .checked_add_signed(liquidity_delta)
+            .ok_or(SolanaCoreError::InsufficientLiquidity)?;
+        pool.set_liquidity(liquidity);
+    }
+
+    amounts_for_liquidity(
+        pool.sqrt_price(),
+        tick_current,
+        tick_lower,
+        tick_upper,
+        liquidity_delta.unsigned_abs(),
+        liquidity_delta > 0,
+    )
+}
@@ -0,0 +1,418 @@
+use crate::{
+    errors::SolanaCoreError,
+    states::Pool,
+    utils::{
+        compute_d, constant_product_in, constant_product_out, isqrt, lp_to_burn, lp_to_mint, stable_spot_price,
+        stable_swap_in, stable_swap_out, weighted_invariant, weighted_spot_price, weighted_swap_in, weighted_swap_out,
+        SwapDirection, BPS_DENOMINATOR,
+    },
+};
+
+// Swap and liquidity math behind every pool type. Handlers and quotes only talk to
+// `Curve`, so adding a pool type means adding an implementation here and a
+// `CurveType` tag, not touching `swap`, `liquidate_pool` or `deliquidate_pool`.
+//
+// Amounts passed to the swap functions are net of fees; fees are applied by `quote`.
+// Every function rounds in the pool's favour.
+
+pub trait Curve {
+    // `direction` tells which pool side `reserve_in` is; only asymmetric curves use it.
+
+    /// Output received for `amount_in`.
+    fn swap_exact_in(
+        &self,
+        amount_in: u64,
+        reserve_in: u64,
+        reserve_out: u64,
+        direction: SwapDirection,
+    ) -> Result<u64, SolanaCoreError>;
+
+    /// Input needed to receive exactly `amount_out`.
+    fn swap_exact_out(
+        &self,
+        amount_out: u64,
+        reserve_in: u64,
+        reserve_out: u64,
+        direction: SwapDirection,
+    ) -> Result<u64, SolanaCoreError>;
+
+    /// Marginal price of the input token in output-token units, as `(numerator, denominator)`
+    /// with both parts fitting in 64 bits.
+    fn spot_price(
+        &self,
+        reserve_in: u64,
+        reserve_out: u64,
+        direction: SwapDirection,
+    ) -> Result<(u128, u128), SolanaCoreError>;
+
+    /// LP tokens minted to the creator of a pool seeded with `amount_0` / `amount_1`.
+    fn initial_lp(&self, amount_0: u64, amount_1: u64) -> Result<u64, SolanaCoreError>;
+
+    /// `(lp_to_mint, amount_0, amount_1)` for a deposit of at most `amount_0` / `amount_1`.
+    /// Proportional deposits keep the invariant per LP token unchanged on any curve
+    /// whose invariant scales linearly with the reserves.
+    #[inline(always)]
+    fn deposit(
+        &self,
+        amount_0: u64,
+        amount_1: u64,
+        reserve_0: u64,
+        reserve_1: u64,
+        lp_supply: u64,
+    ) -> Result<(u64, u64, u64), SolanaCoreError> {
+        lp_to_mint(amount_0, amount_1, reserve_0, reserve_1, lp_supply)
+    }
+
+    /// `(lp_to_burn, amount_0, amount_1)` for a withdrawal of at most `amount_0` / `amount_1`.
+    #[inline(always)]
+    fn withdraw(
+        &self,
+        amount_0: u64,
+        amount_1: u64,
+        reserve_0: u64,
+        reserve_1: u64,
+        lp_supply: u64,
+    ) -> Result<(u64, u64, u64), SolanaCoreError> {
+        Ok(lp_to_burn(amount_0, amount_1, reserve_0, reserve_1, lp_supply))
+    }
+}
+
+/// x * y = k
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConstantProduct;
+
+impl Curve for ConstantProduct {
+    #[inline(always)]
+    fn swap_exact_in(
+        &self,
+        amount_in: u64,
+        reserve_in: u64,
+        reserve_out: u64,
+        _direction: SwapDirection,
+    ) -> Result<u64, SolanaCoreError> {
+        constant_product_out(amount_in, reserve_in, reserve_out)
+    }
+
+    #[inline(always)]
+    fn swap_exact_out(
+        &self,
+        amount_out: u64,
+        reserve_in: u64,
+        reserve_out: u64,
+        _direction: SwapDirection,
+    ) -> Result<u64, SolanaCoreError> {
+        constant_product_in(amount_out, reserve_in, reserve_out)
+    }
+
+    #[inline(always)]
+    fn spot_price(
+        &self,
+        reserve_in: u64,
+        reserve_out: u64,
+        _direction: SwapDirection,
+    ) -> Result<(u128, u128), SolanaCoreError> {
+        Ok((reserve_out as u128, reserve_in as u128))
+    }
+
+    #[inline(always)]
+    fn initial_lp(&self, amount_0: u64, amount_1: u64) -> Result<u64, SolanaCoreError> {
+        Ok(isqrt(amount_0 as u128 * amount_1 as u128) as u64)
+    }
+}
+
+/// Curve stable-swap invariant, see `utils::stable`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StableSwap {
+    pub amp: u64,
+}
+
+impl Curve for StableSwap {
+    #[inline(always)]
+    fn swap_exact_in(
+        &self,
+        amount_in: u64,
+        reserve_in: u64,
+        reserve_out: u64,
+        _direction: SwapDirection,
+    ) -> Result<u64, SolanaCoreError> {
+        stable_swap_out(self.amp, amount_in, reserve_in, reserve_out)
+    }
+
+    #[inline(always)]
+    fn swap_exact_out(
+        &self,
+        amount_out: u64,
+        reserve_in: u64,
+        reserve_out: u64,
+        _direction: SwapDirection,
+    ) -> Result<u64, SolanaCoreError> {
+        stable_swap_in(self.amp, amount_out, reserve_in, reserve_out)
+    }
+
+    #[inline(always)]
+    fn spot_price(
+        &self,
+        reserve_in: u64,
+        reserve_out: u64,
+        _direction: SwapDirection,
+    ) -> Result<(u128, u128), SolanaCoreError> {
+        stable_spot_price(self.amp, reserve_in, reserve_out)
+    }
+
+    #[inline(always)]
+    fn initial_lp(&self, amount_0: u64, amount_1: u64) -> Result<u64, SolanaCoreError> {
+        u64::try_from(compute_d(self.amp, amount_0, amount_1)?).map_err(|_| SolanaCoreError::OverFlowDetected)
+    }
+}
+
+/// x + y = k: trades 1:1 until one side is empty.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConstantSum;
+
+impl Curve for ConstantSum {
+    #[inline(always)]
+    fn swap_exact_in(
+        &self,
+        amount_in: u64,
+        _reserve_in: u64,
+        reserve_out: u64,
+        _direction: SwapDirection,
+    ) -> Result<u64, SolanaCoreError> {
+        if amount_in >= reserve_out {
+            return Err(SolanaCoreError::MathError);
+        }
+        Ok(amount_in)
+    }
+
+    #[inline(always)]
+    fn swap_exact_out(
+        &self,
+        amount_out: u64,
+        _reserve_in: u64,
+        reserve_out: u64,
+        _direction: SwapDirection,
+    ) -> Result<u64, SolanaCoreError> {
+        if amount_out >= reserve_out {
+            return Err(SolanaCoreError::MathError);
+        }
+        Ok(amount_out)
+    }
+
+    #[inline(always)]
+    fn spot_price(
+        &self,
+        _reserve_in: u64,
+        _reserve_out: u64,
+        _direction: SwapDirection,
+    ) -> Result<(u128, u128), SolanaCoreError> {
+        Ok((1, 1))
+    }
+
+    #[inline(always)]
+    fn initial_lp(&self, amount_0: u64, amount_1: u64) -> Result<u64, SolanaCoreError> {
+        amount_0.checked_add(amount_1).ok_or(SolanaCoreError::OverFlowDetected)
+    }
+}
+
+/// Balancer-style weighted product `x^w_0 * y^w_1 = k`, see `utils::weighted`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Weighted {
+    // weight of token_0 in bps; token_1 carries the rest
+    pub weight_0_bps: u64,
+}
+
+impl Weighted {
+    /// `(weight_in, weight_out)` in bps for a swap in `direction`.
+    #[inline(always)]
+    fn weights(&self, direction: SwapDirection) -> Result<(u64, u64), SolanaCoreError> {
+        let weight_1_bps = BPS_DENOMINATOR.checked_sub(self.weight_0_bps).ok_or(SolanaCoreError::InvalidWeight)?;
+        Ok(match direction {
+            SwapDirection::ZeroForOne => (self.weight_0_bps, weight_1_bps),
+            SwapDirection::OneForZero => (weight_1_bps, self.weight_0_bps),
+        })
+    }
+}
+
+impl Curve for Weighted {
+    #[inline(always)]
+    fn swap_exact_in(
+        &self,
+        amount_in: u64,
+        reserve_in: u64,
+        reserve_out: u64,
+        direction: SwapDirection,
+    ) -> Result<u64, SolanaCoreError> {
+        let (weight_in, weight_out) = self.weights(direction)?;
+        weighted_swap_out(amount_in, reserve_in, reserve_out, weight_in, weight_out)
+    }
+
+    #[inline(always)]
+    fn swap_exact_out(
+        &self,
+        amount_out: u64,
+        reserve_in: u64,
+        reserve_out: u64,
+        direction: SwapDirection,
+    ) -> Result<u64, SolanaCoreError> {
+        let (weight_in, weight_out) = self.weights(direction)?;
+        weighted_swap_in(amount_out, reserve_in, reserve_out, weight_in, weight_out)
+    }
+
+    #[inline(always)]
+    fn spot_price(
+        &self,
+        reserve_in: u64,
+        reserve_out: u64,
+        direction: SwapDirection,
+    ) -> Result<(u128, u128), SolanaCoreError> {
+        let (weight_in, weight_out) = self.weights(direction)?;
+        Ok(weighted_spot_price(reserve_in, reserve_out, weight_in, weight_out))
+    }
+
+    #[inline(always)]
+    fn initial_lp(&self, amount_0: u64, amount_1: u64) -> Result<u64, SolanaCoreError> {
+        weighted_invariant(amount_0, amount_1, self.weight_0_bps)
+    }
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CurveType {
+    // x * y = k
+    ConstantProduct = 0,
+    // Curve stable-swap invariant with amplification coefficient A, see `utils::stable`
+    StableSwap = 1,
+    // x + y = k
+    ConstantSum = 2,
+    // x^w_0 * y^w_1 = k with per-side weights, see `utils::weighted`
+    Weighted = 3,
+}
+
+impl TryFrom<u8> for CurveType {
+    type Error = SolanaCoreError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::ConstantProduct),
+            1 => Ok(Self::StableSwap),
+            2 => Ok(Self::ConstantSum),
+            3 => Ok(Self::Weighted),
+            _ => Err(SolanaCoreError::InvalidCurveType),
+        }
+    }
+}
+
+/// The curve of a pool, resolved from its `CurveType` tag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PoolCurve {
+    ConstantProduct(ConstantProduct),
+    StableSwap(StableSwap),
+    ConstantSum(ConstantSum),
+    Weighted(Weighted),
+}
+
+impl PoolCurve {
+    /// Builds the curve for `curve_type`; `amp` is only used by stable-swap
+    /// and `weight_0_bps` only by weighted pools.
+    #[inline(always)]
+    pub fn new(curve_type: CurveType, amp: u64, weight_0_bps: u64) -> Self {
+        match curve_type {
+            CurveType::ConstantProduct => Self::ConstantProduct(ConstantProduct),
+            CurveType::StableSwap => Self::StableSwap(StableSwap { amp }),
+            CurveType::ConstantSum => Self::ConstantSum(ConstantSum),
+            CurveType::Weighted => Self::Weighted(Weighted { weight_0_bps }),
+        }
+    }
+
+    /// `(curve_type, amp, weight_0_bps)` as stored in the pool and the `InitPool` payload.
+    #[inline(always)]
+    pub fn params(&self) -> (CurveType, u64, u64) {
+        match self {
+            Self::ConstantProduct(_) => (CurveType::ConstantProduct, 0, 0),
+            Self::StableSwap(curve) => (CurveType::StableSwap, curve.amp, 0),
+            Self::ConstantSum(_) => (CurveType::ConstantSum, 0, 0),
+            Self::Weighted(curve) => (CurveType::Weighted, 0, curve.weight_0_bps),
+        }
+    }
+
+    #[inline(always)]
+    fn inner(&self) -> &dyn Curve {
+        match self {
+            Self::ConstantProduct(curve) => curve,
+            Self::StableSwap(curve) => curve,
+            Self::ConstantSum(curve) => curve,
+            Self::Weighted(curve) => curve,
+        }
+    }
+}
+
+impl Curve for PoolCurve {
+    #[inline(always)]
+    fn swap_exact_in(
+        &self,
+        amount_in: u64,
+        reserve_in: u64,
+        reserve_out: u64,
+        direction: SwapDirection,
+    ) -> Result<u64, SolanaCoreError> {
+        self.inner().swap_exact_in(amount_in, reserve_in, reserve_out, direction)
+    }
+
+    #[inline(always)]
+    fn swap_exact_out(
+        &self,
+        amount_out: u64,
+        reserve_in: u64,
+        reserve_out: u64,
+        direction: SwapDirection,
+    ) -> Result<u64, SolanaCoreError> {
+        self.inner().swap_exact_out(amount_out, reserve_in, reserve_out, direction)
+    }
+
+    #[inline(always)]
+    fn spot_price(
+        &self,
+        reserve_in: u64,
+        reserve_out: u64,
+        direction: SwapDirection,
+    ) -> Result<(u128, u128), SolanaCoreError> {
+        self.inner().spot_price(reserve_in, reserve_out, direction)
+    }
+
+    #[inline(always)]
+    fn initial_lp(&self, amount_0: u64, amount_1: u64) -> Result<u64, SolanaCoreError> {
+        self.inner().initial_lp(amount_0, amount_1)
+    }
+
+    #[inline(always)]
+    fn deposit(
+        &self,
+        amount_0: u64,
+        amount_1: u64,
+        reserve_0: u64,
+        reserve_1: u64,
+        lp_supply: u64,
+    ) -> Result<(u64, u64, u64), SolanaCoreError> {
+        self.inner().deposit(amount_0, amount_1, reserve_0, reserve_1, lp_supply)
+    }
+
+    #[inline(always)]
+    fn withdraw(
+        &self,
+        amount_0: u64,
+        amount_1: u64,
+        reserve_0: u64,
+        reserve_1: u64,
+        lp_supply: u64,
+    ) -> Result<(u64, u64, u64), SolanaCoreError> {
+        self.inner().withdraw(amount_0, amount_1, reserve_0, reserve_1, lp_supply)
+    }
+}
+
+impl Pool {
+    /// The pool's curve as of `now` (the stable-swap coefficient may be ramping).
+    #[inline(always)]
+    pub fn curve(&self, now: i64) -> Result<PoolCurve, SolanaCoreError> {
+        Ok(PoolCurve::new(self.curve_type()?, self.amp(now), self.weight_0_bps()))
+    }
+}
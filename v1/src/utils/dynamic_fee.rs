@@ -0,0 +1,37 @@
+// Volatility-based fee for pools in dynamic fee mode. Each swap adds its price impact
+// (bps) to a volatility accumulator that halves every `VOLATILITY_HALF_LIFE_SECONDS`;
+// the fee moves linearly from the pool's min to its max fee as the accumulator goes
+// from zero to `VOLATILITY_FOR_MAX_FEE_BPS`. The fee only depends on the volatility
+// before a trade, so quotes stay a pure function of the pool state and time.
+
+pub const VOLATILITY_HALF_LIFE_SECONDS: i64 = 300;
+// accumulated price movement at which the max fee is charged (10 %)
+pub const VOLATILITY_FOR_MAX_FEE_BPS: u64 = 1_000;
+// upper bound on an admin-set max fee (5 %)
+pub const MAX_DYNAMIC_FEE_BPS: u64 = 500;
+
+/// `volatility` after `elapsed` seconds of exponential decay, interpolated linearly
+/// within a half-life.
+#[inline(always)]
+pub fn decay_volatility(volatility: u64, elapsed: i64) -> u64 {
+    if elapsed <= 0 {
+        return volatility;
+    }
+    let half_lives = elapsed / VOLATILITY_HALF_LIFE_SECONDS;
+    if half_lives >= 64 {
+        return 0;
+    }
+    let decayed = volatility >> half_lives;
+    let remainder = (elapsed % VOLATILITY_HALF_LIFE_SECONDS) as u128;
+    // between two half-lives the value drops by up to half of itself
+    let partial = decayed as u128 * remainder / (2 * VOLATILITY_HALF_LIFE_SECONDS as u128);
+    decayed - partial as u64
+}
+
+/// Fee for the current `volatility`, between `min_fee_bps` and `max_fee_bps`.
+#[inline(always)]
+pub fn dynamic_fee_bps(min_fee_bps: u64, max_fee_bps: u64, volatility: u64) -> u64 {
+    let range = max_fee_bps.saturating_sub(min_fee_bps) as u128;
+    let volatility = volatility.min(VOLATILITY_FOR_MAX_FEE_BPS) as u128;
+    min_fee_bps + (range * volatility / VOLATILITY_FOR_MAX_FEE_BPS as u128) as u64
+}
@@ -1,8 +1,36 @@
-    use pinocchio_token::state::Mint;
+    use pinocchio_token::state::{Mint, TokenAccount};
     use crate::errors::SolanaCoreError;
-    use pinocchio::account_info::AccountInfo;
+    use pinocchio::{account_info::AccountInfo, pubkey::Pubkey};
+    use pinocchio::sysvars::{clock::Clock, Sysvar};
     pub fn get_mint_supply(mint_info: &AccountInfo) -> Result<u64, SolanaCoreError> {
         let mint = Mint::from_account_info(mint_info).expect("");
         let mint_supply = mint.supply() as u64;
         Ok(mint_supply)
-    }
\ No newline at end of file
+    }
+
+    pub fn get_mint_decimals(mint_info: &AccountInfo) -> Result<u8, SolanaCoreError> {
+        match Mint::from_account_info(mint_info) {
+            Ok(mint) => Ok(mint.decimals()),
+            Err(_) => Err(SolanaCoreError::InvalidAccountData),
+        }
+    }
+
+    pub fn current_timestamp() -> Result<i64, SolanaCoreError> {
+        match Clock::get() {
+            Ok(clock) => Ok(clock.unix_timestamp),
+            Err(_) => Err(SolanaCoreError::SysvarUnavailable),
+        }
+    }
+    pub fn get_token_balance(token_account_info: &AccountInfo) -> Result<u64, SolanaCoreError> {
+        match TokenAccount::from_account_info(token_account_info) {
+            Ok(token_account) => Ok(token_account.amount()),
+            Err(_) => Err(SolanaCoreError::InvalidAccountData),
+        }
+    }
+
+    pub fn get_token_mint_and_owner(token_account_info: &AccountInfo) -> Result<(Pubkey, Pubkey), SolanaCoreError> {
+        match TokenAccount::from_account_info(token_account_info) {
+            Ok(token_account) => Ok((*token_account.mint(), *token_account.owner())),
+            Err(_) => Err(SolanaCoreError::InvalidAccountData),
+        }
+    }
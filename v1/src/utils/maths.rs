@@ -79,19 +79,93 @@ pub fn lp_to_burn(
     }
 }
 
-//token_0_amount(arg) is exclusive of fees i.e token_0_amount - (token_0_amount * fees_bump/100)
-// slippagem tolerance 
+pub const BPS_DENOMINATOR: u64 = 10_000;
+// highest fee tier or fee rate a pool may charge (5 %)
+pub const MAX_FEES_BPS: u64 = 500;
+
+// floor(sqrt(value)), Newton's method.
 #[inline(always)]
-pub fn calculate_token_out(token_0_amount: u64, expected_token_1_amount: u64, slippage_bps: u64, token_0_liquidity: u64, token_1_liquidity: u64) -> Result<u64, SolanaCoreError> {
-    //formula = k = x*y
-    let  k: u64 = token_0_liquidity.checked_mul(token_1_liquidity).ok_or(SolanaCoreError::OverFlowDetected)?;
-    let updated_k: u64 = (token_0_amount + token_0_liquidity).checked_mul(expected_token_1_amount).ok_or(SolanaCoreError::OverFlowDetected).unwrap(); 
-    let token_1_out = k.checked_div(updated_k);
-
-    if token_1_out >= expected_token_1_amount.checked_sub(expected_token_1_amount.checked_sub(slippage_bps/100).expect("The the subtraction shouldn't cause underflow")) {
-        return Err(SolanaCoreError::Slippage.into())
-    } else {
-        Ok(token_1_out.expect("Token_1_out should be slippage adjusted"))
+pub fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x / 2 + x % 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+// Fee charged on a swap input of `amount_in`, in input-token units:
+// amount_in * fees_bps / 10_000, rounded up (in the pool's favour).
+#[inline(always)]
+pub fn swap_fee(amount_in: u64, fees_bps: u64) -> Result<u64, SolanaCoreError> {
+    if fees_bps >= BPS_DENOMINATOR {
+        return Err(SolanaCoreError::MathError);
     }
+    let fee = (amount_in as u128 * fees_bps as u128).div_ceil(BPS_DENOMINATOR as u128);
+    u64::try_from(fee).map_err(|_| SolanaCoreError::OverFlowDetected)
+}
 
-}
\ No newline at end of file
+// Inverse of `swap_fee`: the smallest gross input whose post-fee amount is at least `amount_in_after_fee`.
+// gross - ceil(gross * bps / 10_000) = floor(gross * (10_000 - bps) / 10_000), so
+// gross = ceil(amount_in_after_fee * 10_000 / (10_000 - bps)).
+#[inline(always)]
+pub fn amount_before_fee(amount_in_after_fee: u64, fees_bps: u64) -> Result<u64, SolanaCoreError> {
+    if fees_bps >= BPS_DENOMINATOR {
+        return Err(SolanaCoreError::MathError);
+    }
+    let amount_in = (amount_in_after_fee as u128 * BPS_DENOMINATOR as u128)
+        .div_ceil((BPS_DENOMINATOR - fees_bps) as u128);
+    u64::try_from(amount_in).map_err(|_| SolanaCoreError::OverFlowDetected)
+}
+
+// Part of a swap `fee` paid to a referrer for `fee_share_bps` of it, rounded down (in the pool's favour).
+#[inline(always)]
+pub fn referral_fee(fee: u64, fee_share_bps: u64) -> u64 {
+    (fee as u128 * fee_share_bps.min(BPS_DENOMINATOR) as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+// x * y = k, output side: dy = y * dx / (x + dx), rounded down (in the pool's favour).
+#[inline(always)]
+pub fn constant_product_out(amount_in: u64, reserve_in: u64, reserve_out: u64) -> Result<u64, SolanaCoreError> {
+    let numerator = (reserve_out as u128)
+        .checked_mul(amount_in as u128)
+        .ok_or(SolanaCoreError::OverFlowDetected)?;
+    let denominator = (reserve_in as u128)
+        .checked_add(amount_in as u128)
+        .ok_or(SolanaCoreError::OverFlowDetected)?;
+    if denominator == 0 {
+        return Err(SolanaCoreError::MathError);
+    }
+    u64::try_from(numerator / denominator).map_err(|_| SolanaCoreError::OverFlowDetected)
+}
+
+// x * y = k, input side: dx = x * dy / (y - dy), rounded up (in the pool's favour).
+#[inline(always)]
+pub fn constant_product_in(amount_out: u64, reserve_in: u64, reserve_out: u64) -> Result<u64, SolanaCoreError> {
+    if amount_out >= reserve_out {
+        return Err(SolanaCoreError::MathError);
+    }
+    let numerator = (reserve_in as u128)
+        .checked_mul(amount_out as u128)
+        .ok_or(SolanaCoreError::OverFlowDetected)?;
+    let denominator = (reserve_out - amount_out) as u128;
+    u64::try_from(numerator.div_ceil(denominator)).map_err(|_| SolanaCoreError::OverFlowDetected)
+}
+
+// `numerator / denominator` expressed in bps, with both operands scaled down first
+// so the multiplication by 10_000 cannot overflow.
+#[inline(always)]
+pub fn ratio_bps(mut numerator: u128, mut denominator: u128) -> Result<u64, SolanaCoreError> {
+    while numerator > u128::MAX / BPS_DENOMINATOR as u128 {
+        numerator >>= 1;
+        denominator >>= 1;
+    }
+    if denominator == 0 {
+        return Err(SolanaCoreError::MathError);
+    }
+    u64::try_from(numerator * BPS_DENOMINATOR as u128 / denominator).map_err(|_| SolanaCoreError::OverFlowDetected)
+}
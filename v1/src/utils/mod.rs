@@ -5,4 +5,34 @@ pub mod checks;
 pub use checks::*;
 
 pub mod helper;
-pub use helper::*;
\ No newline at end of file
+pub use helper::*;
+
+pub mod codec;
+pub use codec::*;
+
+pub mod quote;
+pub use quote::*;
+
+pub mod oracle;
+pub use oracle::*;
+
+pub mod stable;
+pub use stable::*;
+
+pub mod weighted;
+pub use weighted::*;
+
+pub mod curve;
+pub use curve::*;
+
+pub mod tick_math;
+pub use tick_math::*;
+
+pub mod concentrated;
+pub use concentrated::*;
+
+pub mod multi;
+pub use multi::*;
+
+pub mod dynamic_fee;
+pub use dynamic_fee::*;
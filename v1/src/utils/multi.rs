@@ -0,0 +1,256 @@
+use crate::{
+    errors::SolanaCoreError,
+    states::MAX_MULTI_POOL_TOKENS,
+    utils::{
+        amount_before_fee, compute_d_multi, compute_y_multi, swap_fee, weighted_deposit_single,
+        weighted_invariant_multi, weighted_swap_in, weighted_swap_out, weighted_withdraw_single, CurveType, SwapMode,
+        BPS_DENOMINATOR,
+    },
+};
+
+// Swap and liquidity math of `MultiPool`, on a decoded copy of its reserves so handlers
+// and off-chain quotes run the same code. Every function rounds in the pool's favour.
+//
+// A single-token deposit or withdrawal is a proportional one plus an implicit swap of
+// the other tokens' share, so the swap fee is charged on that share only:
+// `amount * (1 - weight)`, with weight `1 / n` on stable-swap pools.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Basket {
+    pub curve_type: CurveType,
+    // stable-swap amplification coefficient
+    pub amp: u64,
+    pub fees_bps: u64,
+    pub token_count: usize,
+    pub reserves: [u64; MAX_MULTI_POOL_TOKENS],
+    // weighted pools only
+    pub weights_bps: [u64; MAX_MULTI_POOL_TOKENS],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MultiSwapQuote {
+    // gross input transferred by the user, fee included
+    pub amount_in: u64,
+    pub amount_out: u64,
+    // part of `amount_in` kept by the pool as fee
+    pub fee: u64,
+}
+
+impl Basket {
+    #[inline(always)]
+    pub fn reserves(&self) -> &[u64] {
+        &self.reserves[..self.token_count]
+    }
+
+    #[inline(always)]
+    fn check_index(&self, index: usize) -> Result<(), SolanaCoreError> {
+        if index >= self.token_count {
+            return Err(SolanaCoreError::InvalidInstructionData);
+        }
+        Ok(())
+    }
+
+    /// Share of the pool value held in token `index`, in bps.
+    #[inline(always)]
+    pub fn weight_bps(&self, index: usize) -> u64 {
+        match self.curve_type {
+            CurveType::Weighted => self.weights_bps[index],
+            _ => BPS_DENOMINATOR / self.token_count as u64,
+        }
+    }
+
+    /// Invariant of the current reserves: D on stable-swap pools, the weighted geometric
+    /// mean on weighted pools. Both scale linearly with the reserves.
+    pub fn invariant(&self) -> Result<u128, SolanaCoreError> {
+        match self.curve_type {
+            CurveType::StableSwap => compute_d_multi(self.amp, self.reserves()),
+            CurveType::Weighted => {
+                Ok(weighted_invariant_multi(self.reserves(), &self.weights_bps[..self.token_count])? as u128)
+            }
+            _ => Err(SolanaCoreError::InvalidCurveType),
+        }
+    }
+
+    /// LP tokens minted to the creator of a pool seeded with the current reserves.
+    #[inline(always)]
+    pub fn initial_lp(&self) -> Result<u64, SolanaCoreError> {
+        u64::try_from(self.invariant()?).map_err(|_| SolanaCoreError::OverFlowDetected)
+    }
+
+    /// Output of token `token_out` for `amount_in` (after fee) of token `token_in`.
+    pub fn swap_exact_in(&self, token_in: usize, token_out: usize, amount_in: u64) -> Result<u64, SolanaCoreError> {
+        self.check_pair(token_in, token_out)?;
+        let (reserve_in, reserve_out) = (self.reserves[token_in], self.reserves[token_out]);
+        match self.curve_type {
+            CurveType::StableSwap => {
+                let d = compute_d_multi(self.amp, self.reserves())?;
+                let mut reserves = self.reserves;
+                reserves[token_in] = reserve_in.checked_add(amount_in).ok_or(SolanaCoreError::OverFlowDetected)?;
+                let new_reserve_out = compute_y_multi(self.amp, &reserves[..self.token_count], token_out, d)?;
+                let amount_out = (reserve_out as u128).saturating_sub(new_reserve_out).saturating_sub(1);
+                u64::try_from(amount_out).map_err(|_| SolanaCoreError::OverFlowDetected)
+            }
+            CurveType::Weighted => weighted_swap_out(
+                amount_in,
+                reserve_in,
+                reserve_out,
+                self.weights_bps[token_in],
+                self.weights_bps[token_out],
+            ),
+            _ => Err(SolanaCoreError::InvalidCurveType),
+        }
+    }
+
+    /// Input of token `token_in` (after fee) needed to receive exactly `amount_out` of `token_out`.
+    pub fn swap_exact_out(&self, token_in: usize, token_out: usize, amount_out: u64) -> Result<u64, SolanaCoreError> {
+        self.check_pair(token_in, token_out)?;
+        let (reserve_in, reserve_out) = (self.reserves[token_in], self.reserves[token_out]);
+        if amount_out >= reserve_out {
+            return Err(SolanaCoreError::MathError);
+        }
+        match self.curve_type {
+            CurveType::StableSwap => {
+                let d = compute_d_multi(self.amp, self.reserves())?;
+                let mut reserves = self.reserves;
+                reserves[token_out] = reserve_out - amount_out;
+                let new_reserve_in = compute_y_multi(self.amp, &reserves[..self.token_count], token_in, d)?;
+                let amount_in = new_reserve_in
+                    .checked_sub(reserve_in as u128)
+                    .ok_or(SolanaCoreError::MathError)?
+                    + 1;
+                u64::try_from(amount_in).map_err(|_| SolanaCoreError::OverFlowDetected)
+            }
+            CurveType::Weighted => weighted_swap_in(
+                amount_out,
+                reserve_in,
+                reserve_out,
+                self.weights_bps[token_in],
+                self.weights_bps[token_out],
+            ),
+            _ => Err(SolanaCoreError::InvalidCurveType),
+        }
+    }
+
+    #[inline(always)]
+    fn check_pair(&self, token_in: usize, token_out: usize) -> Result<(), SolanaCoreError> {
+        self.check_index(token_in)?;
+        self.check_index(token_out)?;
+        if token_in == token_out {
+            return Err(SolanaCoreError::InvalidInstructionData);
+        }
+        Ok(())
+    }
+
+    /// Quotes a swap; `amount` is the gross input for exact-in, the output for exact-out.
+    pub fn quote_swap(
+        &self,
+        token_in: usize,
+        token_out: usize,
+        amount: u64,
+        mode: SwapMode,
+    ) -> Result<MultiSwapQuote, SolanaCoreError> {
+        let (amount_in, amount_out, fee) = match mode {
+            SwapMode::ExactIn => {
+                let fee = swap_fee(amount, self.fees_bps)?;
+                let amount_in_after_fee = amount.checked_sub(fee).ok_or(SolanaCoreError::MathError)?;
+                (amount, self.swap_exact_in(token_in, token_out, amount_in_after_fee)?, fee)
+            }
+            SwapMode::ExactOut => {
+                let amount_in_after_fee = self.swap_exact_out(token_in, token_out, amount)?;
+                let amount_in = amount_before_fee(amount_in_after_fee, self.fees_bps)?;
+                (amount_in, amount, amount_in - amount_in_after_fee)
+            }
+        };
+        if amount_out == 0 {
+            return Err(SolanaCoreError::MathError);
+        }
+        Ok(MultiSwapQuote { amount_in, amount_out, fee })
+    }
+
+    /// Amounts of every token matching `lp_amount` out of `lp_supply`: rounded up for
+    /// deposits, down for withdrawals.
+    pub fn proportional_amounts(
+        &self,
+        lp_amount: u64,
+        lp_supply: u64,
+        round_up: bool,
+    ) -> Result<[u64; MAX_MULTI_POOL_TOKENS], SolanaCoreError> {
+        if lp_supply == 0 || (!round_up && lp_amount > lp_supply) {
+            return Err(SolanaCoreError::MathError);
+        }
+        let mut amounts = [0u64; MAX_MULTI_POOL_TOKENS];
+        for (amount, reserve) in amounts.iter_mut().zip(self.reserves()) {
+            let numerator = *reserve as u128 * lp_amount as u128;
+            let value = if round_up {
+                numerator.div_ceil(lp_supply as u128)
+            } else {
+                numerator / lp_supply as u128
+            };
+            *amount = u64::try_from(value).map_err(|_| SolanaCoreError::OverFlowDetected)?;
+        }
+        Ok(amounts)
+    }
+
+    // Fee on the implicitly swapped share of a single-token `amount`.
+    #[inline(always)]
+    fn single_token_fee(&self, index: usize, amount: u64) -> Result<u64, SolanaCoreError> {
+        let swapped = amount as u128 * (BPS_DENOMINATOR - self.weight_bps(index)) as u128 / BPS_DENOMINATOR as u128;
+        swap_fee(swapped as u64, self.fees_bps)
+    }
+
+    /// `(lp_to_mint, fee)` for depositing `amount_in` of token `index` alone.
+    pub fn deposit_single(&self, index: usize, amount_in: u64, lp_supply: u64) -> Result<(u64, u64), SolanaCoreError> {
+        self.check_index(index)?;
+        let fee = self.single_token_fee(index, amount_in)?;
+        let amount_in_after_fee = amount_in.checked_sub(fee).ok_or(SolanaCoreError::MathError)?;
+        let lp = match self.curve_type {
+            CurveType::StableSwap => {
+                let d_0 = compute_d_multi(self.amp, self.reserves())?;
+                let mut reserves = self.reserves;
+                reserves[index] =
+                    reserves[index].checked_add(amount_in_after_fee).ok_or(SolanaCoreError::OverFlowDetected)?;
+                let d_1 = compute_d_multi(self.amp, &reserves[..self.token_count])?;
+                // D is only accurate to one unit
+                let growth = d_1.checked_sub(d_0).ok_or(SolanaCoreError::MathError)?.saturating_sub(1);
+                let lp = lp_supply as u128 * growth / d_0;
+                u64::try_from(lp).map_err(|_| SolanaCoreError::OverFlowDetected)?
+            }
+            CurveType::Weighted => {
+                weighted_deposit_single(amount_in_after_fee, self.reserves[index], self.weights_bps[index], lp_supply)?
+            }
+            _ => return Err(SolanaCoreError::InvalidCurveType),
+        };
+        if lp == 0 {
+            return Err(SolanaCoreError::MathError);
+        }
+        Ok((lp, fee))
+    }
+
+    /// `(amount_out, fee)` of token `index` for burning `lp_amount`; the fee stays in the pool.
+    pub fn withdraw_single(&self, index: usize, lp_amount: u64, lp_supply: u64) -> Result<(u64, u64), SolanaCoreError> {
+        self.check_index(index)?;
+        if lp_amount >= lp_supply {
+            return Err(SolanaCoreError::MathError);
+        }
+        let reserve = self.reserves[index];
+        let amount_out_before_fee = match self.curve_type {
+            CurveType::StableSwap => {
+                let d_0 = compute_d_multi(self.amp, self.reserves())?;
+                let d_1 = d_0 - d_0 * lp_amount as u128 / lp_supply as u128;
+                let new_reserve = compute_y_multi(self.amp, self.reserves(), index, d_1)?;
+                let amount_out = (reserve as u128).saturating_sub(new_reserve).saturating_sub(1);
+                u64::try_from(amount_out).map_err(|_| SolanaCoreError::OverFlowDetected)?
+            }
+            CurveType::Weighted => {
+                weighted_withdraw_single(lp_amount, reserve, self.weights_bps[index], lp_supply)?
+            }
+            _ => return Err(SolanaCoreError::InvalidCurveType),
+        };
+        let fee = self.single_token_fee(index, amount_out_before_fee)?;
+        let amount_out = amount_out_before_fee.checked_sub(fee).ok_or(SolanaCoreError::MathError)?;
+        if amount_out == 0 {
+            return Err(SolanaCoreError::MathError);
+        }
+        Ok((amount_out, fee))
+    }
+}
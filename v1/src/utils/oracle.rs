@@ -0,0 +1,107 @@
+use crate::{
+    errors::SolanaCoreError,
+    states::Pool,
+    utils::{isqrt, Curve, SwapDirection},
+};
+
+// Uniswap v2 style price accumulators, fitted to `Pool`.
+//
+// Before reserves change, each instruction adds `spot_price * seconds_elapsed` to the
+// cumulative prices, so the time-weighted average price between two observations is
+// `(cumulative_end - cumulative_start) / (time_end - time_start)`. Prices are Q64.64;
+// accumulators wrap on overflow and are always differenced with `wrapping_sub`,
+// which stays exact as long as `avg_price * window` fits in 128 bits.
+// Liquidity (`sqrt(reserve_0 * reserve_1)`) is accumulated the same way.
+
+pub const Q64_SHIFT: u32 = 64;
+
+/// `numerator / denominator` as Q64.64. Zero when `denominator` is zero.
+#[inline(always)]
+pub fn price_q64(numerator: u64, denominator: u64) -> u128 {
+    if denominator == 0 {
+        return 0;
+    }
+    ((numerator as u128) << Q64_SHIFT) / denominator as u128
+}
+
+/// A point-in-time read of the pool's accumulators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceObservation {
+    pub timestamp: i64,
+    pub price_0_cumulative: u128,
+    pub price_1_cumulative: u128,
+    pub liquidity_cumulative: u128,
+}
+
+impl Pool {
+    /// Marginal prices at `now` as Q64.64: `price_0` is token_1 per token_0, `price_1` the inverse.
+    /// Zero when a reserve is empty.
+    #[inline(always)]
+    pub fn spot_prices(&self, now: i64) -> (u128, u128) {
+        let spot = self.curve(now).and_then(|curve| {
+            curve.spot_price(self.token_0_amount(), self.token_1_amount(), SwapDirection::ZeroForOne)
+        });
+        match spot {
+            // both parts fit in 64 bits
+            Ok((numerator, denominator)) => (
+                price_q64(numerator as u64, denominator as u64),
+                price_q64(denominator as u64, numerator as u64),
+            ),
+            Err(_) => (0, 0),
+        }
+    }
+
+    /// Accumulators as they would be at `now`, without writing them.
+    /// Off-chain consumers use this to observe a pool that has not traded recently.
+    #[inline(always)]
+    pub fn observe(&self, now: i64) -> PriceObservation {
+        let elapsed = now.saturating_sub(self.last_update_timestamp()).max(0) as u128;
+        let (reserve_0, reserve_1) = (self.token_0_amount(), self.token_1_amount());
+        let (price_0, price_1) = self.spot_prices(now);
+        PriceObservation {
+            timestamp: now.max(self.last_update_timestamp()),
+            price_0_cumulative: self.price_0_cumulative().wrapping_add(price_0.wrapping_mul(elapsed)),
+            price_1_cumulative: self.price_1_cumulative().wrapping_add(price_1.wrapping_mul(elapsed)),
+            liquidity_cumulative: self
+                .liquidity_cumulative()
+                .wrapping_add(isqrt(reserve_0 as u128 * reserve_1 as u128).wrapping_mul(elapsed)),
+        }
+    }
+
+    /// Accrues the current spot price up to `now`. Must run before reserves change.
+    #[inline(always)]
+    pub fn update_price_accumulators(&mut self, now: i64) {
+        if now <= self.last_update_timestamp() {
+            return;
+        }
+        let observation = self.observe(now);
+        self.set_price_0_cumulative(observation.price_0_cumulative);
+        self.set_price_1_cumulative(observation.price_1_cumulative);
+        self.set_liquidity_cumulative(observation.liquidity_cumulative);
+        self.set_last_update_timestamp(observation.timestamp);
+    }
+}
+
+/// Time-weighted average of `sqrt(reserve_0 * reserve_1)` between two observations.
+#[inline(always)]
+pub fn average_liquidity(start: &PriceObservation, end: &PriceObservation) -> Result<u128, SolanaCoreError> {
+    let elapsed = end.timestamp.checked_sub(start.timestamp).ok_or(SolanaCoreError::MathError)?;
+    if elapsed <= 0 {
+        return Err(SolanaCoreError::MathError);
+    }
+    Ok(end.liquidity_cumulative.wrapping_sub(start.liquidity_cumulative) / elapsed as u128)
+}
+
+/// Time-weighted average prices (Q64.64) of (token_0 in token_1, token_1 in token_0)
+/// between two observations of the same pool.
+#[inline(always)]
+pub fn twap(start: &PriceObservation, end: &PriceObservation) -> Result<(u128, u128), SolanaCoreError> {
+    let elapsed = end.timestamp.checked_sub(start.timestamp).ok_or(SolanaCoreError::MathError)?;
+    if elapsed <= 0 {
+        return Err(SolanaCoreError::MathError);
+    }
+    Ok((
+        end.price_0_cumulative.wrapping_sub(start.price_0_cumulative) / elapsed as u128,
+        end.price_1_cumulative.wrapping_sub(start.price_1_cumulative) / elapsed as u128,
+    ))
+}
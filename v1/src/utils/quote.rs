@@ -0,0 +1,253 @@
+use crate::{
+    errors::SolanaCoreError,
+    states::Pool,
+    utils::{amount_before_fee, ratio_bps, swap_fee, ConstantProduct, Curve, BPS_DENOMINATOR},
+};
+
+// Swap quoting shared by the on-chain `swap` handler and off-chain clients.
+// `swap` executes exactly the quote returned here, so a quote computed against
+// the same `Pool` state is bit-identical to what the program will do.
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapDirection {
+    // token_0 in, token_1 out
+    ZeroForOne = 0,
+    // token_1 in, token_0 out
+    OneForZero = 1,
+}
+
+impl TryFrom<u8> for SwapDirection {
+    type Error = SolanaCoreError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::ZeroForOne),
+            1 => Ok(Self::OneForZero),
+            _ => Err(SolanaCoreError::InvalidInstructionData),
+        }
+    }
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SwapMode {
+    // `amount` is the input, fee included
+    ExactIn = 0,
+    // `amount` is the desired output
+    ExactOut = 1,
+}
+
+impl TryFrom<u8> for SwapMode {
+    type Error = SolanaCoreError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::ExactIn),
+            1 => Ok(Self::ExactOut),
+            _ => Err(SolanaCoreError::InvalidInstructionData),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapQuote {
+    // gross input transferred by the user, fee included
+    pub amount_in: u64,
+    pub amount_out: u64,
+    // part of `amount_in` kept by the pool as fee
+    pub fee: u64,
+    // fee rate the quote was computed with, which differs from the pool's tier in dynamic fee mode
+    pub fees_bps: u64,
+    // execution price vs. spot price before the trade, in bps
+    pub price_impact_bps: u64,
+    pub reserve_0_after: u64,
+    pub reserve_1_after: u64,
+}
+
+impl SwapQuote {
+    /// See [`check_slippage`].
+    #[inline(always)]
+    pub fn check_slippage(&self, mode: SwapMode, expected_amount: u64, slippage_bps: u64) -> Result<(), SolanaCoreError> {
+        check_slippage(mode, self.amount_in, self.amount_out, expected_amount, slippage_bps)
+    }
+}
+
+/// Checks a swap against the user's expected counter amount:
+/// at least `expected * (1 - slippage)` out for exact-in,
+/// at most `expected * (1 + slippage)` in for exact-out.
+#[inline(always)]
+pub fn check_slippage(
+    mode: SwapMode,
+    amount_in: u64,
+    amount_out: u64,
+    expected_amount: u64,
+    slippage_bps: u64,
+) -> Result<(), SolanaCoreError> {
+    match mode {
+        SwapMode::ExactIn => {
+            let min_out = (expected_amount as u128)
+                * BPS_DENOMINATOR.saturating_sub(slippage_bps) as u128
+                / BPS_DENOMINATOR as u128;
+            if (amount_out as u128) < min_out {
+                return Err(SolanaCoreError::Slippage);
+            }
+        }
+        SwapMode::ExactOut => {
+            let max_in = (expected_amount as u128)
+                * (BPS_DENOMINATOR as u128 + slippage_bps as u128)
+                / BPS_DENOMINATOR as u128;
+            if (amount_in as u128) > max_in {
+                return Err(SolanaCoreError::Slippage);
+            }
+        }
+    }
+    Ok(())
+}
+
+// `spot` is the marginal price before the trade, output per input, as (numerator, denominator).
+#[inline(always)]
+fn finish_quote(
+    amount_in: u64,
+    fee: u64,
+    amount_out: u64,
+    spot: (u128, u128),
+    reserve_0: u64,
+    reserve_1: u64,
+    direction: SwapDirection,
+) -> Result<SwapQuote, SolanaCoreError> {
+    let (reserve_in, reserve_out) = match direction {
+        SwapDirection::ZeroForOne => (reserve_0, reserve_1),
+        SwapDirection::OneForZero => (reserve_1, reserve_0),
+    };
+    if amount_out == 0 {
+        return Err(SolanaCoreError::MathError);
+    }
+
+    // Execution price is amount_out / (amount_in - fee), compared against the spot price.
+    let executed = (amount_out as u128) * spot.1;
+    let spot = ((amount_in - fee) as u128) * spot.0;
+    let price_impact_bps = BPS_DENOMINATOR.saturating_sub(ratio_bps(executed, spot)?);
+
+    // The whole input, fee included, stays in the pool.
+    let reserve_in_after = reserve_in.checked_add(amount_in).ok_or(SolanaCoreError::OverFlowDetected)?;
+    let reserve_out_after = reserve_out.checked_sub(amount_out).ok_or(SolanaCoreError::MathError)?;
+    let (reserve_0_after, reserve_1_after) = match direction {
+        SwapDirection::ZeroForOne => (reserve_in_after, reserve_out_after),
+        SwapDirection::OneForZero => (reserve_out_after, reserve_in_after),
+    };
+
+    Ok(SwapQuote {
+        amount_in,
+        amount_out,
+        fee,
+        // filled in by the callers, which know the rate
+        fees_bps: 0,
+        price_impact_bps,
+        reserve_0_after,
+        reserve_1_after,
+    })
+}
+
+/// Quotes a swap of exactly `amount_in` (fee included) on `curve`.
+#[inline(always)]
+pub fn quote_curve_exact_in(
+    curve: &impl Curve,
+    reserve_0: u64,
+    reserve_1: u64,
+    fees_bps: u64,
+    amount_in: u64,
+    direction: SwapDirection,
+) -> Result<SwapQuote, SolanaCoreError> {
+    let (reserve_in, reserve_out) = match direction {
+        SwapDirection::ZeroForOne => (reserve_0, reserve_1),
+        SwapDirection::OneForZero => (reserve_1, reserve_0),
+    };
+    let fee = swap_fee(amount_in, fees_bps)?;
+    let amount_in_after_fee = amount_in.checked_sub(fee).ok_or(SolanaCoreError::MathError)?;
+    let amount_out = curve.swap_exact_in(amount_in_after_fee, reserve_in, reserve_out, direction)?;
+
+    let spot = curve.spot_price(reserve_in, reserve_out, direction)?;
+    let quote = finish_quote(amount_in, fee, amount_out, spot, reserve_0, reserve_1, direction)?;
+    Ok(SwapQuote { fees_bps, ..quote })
+}
+
+/// Quotes the input (fee included) needed to receive exactly `amount_out` on `curve`.
+#[inline(always)]
+pub fn quote_curve_exact_out(
+    curve: &impl Curve,
+    reserve_0: u64,
+    reserve_1: u64,
+    fees_bps: u64,
+    amount_out: u64,
+    direction: SwapDirection,
+) -> Result<SwapQuote, SolanaCoreError> {
+    let (reserve_in, reserve_out) = match direction {
+        SwapDirection::ZeroForOne => (reserve_0, reserve_1),
+        SwapDirection::OneForZero => (reserve_1, reserve_0),
+    };
+    let amount_in_after_fee = curve.swap_exact_out(amount_out, reserve_in, reserve_out, direction)?;
+    let amount_in = amount_before_fee(amount_in_after_fee, fees_bps)?;
+    let fee = amount_in - amount_in_after_fee;
+
+    let spot = curve.spot_price(reserve_in, reserve_out, direction)?;
+    let quote = finish_quote(amount_in, fee, amount_out, spot, reserve_0, reserve_1, direction)?;
+    Ok(SwapQuote { fees_bps, ..quote })
+}
+
+/// Constant-product [`quote_curve_exact_in`].
+#[inline(always)]
+pub fn quote_exact_in(
+    reserve_0: u64,
+    reserve_1: u64,
+    fees_bps: u64,
+    amount_in: u64,
+    direction: SwapDirection,
+) -> Result<SwapQuote, SolanaCoreError> {
+    quote_curve_exact_in(&ConstantProduct, reserve_0, reserve_1, fees_bps, amount_in, direction)
+}
+
+/// Constant-product [`quote_curve_exact_out`].
+#[inline(always)]
+pub fn quote_exact_out(
+    reserve_0: u64,
+    reserve_1: u64,
+    fees_bps: u64,
+    amount_out: u64,
+    direction: SwapDirection,
+) -> Result<SwapQuote, SolanaCoreError> {
+    quote_curve_exact_out(&ConstantProduct, reserve_0, reserve_1, fees_bps, amount_out, direction)
+}
+
+/// Quotes a swap against the state of `pool` at unix time `now`
+/// (the stable-swap amplification coefficient may be ramping).
+#[inline(always)]
+pub fn quote_swap(
+    pool: &Pool,
+    amount: u64,
+    direction: SwapDirection,
+    mode: SwapMode,
+    now: i64,
+) -> Result<SwapQuote, SolanaCoreError> {
+    let curve = pool.curve(now)?;
+    let (reserve_0, reserve_1, fees_bps) = (pool.token_0_amount(), pool.token_1_amount(), pool.effective_fees_bps(now));
+    match mode {
+        SwapMode::ExactIn => quote_curve_exact_in(&curve, reserve_0, reserve_1, fees_bps, amount, direction),
+        SwapMode::ExactOut => quote_curve_exact_out(&curve, reserve_0, reserve_1, fees_bps, amount, direction),
+    }
+}
+
+// largest number of pools a single `route_swap` may go through
+pub const MAX_ROUTE_LEGS: usize = 4;
+
+/// Quotes an exact-in `route_swap`: each leg swaps the previous leg's output.
+/// Returns the final output. Pools must be distinct, as on-chain each leg sees the
+/// state left by the previous ones.
+pub fn quote_route(legs: &[(&Pool, SwapDirection)], amount_in: u64, now: i64) -> Result<u64, SolanaCoreError> {
+    if legs.is_empty() || legs.len() > MAX_ROUTE_LEGS {
+        return Err(SolanaCoreError::InvalidRoute);
+    }
+    legs.iter().try_fold(amount_in, |amount, (pool, direction)| {
+        Ok(quote_swap(pool, amount, *direction, SwapMode::ExactIn, now)?.amount_out)
+    })
+}
@@ -0,0 +1,236 @@
+use crate::errors::SolanaCoreError;
+use uint::construct_uint;
+
+// Two-token stable-swap (Curve) invariant:
+// `A * n^n * (x + y) + D = A * n^n * D + D^(n+1) / (n^n * x * y)` with n = 2.
+// Near balance it trades almost 1:1, far from balance it degrades towards x * y = k.
+// A larger amplification coefficient `A` keeps the curve flat for longer.
+
+construct_uint! {
+    // 256-bit integers for the stable-swap invariant; D^3 does not fit in u128.
+    pub struct U256(4);
+}
+
+pub const MIN_AMP: u64 = 1;
+pub const MAX_AMP: u64 = 1_000_000;
+// a single ramp may change A by at most this factor, up or down
+pub const MAX_AMP_CHANGE: u64 = 10;
+pub const MIN_RAMP_DURATION: i64 = 86_400;
+
+const N_COINS: u64 = 2;
+const MAX_ITERATIONS: usize = 255;
+
+// A * n^n
+#[inline(always)]
+fn ann(amp: u64) -> Result<U256, SolanaCoreError> {
+    if !(MIN_AMP..=MAX_AMP).contains(&amp) {
+        return Err(SolanaCoreError::InvalidAmp);
+    }
+    Ok(U256::from(amp) * U256::from(N_COINS * N_COINS))
+}
+
+#[inline(always)]
+fn to_u128(value: U256) -> Result<u128, SolanaCoreError> {
+    if value > U256::from(u128::MAX) {
+        return Err(SolanaCoreError::OverFlowDetected);
+    }
+    Ok(value.as_u128())
+}
+
+#[inline(always)]
+fn to_u64(value: U256) -> Result<u64, SolanaCoreError> {
+    if value > U256::from(u64::MAX) {
+        return Err(SolanaCoreError::OverFlowDetected);
+    }
+    Ok(value.as_u64())
+}
+
+#[inline(always)]
+fn converged(a: U256, b: U256) -> bool {
+    if a > b { a - b <= U256::one() } else { b - a <= U256::one() }
+}
+
+/// Stable-swap invariant D for reserves `x`, `y`:
+/// `Ann * (x + y) + D = Ann * D + D^3 / (4 * x * y)`, solved with Newton's method.
+pub fn compute_d(amp: u64, x: u64, y: u64) -> Result<u128, SolanaCoreError> {
+    let sum = U256::from(x) + U256::from(y);
+    if sum.is_zero() {
+        return Ok(0);
+    }
+    if x == 0 || y == 0 {
+        return Err(SolanaCoreError::MathError);
+    }
+    let ann = ann(amp)?;
+    let n = U256::from(N_COINS);
+    let mut d = sum;
+    for _ in 0..MAX_ITERATIONS {
+        // D^3 / (4 * x * y) in a single division: truncating per factor can make the
+        // iteration oscillate for unbalanced pools
+        let d_p = d * d * d / (U256::from(x) * U256::from(y) * n * n);
+        let d_prev = d;
+        d = (ann * sum + d_p * n) * d / ((ann - U256::one()) * d + (n + U256::one()) * d_p);
+        if converged(d, d_prev) {
+            return to_u128(d);
+        }
+    }
+    Err(SolanaCoreError::MathError)
+}
+
+/// Reserve of the other token that keeps the invariant at `d` when one reserve is `x`.
+pub fn compute_y(amp: u64, x: u128, d: u128) -> Result<u128, SolanaCoreError> {
+    if x == 0 {
+        return Err(SolanaCoreError::MathError);
+    }
+    let ann = ann(amp)?;
+    let n = U256::from(N_COINS);
+    let (x, d) = (U256::from(x), U256::from(d));
+    // c = D^3 / (4 * x * Ann), b = x + D / Ann
+    let c = d * d * d / (x * ann * n * n);
+    let b = x + d / ann;
+    let mut y = d;
+    for _ in 0..MAX_ITERATIONS {
+        let y_prev = y;
+        let denominator = (y * n + b).checked_sub(d).ok_or(SolanaCoreError::MathError)?;
+        if denominator.is_zero() {
+            return Err(SolanaCoreError::MathError);
+        }
+        y = (y * y + c) / denominator;
+        if converged(y, y_prev) {
+            return to_u128(y);
+        }
+    }
+    Err(SolanaCoreError::MathError)
+}
+
+// Output for `amount_in` (after fee), rounded down by one extra unit in the pool's favour.
+#[inline(always)]
+pub fn stable_swap_out(amp: u64, amount_in: u64, reserve_in: u64, reserve_out: u64) -> Result<u64, SolanaCoreError> {
+    let d = compute_d(amp, reserve_in, reserve_out)?;
+    let new_reserve_in = reserve_in as u128 + amount_in as u128;
+    let new_reserve_out = compute_y(amp, new_reserve_in, d)?;
+    let amount_out = (reserve_out as u128).saturating_sub(new_reserve_out).saturating_sub(1);
+    u64::try_from(amount_out).map_err(|_| SolanaCoreError::OverFlowDetected)
+}
+
+// Input (after fee) needed for `amount_out`, rounded up by one extra unit in the pool's favour.
+#[inline(always)]
+pub fn stable_swap_in(amp: u64, amount_out: u64, reserve_in: u64, reserve_out: u64) -> Result<u64, SolanaCoreError> {
+    if amount_out >= reserve_out {
+        return Err(SolanaCoreError::MathError);
+    }
+    let d = compute_d(amp, reserve_in, reserve_out)?;
+    let new_reserve_in = compute_y(amp, (reserve_out - amount_out) as u128, d)?;
+    let amount_in = new_reserve_in
+        .checked_sub(reserve_in as u128)
+        .ok_or(SolanaCoreError::MathError)?
+        + 1;
+    u64::try_from(amount_in).map_err(|_| SolanaCoreError::OverFlowDetected)
+}
+
+/// Marginal price of the input token in output-token units, as `(numerator, denominator)`,
+/// both scaled to fit in 64 bits: `y * (4 * Ann * x^2 * y + D^3) / (x * (4 * Ann * x * y^2 + D^3))`.
+pub fn stable_spot_price(amp: u64, reserve_in: u64, reserve_out: u64) -> Result<(u128, u128), SolanaCoreError> {
+    let d = U256::from(compute_d(amp, reserve_in, reserve_out)?);
+    let (x, y) = (U256::from(reserve_in), U256::from(reserve_out));
+    let ann4 = ann(amp)? * U256::from(4u64);
+    let d3 = d * d * d;
+    let mut numerator = ann4 * x * x * y + d3;
+    let mut denominator = ann4 * x * y * y + d3;
+    // scale both down so the multiplications below cannot overflow
+    let limit = U256::MAX >> 64;
+    while numerator > limit || denominator > limit {
+        numerator >>= 1;
+        denominator >>= 1;
+    }
+    let (mut numerator, mut denominator) = (y * numerator, x * denominator);
+    while numerator > U256::from(u64::MAX) || denominator > U256::from(u64::MAX) {
+        numerator >>= 1;
+        denominator >>= 1;
+    }
+    Ok((to_u64(numerator)? as u128, to_u64(denominator)? as u128))
+}
+
+// Same invariant for `n` tokens, as used by multi-token pools:
+// `Ann * sum(x_i) + D = Ann * D + D^(n+1) / (n^n * prod(x_i))` with `Ann = A * n^n`.
+// D^(n+1) does not fit in 256 bits for large baskets, so the product term is built one
+// factor at a time, like Curve's reference implementation.
+
+#[inline(always)]
+fn ann_multi(amp: u64, n: usize) -> Result<U256, SolanaCoreError> {
+    if !(MIN_AMP..=MAX_AMP).contains(&amp) {
+        return Err(SolanaCoreError::InvalidAmp);
+    }
+    Ok(U256::from(amp) * U256::from(n as u64).pow(U256::from(n as u64)))
+}
+
+/// Stable-swap invariant D for `reserves` (two tokens or more).
+pub fn compute_d_multi(amp: u64, reserves: &[u64]) -> Result<u128, SolanaCoreError> {
+    let sum = reserves.iter().fold(U256::zero(), |sum, reserve| sum + U256::from(*reserve));
+    if sum.is_zero() {
+        return Ok(0);
+    }
+    if reserves.len() < 2 || reserves.contains(&0) {
+        return Err(SolanaCoreError::MathError);
+    }
+    let ann = ann_multi(amp, reserves.len())?;
+    let n = U256::from(reserves.len() as u64);
+    let mut d = sum;
+    for _ in 0..MAX_ITERATIONS {
+        let mut d_p = d;
+        for reserve in reserves {
+            d_p = d_p.checked_mul(d).ok_or(SolanaCoreError::OverFlowDetected)? / (U256::from(*reserve) * n);
+        }
+        let d_prev = d;
+        let numerator = (ann * sum)
+            .checked_add(d_p * n)
+            .and_then(|value| value.checked_mul(d))
+            .ok_or(SolanaCoreError::OverFlowDetected)?;
+        let denominator = (n + U256::one())
+            .checked_mul(d_p)
+            .and_then(|value| value.checked_add((ann - U256::one()) * d))
+            .ok_or(SolanaCoreError::OverFlowDetected)?;
+        d = numerator / denominator;
+        if converged(d, d_prev) {
+            return to_u128(d);
+        }
+    }
+    Err(SolanaCoreError::MathError)
+}
+
+/// Reserve of token `index` that keeps the invariant at `d`, given the other `reserves`
+/// (`reserves[index]` is ignored).
+pub fn compute_y_multi(amp: u64, reserves: &[u64], index: usize, d: u128) -> Result<u128, SolanaCoreError> {
+    if index >= reserves.len() {
+        return Err(SolanaCoreError::MathError);
+    }
+    let ann = ann_multi(amp, reserves.len())?;
+    let n = U256::from(reserves.len() as u64);
+    let d = U256::from(d);
+    // c = D^(n+1) / (n^n * prod(x_j) * Ann), b = sum(x_j) + D / Ann, over j != index
+    let (mut c, mut sum) = (d, U256::zero());
+    for (j, reserve) in reserves.iter().enumerate() {
+        if j == index {
+            continue;
+        }
+        if *reserve == 0 {
+            return Err(SolanaCoreError::MathError);
+        }
+        sum = sum + U256::from(*reserve);
+        c = c.checked_mul(d).ok_or(SolanaCoreError::OverFlowDetected)? / (U256::from(*reserve) * n);
+    }
+    c = c.checked_mul(d).ok_or(SolanaCoreError::OverFlowDetected)? / (ann * n);
+    let b = sum + d / ann;
+    let mut y = d;
+    for _ in 0..MAX_ITERATIONS {
+        let y_prev = y;
+        let denominator = (y * U256::from(2u64) + b).checked_sub(d).ok_or(SolanaCoreError::MathError)?;
+        if denominator.is_zero() {
+            return Err(SolanaCoreError::MathError);
+        }
+        y = (y * y + c) / denominator;
+        if converged(y, y_prev) {
+            return to_u128(y);
+        }
+    }
+    Err(SolanaCoreError::MathError)
+}
@@ -0,0 +1,283 @@
+use crate::{
+    errors::SolanaCoreError,
+    utils::{BPS_DENOMINATOR, U256},
+};
+
+// Uniswap v3 style concentrated-liquidity math on Q64.64 square-root prices.
+//
+// `price = 1.0001^tick` (token_1 per token_0) and `sqrt_price = sqrt(price) * 2^64`.
+// Inside a tick range the pool trades like `x * y = L^2` on virtual reserves
+// `x = L / sqrt_price`, `y = L * sqrt_price`. Amounts owed to the pool round up,
+// amounts paid out round down.
+
+pub const MIN_TICK: i32 = -443_636;
+pub const MAX_TICK: i32 = 443_636;
+// `sqrt_price_at_tick(MIN_TICK)` and `sqrt_price_at_tick(MAX_TICK)`
+pub const MIN_SQRT_PRICE: u128 = 4_295_048_017;
+pub const MAX_SQRT_PRICE: u128 = 79_226_673_515_401_279_992_447_579_062;
+
+const Q64: u32 = 64;
+
+// 2^128 / sqrt(1.0001)^(2^i), i.e. the Q128 factor for bit `i` of `|tick|`
+const TICK_RATIOS: [u128; 19] = [
+    0xfffcb933bd6fad37aa2d162d1a594001,
+    0xfff97272373d413259a46990580e2139,
+    0xfff2e50f5f656932ef12357cf3c7fdcb,
+    0xffe5caca7e10e4e61c3624eaa0941ccf,
+    0xffcb9843d60f6159c9db58835c926643,
+    0xff973b41fa98c081472e6896dfb254bf,
+    0xff2ea16466c96a3843ec78b326b52860,
+    0xfe5dee046a99a2a811c461f1969c3052,
+    0xfcbe86c7900a88aedcffc83b479aa3a3,
+    0xf987a7253ac413176f2b074cf7815e53,
+    0xf3392b0822b70005940c7a398e4b70f2,
+    0xe7159475a2c29b7443b29c7fa6e889d8,
+    0xd097f3bdfd2022b8845ad8f792aa5825,
+    0xa9f746462d870fdf8a65dc1f90e061e4,
+    0x70d869a156d2a1b890bb3df62baf32f6,
+    0x31be135f97d08fd981231505542fcfa5,
+    0x09aa508b5b7a84e1c677de54f3e99bc8,
+    0x005d6af8dedb81196699c329225ee604,
+    0x00002216e584f5fa1ea926041bedfe97,
+];
+
+#[inline(always)]
+fn to_u128(value: U256) -> Result<u128, SolanaCoreError> {
+    if value > U256::from(u128::MAX) {
+        return Err(SolanaCoreError::OverFlowDetected);
+    }
+    Ok(value.as_u128())
+}
+
+#[inline(always)]
+fn to_u64(value: U256) -> Result<u64, SolanaCoreError> {
+    if value > U256::from(u64::MAX) {
+        return Err(SolanaCoreError::OverFlowDetected);
+    }
+    Ok(value.as_u64())
+}
+
+#[inline(always)]
+fn div_ceil(numerator: U256, denominator: U256) -> U256 {
+    let quotient = numerator / denominator;
+    if quotient * denominator == numerator {
+        quotient
+    } else {
+        quotient + 1
+    }
+}
+
+/// `sqrt(1.0001^tick)` as Q64.64, rounded up.
+pub fn sqrt_price_at_tick(tick: i32) -> Result<u128, SolanaCoreError> {
+    if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        return Err(SolanaCoreError::InvalidTick);
+    }
+    let abs_tick = tick.unsigned_abs();
+    // Q128 ratio of 1 / sqrt(1.0001)^|tick|
+    let mut ratio = U256::one() << 128;
+    for (bit, factor) in TICK_RATIOS.iter().enumerate() {
+        if abs_tick & (1 << bit) != 0 {
+            ratio = (ratio * U256::from(*factor)) >> 128;
+        }
+    }
+    if tick > 0 {
+        ratio = U256::MAX / ratio;
+    }
+    to_u128(div_ceil(ratio, U256::one() << Q64))
+}
+
+/// Greatest tick whose square-root price is at most `sqrt_price`.
+pub fn tick_at_sqrt_price(sqrt_price: u128) -> Result<i32, SolanaCoreError> {
+    if !(MIN_SQRT_PRICE..=MAX_SQRT_PRICE).contains(&sqrt_price) {
+        return Err(SolanaCoreError::InvalidTick);
+    }
+    let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+    while low < high {
+        let mid = low + (high - low + 1) / 2;
+        if sqrt_price_at_tick(mid)? <= sqrt_price {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Ok(low)
+}
+
+/// token_0 between two square-root prices: `L * (sqrt_b - sqrt_a) / (sqrt_a * sqrt_b)`.
+pub fn amount_0_delta(sqrt_a: u128, sqrt_b: u128, liquidity: u128, round_up: bool) -> Result<u64, SolanaCoreError> {
+    let (sqrt_a, sqrt_b) = if sqrt_a > sqrt_b { (sqrt_b, sqrt_a) } else { (sqrt_a, sqrt_b) };
+    if sqrt_a == 0 {
+        return Err(SolanaCoreError::MathError);
+    }
+    let numerator = (U256::from(liquidity) << Q64) * U256::from(sqrt_b - sqrt_a);
+    let amount = if round_up {
+        div_ceil(div_ceil(numerator, U256::from(sqrt_b)), U256::from(sqrt_a))
+    } else {
+        numerator / U256::from(sqrt_b) / U256::from(sqrt_a)
+    };
+    to_u64(amount)
+}
+
+/// token_1 between two square-root prices: `L * (sqrt_b - sqrt_a)`.
+pub fn amount_1_delta(sqrt_a: u128, sqrt_b: u128, liquidity: u128, round_up: bool) -> Result<u64, SolanaCoreError> {
+    let (sqrt_a, sqrt_b) = if sqrt_a > sqrt_b { (sqrt_b, sqrt_a) } else { (sqrt_a, sqrt_b) };
+    let product = U256::from(liquidity) * U256::from(sqrt_b - sqrt_a);
+    let amount = if round_up {
+        div_ceil(product, U256::one() << Q64)
+    } else {
+        product >> Q64
+    };
+    to_u64(amount)
+}
+
+/// Square-root price after adding `amount_in` of the input token, rounded against the trader.
+pub fn next_sqrt_price_from_input(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount_in: u64,
+    zero_for_one: bool,
+) -> Result<u128, SolanaCoreError> {
+    if liquidity == 0 {
+        return Err(SolanaCoreError::InsufficientLiquidity);
+    }
+    if zero_for_one {
+        // token_0 in: sqrt' = L * sqrt / (L + amount * sqrt)
+        let liquidity = U256::from(liquidity) << Q64;
+        let denominator = liquidity + U256::from(amount_in) * U256::from(sqrt_price);
+        to_u128(div_ceil(liquidity * U256::from(sqrt_price), denominator))
+    } else {
+        // token_1 in: sqrt' = sqrt + amount / L
+        let delta = to_u128((U256::from(amount_in) << Q64) / U256::from(liquidity))?;
+        sqrt_price.checked_add(delta).ok_or(SolanaCoreError::OverFlowDetected)
+    }
+}
+
+/// Square-root price after removing `amount_out` of the output token, rounded against the trader.
+pub fn next_sqrt_price_from_output(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount_out: u64,
+    zero_for_one: bool,
+) -> Result<u128, SolanaCoreError> {
+    if liquidity == 0 {
+        return Err(SolanaCoreError::InsufficientLiquidity);
+    }
+    if zero_for_one {
+        // token_1 out: sqrt' = sqrt - amount / L
+        let delta = to_u128(div_ceil(U256::from(amount_out) << Q64, U256::from(liquidity)))?;
+        sqrt_price.checked_sub(delta).filter(|price| *price > 0).ok_or(SolanaCoreError::InsufficientLiquidity)
+    } else {
+        // token_0 out: sqrt' = L * sqrt / (L - amount * sqrt)
+        let liquidity = U256::from(liquidity) << Q64;
+        let product = U256::from(amount_out) * U256::from(sqrt_price);
+        if product >= liquidity {
+            return Err(SolanaCoreError::InsufficientLiquidity);
+        }
+        to_u128(div_ceil(liquidity * U256::from(sqrt_price), liquidity - product))
+    }
+}
+
+/// One step of a swap inside a single tick range.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SwapStep {
+    pub sqrt_price_next: u128,
+    // input net of fee
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+}
+
+/// Moves the price from `sqrt_price` towards `sqrt_price_target` with `liquidity`, consuming
+/// at most `amount_remaining` (input fee included for exact-in, output for exact-out).
+pub fn compute_swap_step(
+    sqrt_price: u128,
+    sqrt_price_target: u128,
+    liquidity: u128,
+    amount_remaining: u64,
+    fees_bps: u64,
+    exact_in: bool,
+) -> Result<SwapStep, SolanaCoreError> {
+    let zero_for_one = sqrt_price >= sqrt_price_target;
+    let amount_in_to_target = |round_up| match zero_for_one {
+        true => amount_0_delta(sqrt_price_target, sqrt_price, liquidity, round_up),
+        false => amount_1_delta(sqrt_price, sqrt_price_target, liquidity, round_up),
+    };
+    let amount_out_to_target = |round_up| match zero_for_one {
+        true => amount_1_delta(sqrt_price_target, sqrt_price, liquidity, round_up),
+        false => amount_0_delta(sqrt_price, sqrt_price_target, liquidity, round_up),
+    };
+
+    let sqrt_price_next = if exact_in {
+        let remaining_less_fee =
+            (amount_remaining as u128 * (BPS_DENOMINATOR - fees_bps) as u128 / BPS_DENOMINATOR as u128) as u64;
+        // an amount beyond u64 to the target means the target is out of reach
+        match amount_in_to_target(true) {
+            Ok(amount_in) if remaining_less_fee >= amount_in => sqrt_price_target,
+            _ => next_sqrt_price_from_input(sqrt_price, liquidity, remaining_less_fee, zero_for_one)?,
+        }
+    } else {
+        match amount_out_to_target(false) {
+            Ok(amount_out) if amount_remaining >= amount_out => sqrt_price_target,
+            _ => next_sqrt_price_from_output(sqrt_price, liquidity, amount_remaining, zero_for_one)?,
+        }
+    };
+
+    let (amount_in, mut amount_out) = if zero_for_one {
+        (
+            amount_0_delta(sqrt_price_next, sqrt_price, liquidity, true)?,
+            amount_1_delta(sqrt_price_next, sqrt_price, liquidity, false)?,
+        )
+    } else {
+        (
+            amount_1_delta(sqrt_price, sqrt_price_next, liquidity, true)?,
+            amount_0_delta(sqrt_price, sqrt_price_next, liquidity, false)?,
+        )
+    };
+    if !exact_in && amount_out > amount_remaining {
+        amount_out = amount_remaining;
+    }
+
+    let fee = if exact_in && sqrt_price_next != sqrt_price_target {
+        // the whole remainder is spent, whatever the curve did not take is fee
+        amount_remaining.checked_sub(amount_in).ok_or(SolanaCoreError::MathError)?
+    } else {
+        let fee = (amount_in as u128 * fees_bps as u128).div_ceil((BPS_DENOMINATOR - fees_bps) as u128);
+        let fee = u64::try_from(fee).map_err(|_| SolanaCoreError::OverFlowDetected)?;
+        // rounding both ways may overshoot the input by one unit
+        if exact_in {
+            fee.min(amount_remaining - amount_in)
+        } else {
+            fee
+        }
+    };
+
+    Ok(SwapStep {
+        sqrt_price_next,
+        amount_in,
+        amount_out,
+        fee,
+    })
+}
+
+/// Token amounts backing `liquidity` over `[tick_lower, tick_upper)` at the current price.
+pub fn amounts_for_liquidity(
+    sqrt_price: u128,
+    tick_current: i32,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<(u64, u64), SolanaCoreError> {
+    let sqrt_lower = sqrt_price_at_tick(tick_lower)?;
+    let sqrt_upper = sqrt_price_at_tick(tick_upper)?;
+    if tick_current < tick_lower {
+        Ok((amount_0_delta(sqrt_lower, sqrt_upper, liquidity, round_up)?, 0))
+    } else if tick_current >= tick_upper {
+        Ok((0, amount_1_delta(sqrt_lower, sqrt_upper, liquidity, round_up)?))
+    } else {
+        Ok((
+            amount_0_delta(sqrt_price, sqrt_upper, liquidity, round_up)?,
+            amount_1_delta(sqrt_lower, sqrt_price, liquidity, round_up)?,
+        ))
+    }
+}
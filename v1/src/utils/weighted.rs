@@ -0,0 +1,244 @@
+use crate::{
+    errors::SolanaCoreError,
+    utils::{BPS_DENOMINATOR, U256},
+};
+
+// Balancer-style weighted product invariant: `x^w_0 * y^w_1 = k` with `w_0 + w_1 = 1`.
+// Weights are stored in bps; the math runs on 18-decimal fixed point ("wad") with
+// `ln`/`exp` series in integer arithmetic. Powers are rounded by their error bound
+// in the pool's favour, and a single trade may move at most `MAX_RATIO_BPS` of a reserve.
+
+// 1.0 in 18-decimal fixed point
+pub const WAD: u128 = 1_000_000_000_000_000_000;
+const LN_2_WAD: i128 = 693_147_180_559_945_309;
+// relative error bound of `pow_wad`, used to round powers in the pool's favour
+pub const MAX_POW_RELATIVE_ERROR: u128 = 10_000; // 1e-14
+const SERIES_TERMS: i128 = 40;
+
+// smallest weight either side may carry (1 %)
+pub const MIN_WEIGHT_BPS: u64 = 100;
+// largest share of a reserve a single trade may add or remove (30 %)
+pub const MAX_RATIO_BPS: u64 = 3_000;
+
+/// Natural logarithm of `x` (wad, > 0), as a signed wad.
+pub fn ln_wad(x: u128) -> Result<i128, SolanaCoreError> {
+    if x == 0 {
+        return Err(SolanaCoreError::MathError);
+    }
+    // x = m * 2^k with m in [1, 2)
+    let mut k: i128 = 0;
+    let mut m = x;
+    while m >= 2 * WAD {
+        m >>= 1;
+        k += 1;
+    }
+    while m < WAD {
+        m <<= 1;
+        k -= 1;
+    }
+    // ln(m) = 2 * atanh(z) = 2 * (z + z^3/3 + z^5/5 + ...), z = (m - 1) / (m + 1) <= 1/3
+    let z = ((m - WAD) * WAD / (m + WAD)) as i128;
+    let z2 = z * z / WAD as i128;
+    let mut term = z;
+    let mut sum = z;
+    let mut n = 1;
+    while n < SERIES_TERMS && term != 0 {
+        term = term * z2 / WAD as i128;
+        n += 2;
+        sum += term / n;
+    }
+    Ok(k * LN_2_WAD + 2 * sum)
+}
+
+/// e^y for a signed wad `y`, as a wad.
+pub fn exp_wad(y: i128) -> Result<u128, SolanaCoreError> {
+    // e^y = 2^k * e^r with |r| <= ln(2) / 2
+    let k = (y + if y >= 0 { LN_2_WAD / 2 } else { -LN_2_WAD / 2 }) / LN_2_WAD;
+    if k > 64 {
+        return Err(SolanaCoreError::OverFlowDetected);
+    }
+    if k < -64 {
+        return Ok(0);
+    }
+    let r = y - k * LN_2_WAD;
+    // Taylor series, |r| < 0.35
+    let mut term = WAD as i128;
+    let mut sum = WAD as i128;
+    let mut n = 1;
+    while n < SERIES_TERMS && term != 0 {
+        term = term * r / WAD as i128 / n;
+        sum += term;
+        n += 1;
+    }
+    let sum = sum as u128;
+    Ok(if k >= 0 { sum << k } else { sum >> -k })
+}
+
+/// `base ^ exponent` for wads, accurate to about `MAX_POW_RELATIVE_ERROR`.
+pub fn pow_wad(base: u128, exponent: u128) -> Result<u128, SolanaCoreError> {
+    if exponent == 0 {
+        return Ok(WAD);
+    }
+    if base == 0 {
+        return Ok(0);
+    }
+    if exponent == WAD {
+        return Ok(base);
+    }
+    let ln = ln_wad(base)?;
+    let exponent = i128::try_from(exponent).map_err(|_| SolanaCoreError::OverFlowDetected)?;
+    let product = ln.checked_mul(exponent).ok_or(SolanaCoreError::OverFlowDetected)? / WAD as i128;
+    exp_wad(product)
+}
+
+#[inline(always)]
+fn pow_up(base: u128, exponent: u128) -> Result<u128, SolanaCoreError> {
+    let raw = pow_wad(base, exponent)?;
+    Ok(raw + raw / (WAD / MAX_POW_RELATIVE_ERROR) + 1)
+}
+
+#[inline(always)]
+fn pow_down(base: u128, exponent: u128) -> Result<u128, SolanaCoreError> {
+    let raw = pow_wad(base, exponent)?;
+    Ok(raw.saturating_sub(raw / (WAD / MAX_POW_RELATIVE_ERROR) + 1))
+}
+
+#[inline(always)]
+fn check_ratio(amount: u64, reserve: u64) -> Result<(), SolanaCoreError> {
+    if amount as u128 * BPS_DENOMINATOR as u128 > reserve as u128 * MAX_RATIO_BPS as u128 {
+        return Err(SolanaCoreError::TradeTooLarge);
+    }
+    Ok(())
+}
+
+/// `out = reserve_out * (1 - (reserve_in / (reserve_in + amount_in)) ^ (weight_in / weight_out))`
+pub fn weighted_swap_out(
+    amount_in: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    weight_in_bps: u64,
+    weight_out_bps: u64,
+) -> Result<u64, SolanaCoreError> {
+    check_ratio(amount_in, reserve_in)?;
+    if reserve_in == 0 || weight_out_bps == 0 {
+        return Err(SolanaCoreError::MathError);
+    }
+    // base rounded up and exponent rounded down both shrink the output
+    let base = (reserve_in as u128 * WAD).div_ceil(reserve_in as u128 + amount_in as u128);
+    let exponent = weight_in_bps as u128 * WAD / weight_out_bps as u128;
+    let power = pow_up(base, exponent)?;
+    if power >= WAD {
+        return Ok(0);
+    }
+    u64::try_from(reserve_out as u128 * (WAD - power) / WAD).map_err(|_| SolanaCoreError::OverFlowDetected)
+}
+
+/// `in = reserve_in * ((reserve_out / (reserve_out - amount_out)) ^ (weight_out / weight_in) - 1)`
+pub fn weighted_swap_in(
+    amount_out: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    weight_in_bps: u64,
+    weight_out_bps: u64,
+) -> Result<u64, SolanaCoreError> {
+    check_ratio(amount_out, reserve_out)?;
+    if amount_out >= reserve_out || weight_in_bps == 0 {
+        return Err(SolanaCoreError::MathError);
+    }
+    // base and exponent rounded up both grow the input
+    let base = (reserve_out as u128 * WAD).div_ceil((reserve_out - amount_out) as u128);
+    let exponent = (weight_out_bps as u128 * WAD).div_ceil(weight_in_bps as u128);
+    let power = pow_up(base, exponent)?;
+    let amount_in = (reserve_in as u128)
+        .checked_mul(power - WAD)
+        .ok_or(SolanaCoreError::OverFlowDetected)?
+        .div_ceil(WAD);
+    u64::try_from(amount_in).map_err(|_| SolanaCoreError::OverFlowDetected)
+}
+
+/// Marginal price of the input token in output-token units:
+/// `(reserve_out / weight_out) / (reserve_in / weight_in)`, both parts scaled to 64 bits.
+pub fn weighted_spot_price(
+    reserve_in: u64,
+    reserve_out: u64,
+    weight_in_bps: u64,
+    weight_out_bps: u64,
+) -> (u128, u128) {
+    let mut numerator = reserve_out as u128 * weight_in_bps as u128;
+    let mut denominator = reserve_in as u128 * weight_out_bps as u128;
+    while numerator > u64::MAX as u128 || denominator > u64::MAX as u128 {
+        numerator >>= 1;
+        denominator >>= 1;
+    }
+    (numerator, denominator)
+}
+
+/// Weighted geometric mean `x^w_0 * y^w_1`, the invariant value used as initial LP supply.
+pub fn weighted_invariant(amount_0: u64, amount_1: u64, weight_0_bps: u64) -> Result<u64, SolanaCoreError> {
+    let weight_1_bps = BPS_DENOMINATOR.checked_sub(weight_0_bps).ok_or(SolanaCoreError::MathError)?;
+    let scale = |weight: u64| weight as u128 * WAD / BPS_DENOMINATOR as u128;
+    let factor_0 = pow_wad(amount_0 as u128 * WAD, scale(weight_0_bps))?;
+    let factor_1 = pow_wad(amount_1 as u128 * WAD, scale(weight_1_bps))?;
+    let invariant = U256::from(factor_0) * U256::from(factor_1) / (U256::from(WAD) * U256::from(WAD));
+    if invariant > U256::from(u64::MAX) {
+        return Err(SolanaCoreError::OverFlowDetected);
+    }
+    Ok(invariant.as_u64())
+}
+
+/// `prod(reserve_i ^ weight_i)` over any number of tokens, weights in bps summing to 10_000.
+pub fn weighted_invariant_multi(reserves: &[u64], weights_bps: &[u64]) -> Result<u64, SolanaCoreError> {
+    let mut ln_sum: i128 = 0;
+    for (reserve, weight_bps) in reserves.iter().zip(weights_bps) {
+        let ln = ln_wad(*reserve as u128 * WAD)?;
+        let weight = (*weight_bps as u128 * WAD / BPS_DENOMINATOR as u128) as i128;
+        ln_sum += ln.checked_mul(weight).ok_or(SolanaCoreError::OverFlowDetected)? / WAD as i128;
+    }
+    u64::try_from(exp_wad(ln_sum)? / WAD).map_err(|_| SolanaCoreError::OverFlowDetected)
+}
+
+/// LP minted for adding `amount_in` (after fee) of a single token:
+/// `lp_supply * ((1 + amount_in / reserve) ^ weight - 1)`.
+pub fn weighted_deposit_single(
+    amount_in: u64,
+    reserve: u64,
+    weight_bps: u64,
+    lp_supply: u64,
+) -> Result<u64, SolanaCoreError> {
+    check_ratio(amount_in, reserve)?;
+    if reserve == 0 {
+        return Err(SolanaCoreError::MathError);
+    }
+    // base and exponent rounded down, power rounded down: fewer LP tokens
+    let base = (reserve as u128 + amount_in as u128) * WAD / reserve as u128;
+    let exponent = weight_bps as u128 * WAD / BPS_DENOMINATOR as u128;
+    let power = pow_down(base, exponent)?;
+    let lp = (lp_supply as u128)
+        .checked_mul(power.saturating_sub(WAD))
+        .ok_or(SolanaCoreError::OverFlowDetected)?
+        / WAD;
+    u64::try_from(lp).map_err(|_| SolanaCoreError::OverFlowDetected)
+}
+
+/// Amount of a single token (before fee) returned for burning `lp_amount`:
+/// `reserve * (1 - (1 - lp_amount / lp_supply) ^ (1 / weight))`.
+pub fn weighted_withdraw_single(
+    lp_amount: u64,
+    reserve: u64,
+    weight_bps: u64,
+    lp_supply: u64,
+) -> Result<u64, SolanaCoreError> {
+    if lp_amount >= lp_supply || weight_bps == 0 {
+        return Err(SolanaCoreError::MathError);
+    }
+    // base rounded up, exponent rounded down and power rounded up: a smaller output
+    let base = ((lp_supply - lp_amount) as u128 * WAD).div_ceil(lp_supply as u128);
+    let exponent = BPS_DENOMINATOR as u128 * WAD / weight_bps as u128;
+    let power = pow_up(base, exponent)?;
+    if power >= WAD {
+        return Ok(0);
+    }
+    let amount_out = u64::try_from(reserve as u128 * (WAD - power) / WAD).map_err(|_| SolanaCoreError::OverFlowDetected)?;
+    check_ratio(amount_out, reserve)?;
+    Ok(amount_out)
+}